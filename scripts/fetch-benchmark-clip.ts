@@ -0,0 +1,37 @@
+#!/usr/bin/env bun
+/**
+ * Script to fetch the reference recording the model benchmark runs on by
+ * default into src-tauri/resources/benchmark. It is the JFK inaugural address
+ * excerpt whisper.cpp ships as a sample (public domain); its transcript is
+ * kept next to it in jfk.txt.
+ *
+ * Usage:
+ *   bun ./scripts/fetch-benchmark-clip.ts
+ */
+
+import { existsSync, writeFileSync } from "node:fs";
+import { join } from "node:path";
+
+const rootDir = join(import.meta.dirname, "..");
+const TARGET = join(rootDir, "src-tauri/resources/benchmark/jfk.wav");
+const URL =
+  "https://raw.githubusercontent.com/ggml-org/whisper.cpp/master/samples/jfk.wav";
+
+async function main(): Promise<void> {
+  if (existsSync(TARGET)) {
+    console.log(`✓ ${TARGET} is already there`);
+    return;
+  }
+  console.log(`Downloading ${URL}`);
+  const response = await fetch(URL);
+  if (!response.ok) {
+    throw new Error(`HTTP ${response.status}`);
+  }
+  writeFileSync(TARGET, Buffer.from(await response.arrayBuffer()));
+  console.log(`✓ Saved ${TARGET}`);
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
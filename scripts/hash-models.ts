@@ -0,0 +1,85 @@
+#!/usr/bin/env bun
+/**
+ * Script to fill in the SHA-256 of every model in the built-in catalog
+ * (src-tauri/resources/model_catalog.json). Downloads each model from the
+ * first URL that works and hashes the file as served: the archive, for
+ * models that are extracted into a directory.
+ *
+ * Usage:
+ *   bun ./scripts/hash-models.ts          # only models without a hash
+ *   bun ./scripts/hash-models.ts --all    # re-hash every model
+ */
+
+import { createHash } from "node:crypto";
+import { readFileSync, writeFileSync } from "node:fs";
+import { join } from "node:path";
+
+const rootDir = join(import.meta.dirname, "..");
+const CATALOG = join(rootDir, "src-tauri/resources/model_catalog.json");
+
+interface CatalogEntry {
+  id: string;
+  urls: string[];
+  sha256?: string;
+}
+
+async function hashUrl(url: string): Promise<string> {
+  const response = await fetch(url);
+  if (!(response.ok && response.body)) {
+    throw new Error(`HTTP ${response.status}`);
+  }
+  const hash = createHash("sha256");
+  for await (const chunk of response.body) {
+    hash.update(chunk);
+  }
+  return hash.digest("hex");
+}
+
+async function hashEntry(entry: CatalogEntry): Promise<string> {
+  for (const url of entry.urls) {
+    try {
+      console.log(`  downloading ${url}`);
+      return await hashUrl(url);
+    } catch (error) {
+      console.warn(`  failed: ${error}`);
+    }
+  }
+  throw new Error(`No URL of ${entry.id} could be downloaded`);
+}
+
+/** Set the hash in the catalog text, keeping its hand-written layout. */
+function setHash(text: string, id: string, sha256: string): string {
+  const start = text.indexOf(`"id": "${id}"`);
+  const end = text.indexOf("}", start);
+  const block = text.slice(start, end);
+  const updated = block.includes('"sha256"')
+    ? block.replace(/"sha256": "[^"]*"/, `"sha256": "${sha256}"`)
+    : block.replace(
+        /(\n\s*)"size_mb"/,
+        `$1"sha256": "${sha256}",$1"size_mb"`
+      );
+  return text.slice(0, start) + updated + text.slice(end);
+}
+
+async function main(): Promise<void> {
+  const all = process.argv.includes("--all");
+  let text = readFileSync(CATALOG, "utf-8");
+  const { models } = JSON.parse(text) as { models: CatalogEntry[] };
+
+  for (const entry of models) {
+    if (entry.sha256 && !all) {
+      continue;
+    }
+    console.log(`Hashing ${entry.id}`);
+    const sha256 = await hashEntry(entry);
+    console.log(`✓ ${entry.id}: ${sha256}`);
+    text = setHash(text, entry.id, sha256);
+    // Save as we go, so an interrupted run keeps what it has
+    writeFileSync(CATALOG, text);
+  }
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exit(1);
+});
@@ -1,12 +1,12 @@
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::audio_toolkit::detect_text_language;
+use crate::audio_toolkit::vad::UtteranceEvent;
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{Transcript, TranscriptionManager};
 use crate::managers::tts::TtsManager;
-use crate::overlay::{
-    show_recording_overlay, show_tool_overlay, show_transcribing_overlay, show_warning_overlay,
-};
-use crate::settings::{get_settings, AppSettings};
+use crate::overlay::{show_recording_overlay, show_tool_overlay, show_transcribing_overlay};
+use crate::settings::{get_settings, AppSettings, PROFILE_BINDING_PREFIX};
 use crate::tools::{self, PostProcessOutcome};
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils;
@@ -21,7 +21,7 @@ use log::{debug, error, info};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Instant;
 use tauri::AppHandle;
 use tauri::Manager;
@@ -41,15 +41,26 @@ pub(crate) static TRANSCRIPTION_TASK: Lazy<Mutex<Option<tauri::async_runtime::Jo
 pub trait ShortcutAction: Send + Sync {
     fn start(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
     fn stop(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
+
+    /// Whether each press switches the action on or off, even in push-to-talk
+    /// mode. Only `start` is called for these.
+    fn is_toggle(&self) -> bool {
+        false
+    }
 }
 
 // Transcribe Action
 struct TranscribeAction;
 
+/// Run the selected post-process prompt over a transcription.
+///
+/// Besides the transcription, the prompt can reference the spoken language
+/// (an ISO 639-1 code, or "unknown") as `${language}` or `@language`.
 pub async fn maybe_post_process_transcription(
     app: &AppHandle,
     settings: &AppSettings,
     transcription: &str,
+    language: Option<&str>,
 ) -> PostProcessOutcome {
     if !settings.post_process_enabled {
         return PostProcessOutcome::Empty;
@@ -139,6 +150,9 @@ pub async fn maybe_post_process_transcription(
         .replace("${output}", transcription)
         .replace("@output", transcription);
 
+    let processed_prompt = insert_language(&processed_prompt, language);
+    let prompt = insert_language(&prompt, language);
+
     // Log the processed prompt (after variable substitution)
     log::info!(
         "[Post-Process] Prompt with transcript inserted:\n{}",
@@ -378,6 +392,34 @@ pub async fn maybe_post_process_transcription(
     PostProcessOutcome::Empty
 }
 
+/// Language of `text`, the text read aloud, for choosing a TTS voice.
+///
+/// A post-processing prompt may have translated it, so it's guessed from the
+/// text itself; the spoken language is only a fallback for text without letters.
+pub(crate) fn output_language(
+    settings: &AppSettings,
+    spoken: Option<&str>,
+    text: &str,
+) -> Option<String> {
+    if let Some(language) = detect_text_language(text) {
+        Some(language.to_string())
+    } else if settings.translate_to_english {
+        Some("en".to_string())
+    } else {
+        spoken.map(str::to_string)
+    }
+}
+
+/// Replace the language placeholders in a prompt.
+fn insert_language(prompt: &str, language: Option<&str>) -> String {
+    let language = language.unwrap_or("unknown");
+    let mention_regex = regex::Regex::new(r"\[[^\]]*\]\(mention:language\)").unwrap();
+    mention_regex
+        .replace_all(prompt, language)
+        .replace("${language}", language)
+        .replace("@language", language)
+}
+
 async fn maybe_convert_chinese_variant(
     settings: &AppSettings,
     transcription: &str,
@@ -421,6 +463,175 @@ async fn maybe_convert_chinese_variant(
     }
 }
 
+/// Post-process a finished dictation, save it to history and paste it, unless
+/// the operation was cancelled or superseded while it was being transcribed.
+async fn deliver_transcription(
+    ah: AppHandle,
+    binding_id: &str,
+    samples: Vec<f32>,
+    result: anyhow::Result<Transcript>,
+    transcription_time: Instant,
+    gen: u64,
+) {
+    let hm = Arc::clone(&ah.state::<Arc<HistoryManager>>());
+    let tts_manager = Arc::clone(&ah.state::<Arc<TtsManager>>());
+
+    match result {
+        Ok(transcript) => {
+            let transcription = transcript.text.clone();
+            debug!(
+                "Transcription completed in {:?}: '{}'",
+                transcription_time.elapsed(),
+                transcription
+            );
+            if !transcription.is_empty() {
+                let settings = get_settings(&ah).for_binding(binding_id);
+                let mut final_text = transcription.clone();
+                let mut post_processed_text: Option<String> = None;
+                let mut post_process_prompt: Option<String> = None;
+
+                if let Some(converted_text) =
+                    maybe_convert_chinese_variant(&settings, &transcription).await
+                {
+                    final_text = converted_text.clone();
+                    post_processed_text = Some(converted_text);
+                } else {
+                    match maybe_post_process_transcription(
+                        &ah,
+                        &settings,
+                        &transcription,
+                        transcript.language.as_deref(),
+                    )
+                    .await
+                    {
+                        PostProcessOutcome::Text(processed_text) => {
+                            final_text = processed_text.clone();
+                            post_processed_text = Some(processed_text);
+
+                            // Get the prompt that was used
+                            if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
+                                if let Some(prompt) = settings
+                                    .post_process_prompts
+                                    .iter()
+                                    .find(|p| &p.id == prompt_id)
+                                {
+                                    post_process_prompt = Some(prompt.prompt.clone());
+                                }
+                            }
+                        }
+                        PostProcessOutcome::ToolExecuted(message) => {
+                            // Save to history (original transcription only)
+                            let hm_clone = Arc::clone(&hm);
+                            let transcription_for_history = transcript.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = hm_clone
+                                    .save_transcription(
+                                        samples,
+                                        transcription_for_history,
+                                        None,
+                                        None,
+                                    )
+                                    .await
+                                {
+                                    error!("Failed to save transcription to history: {}", e);
+                                }
+                            });
+
+                            // Show tool result in overlay, do NOT paste
+                            if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
+                                show_tool_overlay(&ah, &message);
+                                change_tray_icon(&ah, idle_tray_icon(&ah));
+                            }
+                            return;
+                        }
+                        PostProcessOutcome::Empty => {
+                            // No-op, original transcription used as final_text
+                        }
+                    }
+                }
+
+                // Trigger TTS if enabled and post-processing was successful
+                if settings.tts_enabled && post_processed_text.is_some() {
+                    let tts_manager_clone = tts_manager.clone();
+                    let text_to_speak = final_text.clone();
+                    let tts_language =
+                        output_language(&settings, transcript.language.as_deref(), &text_to_speak);
+                    info!("Triggering TTS with text: {}", text_to_speak);
+                    std::thread::spawn(move || {
+                        if let Err(e) =
+                            tts_manager_clone.speak(&text_to_speak, tts_language.as_deref())
+                        {
+                            error!("TTS failed: {}", e);
+                        }
+                    });
+                }
+
+                // Save to history with post-processed text and prompt
+                let hm_clone = Arc::clone(&hm);
+                let transcription_for_history = transcript;
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = hm_clone
+                        .save_transcription(
+                            samples,
+                            transcription_for_history,
+                            post_processed_text,
+                            post_process_prompt,
+                        )
+                        .await
+                    {
+                        error!("Failed to save transcription to history: {}", e);
+                    }
+                });
+
+                // Check if this operation is still current before pasting
+                if OPERATION_GENERATION.load(Ordering::SeqCst) != gen {
+                    debug!("Operation became stale during transcription, skipping paste");
+                    return;
+                }
+
+                // Paste the final text (either processed or original)
+                let ah_clone = ah.clone();
+                let paste_time = Instant::now();
+                ah.run_on_main_thread(move || {
+                    match utils::paste(final_text, ah_clone.clone()) {
+                        Ok(()) => debug!("Text pasted successfully in {:?}", paste_time.elapsed()),
+                        Err(e) => error!("Failed to paste transcription: {}", e),
+                    }
+                    // Hide the overlay after transcription is complete
+                    utils::hide_recording_overlay(&ah_clone);
+                    change_tray_icon(&ah_clone, idle_tray_icon(&ah_clone));
+                })
+                .unwrap_or_else(|e| {
+                    error!("Failed to run paste on main thread: {:?}", e);
+                    if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
+                        utils::hide_recording_overlay(&ah);
+                        change_tray_icon(&ah, idle_tray_icon(&ah));
+                    }
+                });
+            } else if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
+                utils::hide_recording_overlay(&ah);
+                change_tray_icon(&ah, idle_tray_icon(&ah));
+            }
+        }
+        Err(err) => {
+            debug!("Global Shortcut Transcription error: {}", err);
+            if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
+                utils::hide_recording_overlay(&ah);
+                change_tray_icon(&ah, idle_tray_icon(&ah));
+            }
+        }
+    }
+}
+
+/// Tray icon once a dictation is done: hands-free mode is still listening.
+fn idle_tray_icon(app: &AppHandle) -> TrayIconState {
+    if app.state::<Arc<AudioRecordingManager>>().is_hands_free() {
+        TrayIconState::Recording
+    } else {
+        TrayIconState::Idle
+    }
+}
+
 impl ShortcutAction for TranscribeAction {
     fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
         let start_time = Instant::now();
@@ -429,22 +640,10 @@ impl ShortcutAction for TranscribeAction {
         // Increment generation to invalidate any in-flight operations from previous recordings
         OPERATION_GENERATION.fetch_add(1, Ordering::SeqCst);
 
-        // Check if a file transcription is currently active
-        if crate::is_file_transcription_active() {
-            debug!("File transcription in progress - showing warning overlay");
-            show_warning_overlay(app, "File transcription in progress. Please wait...");
-
-            // Reset the toggle state so next press will call start() again
-            let toggle_state_manager = app.state::<ManagedToggleState>();
-            if let Ok(mut states) = toggle_state_manager.lock() {
-                states.active_toggles.insert(binding_id.to_string(), false);
-            }
-            return;
-        }
-
-        // Load model in the background
+        // Resolve the binding's profile and load its model in the background
+        let settings = get_settings(app);
         let tm = app.state::<Arc<TranscriptionManager>>();
-        tm.initiate_model_load();
+        tm.set_dictation_profile(settings.profile_for_binding(binding_id).cloned());
 
         let binding_id = binding_id.to_string();
         change_tray_icon(app, TrayIconState::Recording);
@@ -453,7 +652,6 @@ impl ShortcutAction for TranscribeAction {
         let rm = app.state::<Arc<AudioRecordingManager>>();
 
         // Get the microphone mode to determine audio feedback timing
-        let settings = get_settings(app);
         let is_always_on = settings.always_on_microphone;
         debug!("Microphone mode - always_on: {}", is_always_on);
 
@@ -529,8 +727,6 @@ impl ShortcutAction for TranscribeAction {
         let ah = app.clone();
         let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
         let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
-        let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
-        let tts_manager = Arc::clone(&app.state::<Arc<TtsManager>>());
 
         change_tray_icon(app, TrayIconState::Transcribing);
         show_transcribing_overlay(app);
@@ -570,147 +766,13 @@ impl ShortcutAction for TranscribeAction {
                     samples.len() as f32 / 16000.0
                 );
 
-                // Final transcription: transcribe ALL audio for complete result
-                // (streaming preview is limited, but final result is complete)
+                // Final transcription: keep the words committed while streaming
+                // and only transcribe the audio after them
                 let transcription_time = Instant::now();
-                let samples_clone = samples.clone(); // Clone full samples for history saving
-
-                match tm.transcribe(samples) {
-                    Ok(transcription) => {
-                        debug!(
-                            "Transcription completed in {:?}: '{}'",
-                            transcription_time.elapsed(),
-                            transcription
-                        );
-                        if !transcription.is_empty() {
-                            let settings = get_settings(&ah);
-                            let mut final_text = transcription.clone();
-                            let mut post_processed_text: Option<String> = None;
-                            let mut post_process_prompt: Option<String> = None;
-
-                            if let Some(converted_text) =
-                                maybe_convert_chinese_variant(&settings, &transcription).await
-                            {
-                                final_text = converted_text.clone();
-                                post_processed_text = Some(converted_text);
-                            } else {
-                                match maybe_post_process_transcription(&ah, &settings, &transcription).await {
-                                    PostProcessOutcome::Text(processed_text) => {
-                                        final_text = processed_text.clone();
-                                        post_processed_text = Some(processed_text);
-
-                                        // Get the prompt that was used
-                                        if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-                                            if let Some(prompt) = settings
-                                                .post_process_prompts
-                                                .iter()
-                                                .find(|p| &p.id == prompt_id)
-                                            {
-                                                post_process_prompt = Some(prompt.prompt.clone());
-                                            }
-                                        }
-                                    }
-                                    PostProcessOutcome::ToolExecuted(message) => {
-                                        // Save to history (original transcription only)
-                                        let hm_clone = Arc::clone(&hm);
-                                        let transcription_for_history = transcription.clone();
-                                        tauri::async_runtime::spawn(async move {
-                                            if let Err(e) = hm_clone
-                                                .save_transcription(
-                                                    samples_clone,
-                                                    transcription_for_history,
-                                                    None,
-                                                    None,
-                                                )
-                                                .await
-                                            {
-                                                error!("Failed to save transcription to history: {}", e);
-                                            }
-                                        });
-
-                                        // Show tool result in overlay, do NOT paste
-                                        if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
-                                            show_tool_overlay(&ah, &message);
-                                            change_tray_icon(&ah, TrayIconState::Idle);
-                                        }
-                                        return;
-                                    }
-                                    PostProcessOutcome::Empty => {
-                                        // No-op, original transcription used as final_text
-                                    }
-                                }
-                            }
-
-                            // Trigger TTS if enabled and post-processing was successful
-                            if settings.tts_enabled && post_processed_text.is_some() {
-                                let tts_manager_clone = tts_manager.clone();
-                                let text_to_speak = final_text.clone();
-                                info!("Triggering TTS with text: {}", text_to_speak);
-                                std::thread::spawn(move || {
-                                    if let Err(e) = tts_manager_clone.speak(&text_to_speak) {
-                                        error!("TTS failed: {}", e);
-                                    }
-                                });
-                            }
-
-                            // Save to history with post-processed text and prompt
-                            let hm_clone = Arc::clone(&hm);
-                            let transcription_for_history = transcription.clone();
-                            tauri::async_runtime::spawn(async move {
-                                if let Err(e) = hm_clone
-                                    .save_transcription(
-                                        samples_clone,
-                                        transcription_for_history,
-                                        post_processed_text,
-                                        post_process_prompt,
-                                    )
-                                    .await
-                                {
-                                    error!("Failed to save transcription to history: {}", e);
-                                }
-                            });
-
-                            // Check if this operation is still current before pasting
-                            if OPERATION_GENERATION.load(Ordering::SeqCst) != gen {
-                                debug!("Operation became stale during transcription, skipping paste");
-                                return;
-                            }
-
-                            // Paste the final text (either processed or original)
-                            let ah_clone = ah.clone();
-                            let paste_time = Instant::now();
-                            ah.run_on_main_thread(move || {
-                                match utils::paste(final_text, ah_clone.clone()) {
-                                    Ok(()) => debug!(
-                                        "Text pasted successfully in {:?}",
-                                        paste_time.elapsed()
-                                    ),
-                                    Err(e) => error!("Failed to paste transcription: {}", e),
-                                }
-                                // Hide the overlay after transcription is complete
-                                utils::hide_recording_overlay(&ah_clone);
-                                change_tray_icon(&ah_clone, TrayIconState::Idle);
-                            })
-                            .unwrap_or_else(|e| {
-                                error!("Failed to run paste on main thread: {:?}", e);
-                                if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
-                                    utils::hide_recording_overlay(&ah);
-                                    change_tray_icon(&ah, TrayIconState::Idle);
-                                }
-                            });
-                        } else if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                        }
-                    }
-                    Err(err) => {
-                        debug!("Global Shortcut Transcription error: {}", err);
-                        if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
-                            utils::hide_recording_overlay(&ah);
-                            change_tray_icon(&ah, TrayIconState::Idle);
-                        }
-                    }
-                }
+                let speech_ms = rm_for_task.last_speech_ms();
+                let result = tm.transcribe_streamed(samples.clone(), speech_ms);
+                deliver_transcription(ah, &binding_id, samples, result, transcription_time, gen)
+                    .await;
             } else {
                 debug!("No samples retrieved from recording stop");
                 if OPERATION_GENERATION.load(Ordering::SeqCst) == gen {
@@ -732,6 +794,84 @@ impl ShortcutAction for TranscribeAction {
     }
 }
 
+// Hands-free Action
+struct HandsFreeAction;
+
+impl ShortcutAction for HandsFreeAction {
+    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+        let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
+        if rm.is_hands_free() {
+            rm.stop_hands_free();
+            play_feedback_sound(app, SoundType::Stop);
+            return;
+        }
+
+        let (events_tx, events_rx) = mpsc::channel();
+        if let Err(e) = rm.start_hands_free(events_tx) {
+            error!("Failed to start hands-free dictation: {}", e);
+            return;
+        }
+
+        // Picking the profile loads its model, so the first utterance isn't
+        // held up by it
+        let settings = get_settings(app);
+        let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+        tm.set_dictation_profile(settings.profile_for_binding(binding_id).cloned());
+
+        change_tray_icon(app, TrayIconState::Recording);
+        play_feedback_sound(app, SoundType::Start);
+
+        let ah = app.clone();
+        let binding_id = binding_id.to_string();
+        std::thread::spawn(move || {
+            // Utterances are transcribed one at a time so they are pasted in order
+            while let Ok(event) = events_rx.recv() {
+                match event {
+                    UtteranceEvent::Started => show_recording_overlay(&ah),
+                    UtteranceEvent::Discarded => {
+                        debug!("Hands-free: ignoring an utterance with too little speech");
+                        utils::hide_recording_overlay(&ah);
+                    }
+                    UtteranceEvent::Finished { samples, speech_ms } => {
+                        debug!(
+                            "Hands-free: utterance of {:.1}s with {}ms of speech",
+                            samples.len() as f32 / 16000.0,
+                            speech_ms
+                        );
+                        show_transcribing_overlay(&ah);
+                        let gen = OPERATION_GENERATION.load(Ordering::SeqCst);
+                        let transcription_time = Instant::now();
+                        let result =
+                            tm.transcribe_dictation(samples.clone(), Some(speech_ms as i64));
+                        tauri::async_runtime::block_on(deliver_transcription(
+                            ah.clone(),
+                            &binding_id,
+                            samples,
+                            result,
+                            transcription_time,
+                            gen,
+                        ));
+                    }
+                }
+            }
+
+            debug!("Hands-free dictation finished");
+            if !rm.is_hands_free() {
+                utils::hide_recording_overlay(&ah);
+                change_tray_icon(&ah, TrayIconState::Idle);
+            }
+        });
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Toggled off by the next press, see `is_toggle`
+    }
+
+    fn is_toggle(&self) -> bool {
+        true
+    }
+}
+
 // Test Action
 struct TestAction;
 
@@ -762,6 +902,10 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe".to_string(),
         Arc::new(TranscribeAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "hands_free".to_string(),
+        Arc::new(HandsFreeAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
@@ -769,10 +913,20 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
     map
 });
 
+/// Action run by a binding.
+pub fn action_for_binding(binding_id: &str) -> Option<&'static Arc<dyn ShortcutAction>> {
+    ACTION_MAP.get(binding_id).or_else(|| {
+        binding_id
+            .strip_prefix(PROFILE_BINDING_PREFIX)
+            .filter(|suffix| !suffix.is_empty())
+            .and_then(|_| ACTION_MAP.get("transcribe"))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::settings::{get_default_settings, LLMPrompt};
+    use crate::settings::{get_default_settings, LLMPrompt, BUILT_IN_BINDINGS};
     use crate::tools::PostProcessOutcome;
 
     /// Helper: returns settings with a fully-configured post-processing setup.
@@ -823,6 +977,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn every_built_in_binding_has_an_action() {
+        for id in BUILT_IN_BINDINGS {
+            assert!(ACTION_MAP.contains_key(id), "no action for binding {}", id);
+        }
+        assert_eq!(ACTION_MAP.len(), BUILT_IN_BINDINGS.len());
+    }
+
     #[test]
     fn disabled_returns_empty() {
         let mut s = settings_with_post_process();
@@ -884,4 +1046,37 @@ mod tests {
         assert!(matches!(tool, PostProcessOutcome::ToolExecuted(_)));
         assert!(matches!(empty, PostProcessOutcome::Empty));
     }
+
+    #[test]
+    fn language_placeholders_are_replaced() {
+        let prompt = "Reply in [language](mention:language), ${language} or @language.";
+        assert_eq!(
+            insert_language(prompt, Some("fr")),
+            "Reply in fr, fr or fr."
+        );
+        assert_eq!(insert_language("${language}", None), "unknown");
+    }
+
+    #[test]
+    fn output_language_is_english_when_translating() {
+        let mut s = get_default_settings();
+        assert_eq!(output_language(&s, Some("de"), "42").as_deref(), Some("de"));
+        s.translate_to_english = true;
+        assert_eq!(output_language(&s, Some("de"), "42").as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn output_language_follows_the_text_read_aloud() {
+        let s = get_default_settings();
+        // Spoken in German, translated to French by the post-processing prompt
+        assert_eq!(
+            output_language(
+                &s,
+                Some("de"),
+                "Bonjour à tous, je vous remercie de votre attention et je vous souhaite une bonne journée."
+            )
+            .as_deref(),
+            Some("fr")
+        );
+    }
 }
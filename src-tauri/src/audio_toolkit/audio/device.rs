@@ -29,6 +29,35 @@ pub fn list_input_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::E
     Ok(out)
 }
 
+/// Whether a device name fits a pattern from the microphone priority list.
+/// Case is ignored and `*` stands for any run of characters; a pattern
+/// without `*` matches names that contain it, so "Jabra" finds the headset
+/// whichever port it is on.
+pub fn device_name_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let name = name.to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    if !pattern.contains('*') {
+        return name.contains(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first().unwrap();
+    let (last, middle) = rest.split_last().unwrap();
+    let Some(mut remaining) = name.strip_prefix(first) else {
+        return false;
+    };
+    for part in middle {
+        match remaining.find(part) {
+            Some(at) => remaining = &remaining[at + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(last)
+}
+
 pub fn list_output_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::Error>> {
     let host = crate::audio_toolkit::get_cpal_host();
     let default_name = host.default_output_device().and_then(|d| d.name().ok());
@@ -50,3 +79,28 @@ pub fn list_output_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::
 
     Ok(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_patterns_match_part_of_the_name() {
+        assert!(device_name_matches("jabra", "Jabra Evolve2 65 (hw:2,0)"));
+        assert!(device_name_matches(
+            " Built-in ",
+            "MacBook Pro Built-in Microphone"
+        ));
+        assert!(!device_name_matches("USB", "Jabra Evolve2 65"));
+        assert!(!device_name_matches("", "Jabra Evolve2 65"));
+    }
+
+    #[test]
+    fn wildcards_anchor_to_the_whole_name() {
+        assert!(device_name_matches("USB*Mic*", "USB Desk Mic (hw:3,0)"));
+        assert!(device_name_matches("*(hw:3,0)", "USB Desk Mic (hw:3,0)"));
+        assert!(device_name_matches("*", "anything"));
+        assert!(!device_name_matches("Mic*", "USB Desk Mic"));
+        assert!(!device_name_matches("USB*Mic*Desk", "USB Desk Mic"));
+    }
+}
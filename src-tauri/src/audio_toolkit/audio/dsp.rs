@@ -0,0 +1,393 @@
+//! Optional clean-up of microphone audio before the VAD sees it: a high-pass
+//! filter against rumble, spectral-gating noise suppression against fans and
+//! keyboards, and automatic gain for quiet speakers. Runs on 16kHz frames.
+
+use rustfft::{num_complex::Complex32, Fft, FftPlanner};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+const HIGH_PASS_HZ: f32 = 80.0;
+
+const FFT_SIZE: usize = 512;
+const HOP: usize = FFT_SIZE / 2;
+/// How fast the noise estimate follows a quieter spectrum, per hop.
+const NOISE_FALL: f32 = 0.9;
+/// How fast it follows a louder one; slow, so speech isn't learnt as noise.
+const NOISE_RISE: f32 = 0.995;
+/// The estimate sits low in the noise's spread, so it is subtracted
+/// generously to keep the residue down.
+const OVER_SUBTRACTION: f32 = 2.5;
+/// Noise is attenuated by at most 20 dB, which keeps it sounding natural.
+const MIN_NOISE_GAIN: f32 = 0.1;
+/// Smooths gains falling between hops against "musical noise".
+const GAIN_RELEASE: f32 = 0.5;
+
+/// Speech level automatic gain aims for (-20 dBFS RMS).
+const TARGET_RMS: f32 = 0.1;
+/// Frames quieter than this (-50 dBFS) leave the gain alone, so silence
+/// isn't boosted.
+const AGC_GATE_RMS: f32 = 0.003;
+const MAX_AGC_GAIN: f32 = 10.0;
+const MIN_AGC_GAIN: f32 = 0.1;
+/// Share of the way to the desired gain covered per frame when turning down
+/// and up: loud audio is tamed quickly, quiet audio raised slowly.
+const AGC_ATTACK: f32 = 0.5;
+const AGC_RELEASE: f32 = 0.05;
+const PEAK_LIMIT: f32 = 0.99;
+
+/// Stages of the chain, each turned on separately.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DspConfig {
+    pub high_pass: bool,
+    pub noise_suppression: bool,
+    pub auto_gain: bool,
+}
+
+impl DspConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.high_pass || self.noise_suppression || self.auto_gain
+    }
+}
+
+/// High-pass, noise suppression and gain, applied in that order so the gain
+/// doesn't raise what the other stages removed.
+pub struct DspChain {
+    config: DspConfig,
+    high_pass: HighPass,
+    noise: NoiseSuppressor,
+    gain: AutoGain,
+    buffer: Vec<f32>,
+}
+
+impl DspChain {
+    pub fn new(config: DspConfig) -> Self {
+        Self {
+            config,
+            high_pass: HighPass::new(HIGH_PASS_HZ, WHISPER_SAMPLE_RATE as f32),
+            noise: NoiseSuppressor::new(),
+            gain: AutoGain::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Switch stages on or off. A stage that is switched on starts afresh.
+    pub fn set_config(&mut self, config: DspConfig) {
+        if config.high_pass && !self.config.high_pass {
+            self.high_pass = HighPass::new(HIGH_PASS_HZ, WHISPER_SAMPLE_RATE as f32);
+        }
+        if config.noise_suppression && !self.config.noise_suppression {
+            self.noise = NoiseSuppressor::new();
+        }
+        if config.auto_gain && !self.config.auto_gain {
+            self.gain = AutoGain::new();
+        }
+        self.config = config;
+    }
+
+    /// Samples by which the output trails the input.
+    pub fn latency(&self) -> usize {
+        if self.config.noise_suppression {
+            NoiseSuppressor::LATENCY
+        } else {
+            0
+        }
+    }
+
+    /// Process one frame, returning as many samples as were given.
+    pub fn process<'a>(&'a mut self, frame: &'a [f32]) -> &'a [f32] {
+        if !self.config.is_enabled() {
+            return frame;
+        }
+
+        self.buffer.clear();
+        self.buffer.extend_from_slice(frame);
+        if self.config.high_pass {
+            self.high_pass.process(&mut self.buffer);
+        }
+        if self.config.noise_suppression {
+            self.noise.process(&mut self.buffer);
+        }
+        if self.config.auto_gain {
+            self.gain.process(&mut self.buffer);
+        }
+        &self.buffer
+    }
+}
+
+/// Run the chain over a whole recording, e.g. to compare its effect offline.
+/// The output lines up with the input and has the same length.
+pub fn process_offline(config: DspConfig, samples: &[f32]) -> Vec<f32> {
+    const FRAME: usize = (WHISPER_SAMPLE_RATE / 1000 * 30) as usize;
+
+    let mut chain = DspChain::new(config);
+    let latency = chain.latency();
+    let mut output = Vec::with_capacity(samples.len() + latency + FRAME);
+    let flush = vec![0.0; latency];
+    for frame in samples.chunks(FRAME).chain(flush.chunks(FRAME)) {
+        output.extend_from_slice(chain.process(frame));
+    }
+    output.drain(..latency.min(output.len()));
+    output.truncate(samples.len());
+    output
+}
+
+/// Second-order Butterworth high-pass.
+struct HighPass {
+    b: [f32; 3],
+    a: [f32; 2],
+    z: [f32; 2],
+}
+
+impl HighPass {
+    fn new(cutoff_hz: f32, sample_rate: f32) -> Self {
+        let w0 = 2.0 * PI * cutoff_hz / sample_rate;
+        let alpha = w0.sin() / 2.0_f32.sqrt();
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b: [
+                (1.0 + cos) / 2.0 / a0,
+                -(1.0 + cos) / a0,
+                (1.0 + cos) / 2.0 / a0,
+            ],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+            z: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let x = *sample;
+            let y = self.b[0] * x + self.z[0];
+            self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+            self.z[1] = self.b[2] * x - self.a[1] * y;
+            *sample = y;
+        }
+    }
+}
+
+/// Spectral gating: each frequency bin is attenuated by how close it is to
+/// a running estimate of the background noise in that bin.
+struct NoiseSuppressor {
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    spectrum: Vec<Complex32>,
+    input: Vec<f32>,
+    overlap: Vec<f32>,
+    output: VecDeque<f32>,
+    noise: Option<Vec<f32>>,
+    gains: Vec<f32>,
+}
+
+impl NoiseSuppressor {
+    const LATENCY: usize = FFT_SIZE;
+
+    fn new() -> Self {
+        let mut planner = FftPlanner::<f32>::new();
+        // A square-root Hann window on both analysis and synthesis adds up to
+        // one at 50% overlap
+        let window = (0..FFT_SIZE)
+            .map(|i| (0.5 * (1.0 - (2.0 * PI * i as f32 / FFT_SIZE as f32).cos())).sqrt())
+            .collect();
+        Self {
+            fft: planner.plan_fft_forward(FFT_SIZE),
+            ifft: planner.plan_fft_inverse(FFT_SIZE),
+            window,
+            spectrum: vec![Complex32::new(0.0, 0.0); FFT_SIZE],
+            // Priming both ends keeps enough output ready for any frame size
+            input: vec![0.0; FFT_SIZE - HOP],
+            overlap: vec![0.0; FFT_SIZE],
+            output: VecDeque::from(vec![0.0; HOP]),
+            noise: None,
+            gains: vec![1.0; FFT_SIZE / 2 + 1],
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        self.input.extend_from_slice(samples);
+        while self.input.len() >= FFT_SIZE {
+            self.process_block();
+            self.input.drain(..HOP);
+        }
+        for sample in samples {
+            *sample = self.output.pop_front().unwrap_or(0.0);
+        }
+    }
+
+    fn process_block(&mut self) {
+        for (bin, (&x, &w)) in self
+            .spectrum
+            .iter_mut()
+            .zip(self.input.iter().zip(&self.window))
+        {
+            *bin = Complex32::new(x * w, 0.0);
+        }
+        self.fft.process(&mut self.spectrum);
+
+        let magnitudes: Vec<f32> = self.spectrum[..=FFT_SIZE / 2]
+            .iter()
+            .map(|c| c.norm())
+            .collect();
+        let noise = self.noise.get_or_insert_with(|| magnitudes.clone());
+        for (k, &magnitude) in magnitudes.iter().enumerate() {
+            let rate = if magnitude < noise[k] {
+                NOISE_FALL
+            } else {
+                NOISE_RISE
+            };
+            noise[k] = rate * noise[k] + (1.0 - rate) * magnitude;
+
+            let gain = if magnitude > 0.0 {
+                (1.0 - OVER_SUBTRACTION * noise[k] / magnitude).clamp(MIN_NOISE_GAIN, 1.0)
+            } else {
+                MIN_NOISE_GAIN
+            };
+            self.gains[k] = if gain > self.gains[k] {
+                gain
+            } else {
+                GAIN_RELEASE * self.gains[k] + (1.0 - GAIN_RELEASE) * gain
+            };
+
+            self.spectrum[k] *= self.gains[k];
+            if k > 0 && k < FFT_SIZE / 2 {
+                self.spectrum[FFT_SIZE - k] *= self.gains[k];
+            }
+        }
+
+        self.ifft.process(&mut self.spectrum);
+        let scale = 1.0 / FFT_SIZE as f32;
+        for ((out, bin), &w) in self
+            .overlap
+            .iter_mut()
+            .zip(&self.spectrum)
+            .zip(&self.window)
+        {
+            *out += bin.re * scale * w;
+        }
+        self.output.extend(&self.overlap[..HOP]);
+        self.overlap.copy_within(HOP.., 0);
+        self.overlap[FFT_SIZE - HOP..].fill(0.0);
+    }
+}
+
+/// Slow automatic gain towards a target level with a fast peak limiter.
+struct AutoGain {
+    gain: f32,
+    applied: f32,
+}
+
+impl AutoGain {
+    fn new() -> Self {
+        Self {
+            gain: 1.0,
+            applied: 1.0,
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        if samples.is_empty() {
+            return;
+        }
+
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        if rms > AGC_GATE_RMS {
+            let desired = (TARGET_RMS / rms).clamp(MIN_AGC_GAIN, MAX_AGC_GAIN);
+            let rate = if desired < self.gain {
+                AGC_ATTACK
+            } else {
+                AGC_RELEASE
+            };
+            self.gain += (desired - self.gain) * rate;
+        }
+
+        let peak = samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        let target = if peak * self.gain > PEAK_LIMIT {
+            PEAK_LIMIT / peak
+        } else {
+            self.gain
+        };
+
+        // Ramp across the frame so gain changes don't click
+        let start = self.applied;
+        let len = samples.len() as f32;
+        for (i, sample) in samples.iter_mut().enumerate() {
+            let gain = start + (target - start) * (i + 1) as f32 / len;
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+        self.applied = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::signals::{noise, sine};
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    fn config(high_pass: bool, noise_suppression: bool, auto_gain: bool) -> DspConfig {
+        DspConfig {
+            high_pass,
+            noise_suppression,
+            auto_gain,
+        }
+    }
+
+    #[test]
+    fn disabled_chain_passes_audio_through() {
+        let input = sine(440.0, 0.5, 0.1);
+        assert_eq!(process_offline(DspConfig::default(), &input), input);
+    }
+
+    #[test]
+    fn high_pass_removes_rumble_and_keeps_speech() {
+        let rumble = process_offline(config(true, false, false), &sine(20.0, 0.5, 1.0));
+        assert!(rms(&rumble[8000..]) < 0.05);
+
+        let voice = process_offline(config(true, false, false), &sine(1000.0, 0.5, 1.0));
+        assert!(rms(&voice[8000..]) > 0.34);
+    }
+
+    #[test]
+    fn noise_suppression_keeps_alignment_without_noise() {
+        // A tone that starts halfway is still there, at the same place
+        let mut input = vec![0.0; 8000];
+        input.extend(sine(1000.0, 0.5, 0.5));
+        let output = process_offline(config(false, true, false), &input);
+        assert_eq!(output.len(), input.len());
+        assert!(rms(&output[..7000]) < 1e-4);
+        assert!(rms(&output[9000..12000]) > 0.25);
+    }
+
+    #[test]
+    fn noise_suppression_attenuates_steady_noise() {
+        let input = noise(0.05, 2.0, 0x1234_5678);
+        let output = process_offline(config(false, true, false), &input);
+        assert!(rms(&output[16000..]) < 0.5 * rms(&input[16000..]));
+
+        // A voice starting over the noise mostly survives
+        let voice: Vec<f32> = input
+            .iter()
+            .zip(vec![0.0; 16000].into_iter().chain(sine(500.0, 0.3, 1.0)))
+            .map(|(n, v)| n + v)
+            .collect();
+        let output = process_offline(config(false, true, false), &voice);
+        assert!(rms(&output[16500..23500]) > 0.6 * rms(&voice[16500..23500]));
+    }
+
+    #[test]
+    fn auto_gain_raises_quiet_speech_and_limits_peaks() {
+        let quiet = process_offline(config(false, false, true), &sine(300.0, 0.01, 3.0));
+        let level = rms(&quiet[40000..]);
+        assert!(level > 0.05 && level < 0.12, "level {}", level);
+
+        let loud = process_offline(config(false, false, true), &sine(300.0, 0.95, 1.0));
+        assert!(loud.iter().all(|s| s.abs() <= PEAK_LIMIT + 1e-6));
+    }
+}
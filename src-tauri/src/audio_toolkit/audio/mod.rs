@@ -1,15 +1,19 @@
 // Re-export all audio components
 mod decoder;
 mod device;
+pub mod dsp;
 pub mod recorder;
 mod resampler;
+mod source;
 pub mod system_capture;
 mod utils;
 mod visualizer;
 
 pub use decoder::{decode_audio_file, AudioFormat};
-pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
+pub use device::{device_name_matches, list_input_devices, list_output_devices, CpalDeviceInfo};
+pub use dsp::{DspChain, DspConfig};
 pub use recorder::AudioRecorder;
 pub use resampler::FrameResampler;
-pub use utils::{load_wav_file, save_wav_file};
+pub use source::InputSource;
+pub use utils::{encode_wav, load_wav_file, save_wav_file};
 pub use visualizer::AudioVisualiser;
@@ -1,7 +1,11 @@
 use std::{
+    collections::VecDeque,
     io::Error,
-    sync::{mpsc, Arc, Mutex},
-    time::Duration,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
 use cpal::{
@@ -10,16 +14,39 @@ use cpal::{
 };
 
 use crate::audio_toolkit::{
-    audio::{AudioVisualiser, FrameResampler},
+    audio::{source::Playback, AudioVisualiser, DspChain, DspConfig, FrameResampler, InputSource},
     constants,
-    vad::{self, VadFrame},
+    kws::SharedKeywordSpotter,
+    vad::{self, UtteranceConfig, UtteranceEvent, UtteranceSegmenter, VadFrame, VadParams},
     VoiceActivityDetector,
 };
-use log::{debug, error, warn};
+use log::{debug, error, info, warn};
+
+/// Longest pre-roll the recorder keeps, in milliseconds.
+pub const MAX_PRE_ROLL_MS: u32 = 2000;
+
+/// A recording stopped on silence ends after this long if nobody speaks.
+const NO_SPEECH_TIMEOUT_MS: u32 = 5000;
+
+/// A stream that delivers nothing for this long has lost its device, even if
+/// the backend never reported an error.
+const STALL_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often to try again while no device can be opened.
+const REOPEN_INTERVAL: Duration = Duration::from_secs(1);
+/// How often to list the devices, to notice a more preferred one plugged in
+/// and to know where to go when the current one stops working.
+const PREFERRED_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Devices to record from, most preferred first. Polled on a thread of its
+/// own while a device is open.
+pub type DeviceChooser = Arc<dyn Fn() -> Vec<Device> + Send + Sync + 'static>;
+type DeviceCallback = Arc<dyn Fn(String) + Send + Sync + 'static>;
 
 enum Cmd {
     Start(Option<mpsc::Sender<Vec<f32>>>),
     Stop(mpsc::Sender<Vec<f32>>),
+    Listen(Option<(UtteranceConfig, mpsc::Sender<UtteranceEvent>)>),
+    StopOnSilence(u32, mpsc::Sender<()>),
     Shutdown,
 }
 
@@ -29,6 +56,13 @@ pub struct AudioRecorder {
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    last_speech_ms: Arc<Mutex<Option<i64>>>,
+    pre_roll_ms: Arc<AtomicU32>,
+    dsp_config: Arc<Mutex<DspConfig>>,
+    keyword_spotter: Option<SharedKeywordSpotter>,
+    keyword_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
+    device_chooser: Option<DeviceChooser>,
+    device_cb: Option<DeviceCallback>,
 }
 
 impl AudioRecorder {
@@ -39,6 +73,13 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            last_speech_ms: Arc::new(Mutex::new(None)),
+            pre_roll_ms: Arc::new(AtomicU32::new(0)),
+            dsp_config: Arc::new(Mutex::new(DspConfig::default())),
+            keyword_spotter: None,
+            keyword_cb: None,
+            device_chooser: None,
+            device_cb: None,
         })
     }
 
@@ -47,6 +88,73 @@ impl AudioRecorder {
         self
     }
 
+    /// Keep the last `ms` milliseconds of audio heard while the stream is open
+    /// but not recording, and put them at the start of the next recording.
+    pub fn with_pre_roll(self, ms: u32) -> Self {
+        self.set_pre_roll_ms(ms);
+        self
+    }
+
+    pub fn set_pre_roll_ms(&self, ms: u32) {
+        self.pre_roll_ms
+            .store(ms.min(MAX_PRE_ROLL_MS), Ordering::Relaxed);
+    }
+
+    /// Retune the VAD, also while the stream is open.
+    pub fn set_vad_params(&self, params: VadParams) {
+        if let Some(vad) = &self.vad {
+            vad.lock().unwrap().configure(&params);
+        }
+    }
+
+    /// Clean up the audio between resampling and the VAD; see [`DspChain`].
+    pub fn with_dsp(self, config: DspConfig) -> Self {
+        self.set_dsp_config(config);
+        self
+    }
+
+    /// Switch processing stages on or off, also while the stream is open.
+    pub fn set_dsp_config(&self, config: DspConfig) {
+        *self.dsp_config.lock().unwrap() = config;
+    }
+
+    /// Run whatever spotter is in `spotter` on the audio heard while not
+    /// recording, and call `on_keyword` with the label of each keyword found.
+    /// The callback runs on the audio thread, so it should hand off any work.
+    pub fn with_keyword_spotter<F>(mut self, spotter: SharedKeywordSpotter, on_keyword: F) -> Self
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        self.keyword_spotter = Some(spotter);
+        self.keyword_cb = Some(Arc::new(on_keyword));
+        self
+    }
+
+    /// Where to go when the device disappears mid-stream: the first of
+    /// `choose()` that opens, else the system default. The recorder also
+    /// moves over when a device `choose()` ranks first shows up. Captured
+    /// audio and the recording in progress are kept across the switch.
+    ///
+    /// `choose()` is polled every few seconds on a thread of its own, so a
+    /// slow device listing never holds up the audio.
+    pub fn with_device_chooser<F>(mut self, choose: F) -> Self
+    where
+        F: Fn() -> Vec<Device> + Send + Sync + 'static,
+    {
+        self.device_chooser = Some(Arc::new(choose));
+        self
+    }
+
+    /// Called with the new device's name after the recorder switched devices.
+    /// Runs on the audio thread.
+    pub fn with_device_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        self.device_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn with_level_callback<F>(mut self, cb: F) -> Self
     where
         F: Fn(Vec<f32>) + Send + Sync + 'static,
@@ -55,7 +163,12 @@ impl AudioRecorder {
         self
     }
 
-    pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
+    /// Start capturing from `source`: a device, `None` for the system
+    /// default, or one of the virtual inputs of [`InputSource`].
+    pub fn open(
+        &mut self,
+        source: impl Into<InputSource>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
         }
@@ -64,65 +177,67 @@ impl AudioRecorder {
         let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
 
         let host = crate::audio_toolkit::get_cpal_host();
-        let device = match device {
-            Some(dev) => dev,
-            None => host
-                .default_input_device()
-                .ok_or_else(|| Error::new(std::io::ErrorKind::NotFound, "No input device found"))?,
+        let source = match source.into() {
+            InputSource::Device(device) => InputSource::Device(Some(match device {
+                Some(dev) => dev,
+                None => host.default_input_device().ok_or_else(|| {
+                    Error::new(std::io::ErrorKind::NotFound, "No input device found")
+                })?,
+            })),
+            source => source,
+        };
+        let device = match &source {
+            InputSource::Device(device) => device.clone(),
+            _ => None,
         };
 
-        let thread_device = device.clone();
         let vad = self.vad.clone();
         // Move the optional level callback into the worker thread
         let level_cb = self.level_cb.clone();
+        let last_speech_ms = self.last_speech_ms.clone();
+        let pre_roll_ms = self.pre_roll_ms.clone();
+        let dsp_config = self.dsp_config.clone();
+        let keyword_spotter = self.keyword_spotter.clone();
+        let keyword_cb = self.keyword_cb.clone();
+        let device_chooser = self.device_chooser.clone();
+        let device_cb = self.device_cb.clone();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<(), String>>();
 
         let worker = std::thread::spawn(move || {
-            let config = AudioRecorder::get_preferred_config(&thread_device)
-                .expect("failed to fetch preferred config");
-
-            let sample_rate = config.sample_rate().0;
-            let channels = config.channels() as usize;
-
-            debug!(
-                "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}",
-                thread_device.name(),
-                sample_rate,
-                channels,
-                config.sample_format()
-            );
-
-            let stream = match config.sample_format() {
-                cpal::SampleFormat::U8 => {
-                    AudioRecorder::build_stream::<u8>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I8 => {
-                    AudioRecorder::build_stream::<i8>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I16 => {
-                    AudioRecorder::build_stream::<i16>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I32 => {
-                    AudioRecorder::build_stream::<i32>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::F32 => {
-                    AudioRecorder::build_stream::<f32>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                _ => panic!("unsupported sample format"),
-            };
-
-            stream.play().expect("failed to start stream");
+            // cpal streams can't move between threads, so this one opens,
+            // replaces and drops them all
+            let mut input = Input::new(sample_tx, device_chooser, device_cb);
+            if let Err(e) = input.start(source) {
+                let _ = ready_tx.send(Err(e.to_string()));
+                return;
+            }
+            let _ = ready_tx.send(Ok(()));
 
-            // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
-            // stream is dropped here, after run_consumer returns
+            run_consumer(
+                input,
+                vad,
+                sample_rx,
+                cmd_rx,
+                level_cb,
+                last_speech_ms,
+                pre_roll_ms,
+                dsp_config,
+                keyword_spotter,
+                keyword_cb,
+            );
+            // the stream is dropped with the input, after run_consumer returns
         });
 
-        self.device = Some(device);
+        match ready_rx.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                let _ = worker.join();
+                return Err(format!("Failed to open input stream: {}", e).into());
+            }
+            Err(_) => return Err("Audio thread exited while opening the stream".into()),
+        }
+
+        self.device = device;
         self.cmd_tx = Some(cmd_tx);
         self.worker_handle = Some(worker);
 
@@ -147,6 +262,53 @@ impl AudioRecorder {
         Ok(resp_rx.recv()?)
     }
 
+    /// Hands-free listening: instead of recording between `start` and `stop`,
+    /// split the stream into utterances with the VAD and send each one to
+    /// `events` as it ends. The channel closes when listening stops.
+    pub fn listen(
+        &self,
+        config: UtteranceConfig,
+        events: mpsc::Sender<UtteranceEvent>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.vad.is_none() {
+            return Err("Listening for utterances needs a VAD".into());
+        }
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Listen(Some((config, events))))?;
+        }
+        Ok(())
+    }
+
+    /// Stop listening; an utterance in progress is sent as it is.
+    pub fn stop_listening(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Listen(None))?;
+        }
+        Ok(())
+    }
+
+    /// Notify `done` once the current recording has gone quiet for `silence_ms`
+    /// after some speech, or has heard no speech at all for a few seconds.
+    /// The watch ends with the recording.
+    pub fn stop_on_silence(
+        &self,
+        silence_ms: u32,
+        done: mpsc::Sender<()>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.vad.is_none() {
+            return Err("Stopping on silence needs a VAD".into());
+        }
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::StopOnSilence(silence_ms, done))?;
+        }
+        Ok(())
+    }
+
+    /// Voice the VAD detected in the last stopped recording, if it keeps count.
+    pub fn last_speech_ms(&self) -> Option<i64> {
+        *self.last_speech_ms.lock().unwrap()
+    }
+
     pub fn close(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = self.cmd_tx.take() {
             let _ = tx.send(Cmd::Shutdown);
@@ -163,6 +325,7 @@ impl AudioRecorder {
         config: &cpal::SupportedStreamConfig,
         sample_tx: mpsc::Sender<Vec<f32>>,
         channels: usize,
+        failed: Arc<AtomicBool>,
     ) -> Result<cpal::Stream, cpal::BuildStreamError>
     where
         T: Sample + SizedSample + Send + 'static,
@@ -199,7 +362,10 @@ impl AudioRecorder {
         device.build_input_stream(
             &config.clone().into(),
             stream_cb,
-            |err| warn!("Stream error: {}", err),
+            move |err| {
+                warn!("Stream error: {}", err);
+                failed.store(true, Ordering::Relaxed);
+            },
             None,
         )
     }
@@ -226,33 +392,355 @@ impl AudioRecorder {
     }
 }
 
+/// The audio feeding the consumer. A device's stream is replaced when the
+/// device goes away; a virtual input is kept until the recorder closes.
+struct Input {
+    stream: Option<cpal::Stream>,
+    playback: Option<Playback>,
+    sample_rate: u32,
+    sample_tx: mpsc::Sender<Vec<f32>>,
+    failed: Arc<AtomicBool>,
+    chooser: Option<DeviceChooser>,
+    candidates: Option<Polled<DeviceCandidates>>,
+    on_change: Option<DeviceCallback>,
+    watch: DeviceWatch,
+}
+
+impl Input {
+    fn new(
+        sample_tx: mpsc::Sender<Vec<f32>>,
+        chooser: Option<DeviceChooser>,
+        on_change: Option<DeviceCallback>,
+    ) -> Self {
+        Self {
+            stream: None,
+            playback: None,
+            sample_rate: constants::WHISPER_SAMPLE_RATE,
+            sample_tx,
+            failed: Arc::new(AtomicBool::new(false)),
+            chooser,
+            candidates: None,
+            on_change,
+            watch: DeviceWatch::new(Instant::now()),
+        }
+    }
+
+    fn start(&mut self, source: InputSource) -> Result<(), Box<dyn std::error::Error>> {
+        match source {
+            InputSource::Device(Some(device)) => {
+                let name = self.open(&device)?;
+                self.watch.opened(name, Instant::now());
+                let chooser = self.chooser.clone();
+                self.candidates = Some(Polled::spawn(
+                    move || DeviceCandidates::list(chooser.as_deref()),
+                    PREFERRED_POLL_INTERVAL,
+                ));
+                Ok(())
+            }
+            InputSource::Device(None) => Err("No input device found".into()),
+            source => self.play(source),
+        }
+    }
+
+    /// Open `device` in place of the current stream, returning its name.
+    fn open(&mut self, device: &Device) -> Result<String, Box<dyn std::error::Error>> {
+        self.stream = None;
+        let config = AudioRecorder::get_preferred_config(device)?;
+        let sample_rate = config.sample_rate().0;
+        let channels = config.channels() as usize;
+
+        debug!(
+            "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}",
+            device.name(),
+            sample_rate,
+            channels,
+            config.sample_format()
+        );
+
+        let failed = Arc::new(AtomicBool::new(false));
+        let tx = self.sample_tx.clone();
+        let stream = match config.sample_format() {
+            cpal::SampleFormat::U8 => {
+                AudioRecorder::build_stream::<u8>(device, &config, tx, channels, failed.clone())?
+            }
+            cpal::SampleFormat::I8 => {
+                AudioRecorder::build_stream::<i8>(device, &config, tx, channels, failed.clone())?
+            }
+            cpal::SampleFormat::I16 => {
+                AudioRecorder::build_stream::<i16>(device, &config, tx, channels, failed.clone())?
+            }
+            cpal::SampleFormat::I32 => {
+                AudioRecorder::build_stream::<i32>(device, &config, tx, channels, failed.clone())?
+            }
+            cpal::SampleFormat::F32 => {
+                AudioRecorder::build_stream::<f32>(device, &config, tx, channels, failed.clone())?
+            }
+            other => return Err(format!("Unsupported sample format {:?}", other).into()),
+        };
+        stream.play()?;
+
+        self.stream = Some(stream);
+        self.sample_rate = sample_rate;
+        self.failed = failed;
+        Ok(device.name().unwrap_or_else(|_| "Unknown".into()))
+    }
+
+    fn play(&mut self, source: InputSource) -> Result<(), Box<dyn std::error::Error>> {
+        let (playback, sample_rate, name) = Playback::start(source, self.sample_tx.clone())?;
+        info!("Using virtual audio input '{}' at {}Hz", name, sample_rate);
+
+        self.playback = Some(playback);
+        self.sample_rate = sample_rate;
+        self.watch.opened(name, Instant::now());
+        Ok(())
+    }
+
+    /// Play a virtual input that has run out again, for a new recording.
+    fn replay(&self) {
+        if let Some(playback) = &self.playback {
+            playback.replay();
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.stream.is_some() || self.playback.is_some()
+    }
+
+    fn heard_audio(&mut self) {
+        self.watch.heard_audio(Instant::now());
+    }
+
+    /// Whether to drop the current stream: it failed or went quiet, or a
+    /// device the chooser prefers has appeared.
+    fn should_switch(&mut self) -> bool {
+        if self.playback.is_some() {
+            return false;
+        }
+        let preferred = self
+            .candidates
+            .as_mut()
+            .and_then(|c| c.latest().preferred.as_deref());
+        self.watch.should_switch(
+            self.failed.load(Ordering::Relaxed),
+            preferred,
+            Instant::now(),
+        )
+    }
+
+    fn close(&mut self) {
+        self.stream = None;
+        self.watch.closed(Instant::now());
+    }
+
+    fn reopen_due(&self) -> bool {
+        !self.is_open() && self.watch.reopen_due(Instant::now())
+    }
+
+    /// Open the best device that works; false if none does yet. The devices
+    /// come from the last listing, so none are enumerated on this thread.
+    fn reopen(&mut self) -> bool {
+        let candidates = self
+            .candidates
+            .as_mut()
+            .map(|c| c.latest().devices.clone())
+            .unwrap_or_default();
+        for (rank, device) in candidates.iter().enumerate() {
+            match self.open(device) {
+                Ok(name) => {
+                    if self.watch.reopened(name, rank, Instant::now()) {
+                        info!("Switched audio input to '{}'", self.watch.device_name);
+                        if let Some(cb) = &self.on_change {
+                            cb(self.watch.device_name.clone());
+                        }
+                    }
+                    return true;
+                }
+                Err(e) => {
+                    warn!("Failed to open audio input {:?}: {}", device.name(), e);
+                    self.watch.failed_to_open(device.name().ok(), rank);
+                }
+            }
+        }
+        self.watch.reopen_failed(Instant::now());
+        false
+    }
+}
+
+/// Devices to open, best first: the chooser's, then the system default.
+#[derive(Default)]
+struct DeviceCandidates {
+    devices: Vec<Device>,
+    /// Name of the device the chooser ranks first.
+    preferred: Option<String>,
+}
+
+impl DeviceCandidates {
+    fn list(chooser: Option<&(dyn Fn() -> Vec<Device> + Send + Sync)>) -> Self {
+        let mut devices = chooser.map(|choose| choose()).unwrap_or_default();
+        let preferred = devices.first().and_then(|d| d.name().ok());
+        devices.extend(crate::audio_toolkit::get_cpal_host().default_input_device());
+        Self { devices, preferred }
+    }
+}
+
+/// The result of `poll`, refreshed on a thread of its own: listing devices
+/// can take long enough to hold up the VAD and the keyword spotter. The
+/// thread ends at its next poll after this is dropped.
+struct Polled<T> {
+    rx: mpsc::Receiver<T>,
+    latest: T,
+}
+
+impl<T: Default + Send + 'static> Polled<T> {
+    fn spawn<F>(poll: F, interval: Duration) -> Self
+    where
+        F: Fn() -> T + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            while tx.send(poll()).is_ok() {
+                std::thread::sleep(interval);
+            }
+        });
+        Self {
+            rx,
+            latest: T::default(),
+        }
+    }
+
+    /// The most recent answer, without waiting for a new one.
+    fn latest(&mut self) -> &T {
+        if let Some(latest) = self.rx.try_iter().last() {
+            self.latest = latest;
+        }
+        &self.latest
+    }
+}
+
+/// When to give up on the current device, and when to try opening one again.
+struct DeviceWatch {
+    /// The device in use, or the last one while none could be opened.
+    device_name: String,
+    /// Preferred device that failed to open, so polling doesn't keep
+    /// switching to it.
+    broken_device: Option<String>,
+    last_audio: Instant,
+    /// When to try opening a device next; `None` while one is open.
+    retry_at: Option<Instant>,
+}
+
+impl DeviceWatch {
+    fn new(now: Instant) -> Self {
+        Self {
+            device_name: String::new(),
+            broken_device: None,
+            last_audio: now,
+            retry_at: None,
+        }
+    }
+
+    fn opened(&mut self, name: String, now: Instant) {
+        self.device_name = name;
+        self.last_audio = now;
+        self.retry_at = None;
+    }
+
+    fn heard_audio(&mut self, now: Instant) {
+        self.last_audio = now;
+    }
+
+    fn should_switch(&self, failed: bool, preferred: Option<&str>, now: Instant) -> bool {
+        if failed || now.saturating_duration_since(self.last_audio) >= STALL_TIMEOUT {
+            warn!("Lost audio input '{}'", self.device_name);
+            return true;
+        }
+        match preferred {
+            Some(name)
+                if name != self.device_name && Some(name) != self.broken_device.as_deref() =>
+            {
+                debug!("Preferred audio input '{}' is available", name);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn closed(&mut self, now: Instant) {
+        self.retry_at = Some(now);
+    }
+
+    fn reopen_due(&self, now: Instant) -> bool {
+        self.retry_at.is_some_and(|at| now >= at)
+    }
+
+    /// The candidate of `rank` in the chooser's order opened; returns whether
+    /// that is a different device.
+    fn reopened(&mut self, name: String, rank: usize, now: Instant) -> bool {
+        if rank == 0 {
+            self.broken_device = None;
+        }
+        let changed = name != self.device_name;
+        self.opened(name, now);
+        changed
+    }
+
+    fn failed_to_open(&mut self, name: Option<String>, rank: usize) {
+        if rank == 0 {
+            self.broken_device = name;
+        }
+    }
+
+    /// No candidate opened; try again in a moment.
+    fn reopen_failed(&mut self, now: Instant) {
+        self.retry_at = Some(now + REOPEN_INTERVAL);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_consumer(
-    in_sample_rate: u32,
+    mut input: Input,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    last_speech_ms: Arc<Mutex<Option<i64>>>,
+    pre_roll_ms: Arc<AtomicU32>,
+    dsp_config: Arc<Mutex<DspConfig>>,
+    keyword_spotter: Option<SharedKeywordSpotter>,
+    keyword_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
 ) {
-    let mut frame_resampler = FrameResampler::new(
-        in_sample_rate as usize,
-        constants::WHISPER_SAMPLE_RATE as usize,
-        Duration::from_millis(30),
-    );
+    const FRAME_MS: u64 = 30;
+    const FRAME_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * FRAME_MS as usize / 1000;
+    let new_resampler = |in_sample_rate: u32| {
+        FrameResampler::new(
+            in_sample_rate as usize,
+            constants::WHISPER_SAMPLE_RATE as usize,
+            Duration::from_millis(FRAME_MS),
+        )
+    };
+    let mut frame_resampler = new_resampler(input.sample_rate);
 
+    let mut dsp = DspChain::new(*dsp_config.lock().unwrap());
     let mut processed_samples = Vec::<f32>::new();
+    let mut pre_roll = PreRoll::default();
+    let mut listener: Option<(UtteranceSegmenter, mpsc::Sender<UtteranceEvent>)> = None;
+    let mut silence_watch: Option<(SilenceWatch, mpsc::Sender<()>)> = None;
     let mut recording = false;
     let mut chunk_tx: Option<mpsc::Sender<Vec<f32>>> = None;
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 64;
     const WINDOW_SIZE: usize = 512;
-    let mut visualizer = AudioVisualiser::new(
-        in_sample_rate,
-        WINDOW_SIZE,
-        BUCKETS,
-        400.0,  // 5% of Nyquist (8kHz)
-        3200.0, // 40% of Nyquist (8kHz) - Matches original TS implementation
-    );
+    let new_visualiser = |in_sample_rate: u32| {
+        AudioVisualiser::new(
+            in_sample_rate,
+            WINDOW_SIZE,
+            BUCKETS,
+            400.0,  // 5% of Nyquist (8kHz)
+            3200.0, // 40% of Nyquist (8kHz) - Matches original TS implementation
+        )
+    };
+    let mut visualizer = new_visualiser(input.sample_rate);
 
     fn handle_frame(
         samples: &[f32],
@@ -260,9 +748,9 @@ fn run_consumer(
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
         chunk_tx: &Option<mpsc::Sender<Vec<f32>>>,
-    ) {
+    ) -> bool {
         if !recording {
-            return;
+            return false;
         }
 
         let mut process_speech = |buf: &[f32]| {
@@ -274,12 +762,81 @@ fn run_consumer(
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
-            match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => process_speech(buf),
-                VadFrame::Noise => {}
+            let voiced_before = det.voiced_frames();
+            let speech = match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
+                VadFrame::Speech(buf) => {
+                    process_speech(buf);
+                    true
+                }
+                VadFrame::Noise => false,
+            };
+            match (voiced_before, det.voiced_frames()) {
+                (Some(before), Some(after)) => after > before,
+                _ => speech,
             }
         } else {
             process_speech(samples);
+            true
+        }
+    }
+
+    fn spot_keyword(
+        samples: &[f32],
+        spotter: &Option<SharedKeywordSpotter>,
+        keyword_cb: &Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
+    ) -> bool {
+        let Some(spotter) = spotter else {
+            return false;
+        };
+        let mut slot = spotter.lock().unwrap();
+        let Some(spotter) = slot.as_mut() else {
+            return false;
+        };
+        match spotter.push_frame(samples) {
+            Ok(Some(label)) => {
+                debug!("Keyword spotted: {}", label);
+                spotter.reset();
+                drop(slot);
+                if let Some(cb) = keyword_cb {
+                    cb(label);
+                }
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!("Keyword spotter failed, disabling it: {}", e);
+                *slot = None;
+                false
+            }
+        }
+    }
+
+    fn listen_frame(
+        samples: &[f32],
+        vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+        segmenter: &mut UtteranceSegmenter,
+        events: &mpsc::Sender<UtteranceEvent>,
+    ) {
+        let Some(vad_arc) = vad else {
+            return;
+        };
+        let mut det = vad_arc.lock().unwrap();
+        let voiced_before = det.voiced_frames();
+        let speech = match det.push_frame(samples) {
+            Ok(VadFrame::Speech(buf)) => Some(buf.to_vec()),
+            _ => None,
+        };
+        let voiced = match (voiced_before, det.voiced_frames()) {
+            (Some(before), Some(after)) => after > before,
+            _ => speech.is_some(),
+        };
+
+        if let Some(event) = segmenter.push(samples, speech.as_deref(), voiced) {
+            if !matches!(event, UtteranceEvent::Started) {
+                // Start the next utterance from silence, not from the hangover
+                det.reset();
+            }
+            let _ = events.send(event);
         }
     }
 
@@ -291,19 +848,46 @@ fn run_consumer(
                     debug!("Cmd::Start received, chunk_tx is_some: {}", tx.is_some());
                     processed_samples.clear();
                     recording = true;
+                    silence_watch = None;
                     chunk_tx = tx;
+                    input.replay();
                     visualizer.reset();
                     if let Some(v) = &vad {
                         v.lock().unwrap().reset();
                     }
+
+                    // Audio from just before the hotkey, so the first syllable is kept
+                    let lead_in = pre_roll.take();
+                    if !lead_in.is_empty() {
+                        debug!("Prepending {} samples of pre-roll", lead_in.len());
+                        if let Some(tx) = &chunk_tx {
+                            let _ = tx.send(lead_in.clone());
+                        }
+                        processed_samples.extend(lead_in);
+                    }
                 }
                 Cmd::Stop(reply_tx) => {
                     debug!("Cmd::Stop received");
                     recording = false;
 
+                    silence_watch = None;
+
                     frame_resampler.finish(&mut |frame: &[f32]| {
-                        handle_frame(frame, true, &vad, &mut processed_samples, &chunk_tx)
+                        let frame = dsp.process(frame);
+                        handle_frame(frame, true, &vad, &mut processed_samples, &chunk_tx);
                     });
+                    // Push out the end of the recording the noise suppressor
+                    // still holds back, as `process_offline` does
+                    let silence = vec![0.0; FRAME_SAMPLES];
+                    for _ in 0..dsp.latency().div_ceil(FRAME_SAMPLES) {
+                        let frame = dsp.process(&silence);
+                        handle_frame(frame, true, &vad, &mut processed_samples, &chunk_tx);
+                    }
+
+                    *last_speech_ms.lock().unwrap() = vad
+                        .as_ref()
+                        .and_then(|v| v.lock().unwrap().voiced_frames())
+                        .map(|frames| (frames as u64 * FRAME_MS) as i64);
 
                     let sample_count = processed_samples.len();
                     let audio_duration_secs = sample_count as f32 / 16000.0;
@@ -315,23 +899,62 @@ fn run_consumer(
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
                     chunk_tx = None;
                 }
+                Cmd::Listen(Some((config, events))) => {
+                    debug!("Cmd::Listen received: {:?}", config);
+                    input.replay();
+                    if let Some(v) = &vad {
+                        v.lock().unwrap().reset();
+                    }
+                    listener = Some((UtteranceSegmenter::new(config, FRAME_MS as u32), events));
+                }
+                Cmd::Listen(None) => {
+                    debug!("Cmd::Listen(None) received");
+                    if let Some((mut segmenter, events)) = listener.take() {
+                        if let Some(event) = segmenter.flush() {
+                            let _ = events.send(event);
+                        }
+                    }
+                }
+                Cmd::StopOnSilence(silence_ms, done) => {
+                    debug!("Cmd::StopOnSilence received: {}ms", silence_ms);
+                    if recording {
+                        silence_watch = Some((SilenceWatch::new(silence_ms), done));
+                    }
+                }
                 Cmd::Shutdown => return,
             }
         }
 
+        // A lost device is closed first; audio it already sent is still
+        // processed below, and the stream is replaced once that has run out
+        if input.is_open() && input.should_switch() {
+            input.close();
+        }
+
         // Use recv_timeout to allow checking for shutdown commands even when
         // no audio samples are being received (e.g., if the audio device is unresponsive)
         let raw = match sample_rx.recv_timeout(Duration::from_millis(100)) {
             Ok(s) => s,
             Err(mpsc::RecvTimeoutError::Timeout) => {
-                // Check for shutdown command on timeout
-                if let Ok(Cmd::Shutdown) = cmd_rx.try_recv() {
-                    return;
+                if input.reopen_due() {
+                    if recording {
+                        frame_resampler.finish(&mut |frame: &[f32]| {
+                            let frame = dsp.process(frame);
+                            handle_frame(frame, true, &vad, &mut processed_samples, &chunk_tx);
+                        });
+                    }
+                    if input.reopen() {
+                        frame_resampler = new_resampler(input.sample_rate);
+                        visualizer = new_visualiser(input.sample_rate);
+                    }
                 }
+                // Commands, shutdown included, are handled at the top of the
+                // loop; taking one here would drop it
                 continue;
             }
             Err(mpsc::RecvTimeoutError::Disconnected) => break,
         };
+        input.heard_audio();
 
         // ---------- spectrum processing ---------------------------------- //
         if let Some(buckets) = visualizer.feed(&raw) {
@@ -341,8 +964,208 @@ fn run_consumer(
         }
 
         // ---------- existing pipeline ------------------------------------ //
+        let pre_roll_samples = pre_roll_ms.load(Ordering::Relaxed) as usize
+            * constants::WHISPER_SAMPLE_RATE as usize
+            / 1000;
+        dsp.set_config(*dsp_config.lock().unwrap());
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples, &chunk_tx)
+            // Clean the audio up before the VAD, the recording and the spotter
+            let frame = dsp.process(frame);
+            if recording {
+                let voiced =
+                    handle_frame(frame, recording, &vad, &mut processed_samples, &chunk_tx);
+                if let Some((watch, done)) = &mut silence_watch {
+                    if watch.push(voiced, FRAME_MS as u32) {
+                        let _ = done.send(());
+                        silence_watch = None;
+                    }
+                }
+            } else if let Some((segmenter, events)) = &mut listener {
+                listen_frame(frame, &vad, segmenter, events)
+            } else {
+                pre_roll.push(frame, pre_roll_samples);
+                if spot_keyword(frame, &keyword_spotter, &keyword_cb) {
+                    // Keep the wake word itself out of the recording it starts
+                    pre_roll.take();
+                }
+            }
         });
     }
 }
+
+/// Ring buffer of the most recent resampled audio heard while not recording.
+#[derive(Default)]
+struct PreRoll {
+    samples: VecDeque<f32>,
+}
+
+impl PreRoll {
+    /// Append a frame, keeping at most `capacity` samples.
+    fn push(&mut self, frame: &[f32], capacity: usize) {
+        self.samples.extend(frame);
+        let excess = self.samples.len().saturating_sub(capacity);
+        self.samples.drain(..excess);
+    }
+
+    fn take(&mut self) -> Vec<f32> {
+        self.samples.drain(..).collect()
+    }
+}
+
+/// Decides when a recording started hands-free has ended.
+struct SilenceWatch {
+    silence_ms: u32,
+    heard_speech: bool,
+    quiet_ms: u32,
+}
+
+impl SilenceWatch {
+    fn new(silence_ms: u32) -> Self {
+        Self {
+            silence_ms,
+            heard_speech: false,
+            quiet_ms: 0,
+        }
+    }
+
+    /// Account for one frame; true once the recording should stop.
+    fn push(&mut self, voiced: bool, frame_ms: u32) -> bool {
+        if voiced {
+            self.heard_speech = true;
+            self.quiet_ms = 0;
+            return false;
+        }
+        self.quiet_ms += frame_ms;
+        let limit = if self.heard_speech {
+            self.silence_ms
+        } else {
+            NO_SPEECH_TIMEOUT_MS
+        };
+        self.quiet_ms >= limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pre_roll_keeps_only_the_latest_samples() {
+        let mut pre_roll = PreRoll::default();
+        pre_roll.push(&[1.0, 2.0, 3.0], 4);
+        pre_roll.push(&[4.0, 5.0, 6.0], 4);
+        assert_eq!(pre_roll.take(), vec![3.0, 4.0, 5.0, 6.0]);
+        assert!(pre_roll.take().is_empty());
+
+        // A capacity of zero disables it
+        pre_roll.push(&[1.0, 2.0], 0);
+        assert!(pre_roll.take().is_empty());
+    }
+
+    #[test]
+    fn replays_a_file_that_ran_out_before_the_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("speech.wav");
+        let speech: Vec<f32> = (0..8000)
+            .map(|i| 0.25 + (i % 100) as f32 / 1000.0)
+            .collect();
+        std::fs::write(&path, super::super::encode_wav(&speech).unwrap()).unwrap();
+
+        let (sample_tx, sample_rx) = mpsc::channel();
+        let mut input = Input::new(sample_tx, None, None);
+        input
+            .start(InputSource::File {
+                path,
+                speed: f32::INFINITY,
+            })
+            .unwrap();
+        let next_chunk = || sample_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        let is_silent = |chunk: &[f32]| chunk.iter().all(|s| *s == 0.0);
+
+        // Like an always-open stream, the file is over before anyone records:
+        // the silence after it says so
+        while !is_silent(&next_chunk()) {}
+        input.replay();
+
+        let mut played = Vec::new();
+        while played.len() < speech.len() {
+            let chunk = next_chunk();
+            if !(played.is_empty() && is_silent(&chunk)) {
+                played.extend(chunk);
+            }
+        }
+        assert!(played
+            .iter()
+            .zip(&speech)
+            .all(|(got, want)| (got - want).abs() < 1e-3));
+    }
+
+    #[test]
+    fn device_watch_closes_and_reopens() {
+        // The chooser ranks a USB microphone first once it is plugged in
+        let plugged_in = Arc::new(AtomicBool::new(false));
+        let usb = plugged_in.clone();
+        let mut preferred = Polled::spawn(
+            move || {
+                let usb = usb.load(Ordering::Relaxed);
+                Some(if usb { "USB" } else { "Built-in" }.to_string())
+            },
+            Duration::from_millis(5),
+        );
+        let mut wait_for = |name: &str| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while preferred.latest().as_deref() != Some(name) {
+                assert!(Instant::now() < deadline, "chooser never returned {}", name);
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        };
+
+        let t0 = Instant::now();
+        let mut watch = DeviceWatch::new(t0);
+        watch.opened("Built-in".to_string(), t0);
+        wait_for("Built-in");
+        assert!(!watch.should_switch(false, Some("Built-in"), t0));
+        assert!(!watch.reopen_due(t0));
+
+        plugged_in.store(true, Ordering::Relaxed);
+        wait_for("USB");
+        assert!(watch.should_switch(false, Some("USB"), t0));
+        watch.closed(t0);
+        assert!(watch.reopen_due(t0));
+
+        // The USB microphone won't open, so the built-in one takes over again
+        // and polling leaves it alone
+        watch.failed_to_open(Some("USB".to_string()), 0);
+        assert!(!watch.reopened("Built-in".to_string(), 1, t0));
+        assert!(!watch.should_switch(false, Some("USB"), t0));
+
+        // A stalled stream is dropped; while nothing opens, retries are spaced out
+        let stalled = t0 + STALL_TIMEOUT;
+        assert!(watch.should_switch(false, None, stalled));
+        watch.closed(stalled);
+        watch.reopen_failed(stalled);
+        assert!(!watch.reopen_due(stalled));
+        assert!(watch.reopen_due(stalled + REOPEN_INTERVAL));
+
+        // Once the USB microphone opens it is no longer avoided
+        assert!(watch.reopened("USB".to_string(), 0, stalled + REOPEN_INTERVAL));
+        assert!(!watch.reopen_due(stalled + REOPEN_INTERVAL));
+        assert!(watch.broken_device.is_none());
+        assert!(!watch.should_switch(false, Some("USB"), stalled + REOPEN_INTERVAL));
+    }
+
+    #[test]
+    fn silence_watch_waits_for_speech_then_silence() {
+        let mut watch = SilenceWatch::new(300);
+        // Silence before anyone speaks only counts towards the timeout
+        assert!(!(0..10).any(|_| watch.push(false, 30)));
+        assert!(!watch.push(true, 30));
+        assert!(!(0..9).any(|_| watch.push(false, 30)));
+        assert!(watch.push(false, 30));
+
+        let mut watch = SilenceWatch::new(300);
+        let frames = (NO_SPEECH_TIMEOUT_MS / 30) as usize;
+        assert!(!(0..frames).any(|_| watch.push(false, 30)));
+        assert!(watch.push(false, 30));
+    }
+}
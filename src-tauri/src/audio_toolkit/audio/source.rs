@@ -0,0 +1,364 @@
+//! Stand-ins for a microphone, so the whole recording pipeline can run where
+//! there is no sound hardware: a file, or raw PCM from a named pipe or stdin,
+//! played into the recorder as if someone were speaking.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use cpal::Device;
+use log::{info, warn};
+
+use super::decode_audio_file;
+use crate::audio_toolkit::constants;
+
+/// Size of the blocks a virtual input delivers, like a sound card's buffer.
+const CHUNK_MS: usize = 10;
+
+/// Where a recorder gets its audio.
+///
+/// `speed` is 1.0 for real time, higher to play faster, or infinite to go as
+/// fast as the recorder keeps up. Once the audio runs out the input carries
+/// on with silence in real time, like a muted microphone.
+pub enum InputSource {
+    /// A microphone; the system default when `None`.
+    Device(Option<Device>),
+    /// Any file [`decode_audio_file`] can read. It plays from when the
+    /// recorder opens, and again from the top whenever a recording or
+    /// hands-free listening starts after it ran out, so an always-open
+    /// stream still hears it.
+    File { path: PathBuf, speed: f32 },
+    /// Raw signed 16-bit little-endian mono PCM from a file or named pipe,
+    /// or stdin when `path` is `None`. A pipe fed live should be read with
+    /// an infinite `speed`, since the writer already sets the pace.
+    Pcm {
+        path: Option<PathBuf>,
+        sample_rate: u32,
+        speed: f32,
+    },
+}
+
+impl From<Option<Device>> for InputSource {
+    fn from(device: Option<Device>) -> Self {
+        InputSource::Device(device)
+    }
+}
+
+fn parse_speed(speed: &str) -> Option<f32> {
+    if speed == "max" {
+        return Some(f32::INFINITY);
+    }
+    speed.parse().ok().filter(|s: &f32| *s > 0.0)
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    /// `file:PATH` or `pcm:RATE:PATH`, with `-` as the path for stdin, and
+    /// optionally `@SPEED` at the end, e.g. `file:hello.wav@4` or
+    /// `pcm:16000:-@max`.
+    fn from_str(spec: &str) -> Result<Self, String> {
+        let (spec, speed) = match spec
+            .rsplit_once('@')
+            .and_then(|(rest, speed)| Some((rest, parse_speed(speed)?)))
+        {
+            Some(split) => split,
+            None => (spec, 1.0),
+        };
+
+        if let Some(path) = spec.strip_prefix("file:") {
+            return Ok(InputSource::File {
+                path: PathBuf::from(path),
+                speed,
+            });
+        }
+        if let Some(rest) = spec.strip_prefix("pcm:") {
+            let (rate, path) = rest
+                .split_once(':')
+                .ok_or_else(|| format!("expected pcm:RATE:PATH, got '{spec}'"))?;
+            let sample_rate = rate
+                .parse()
+                .ok()
+                .filter(|rate: &u32| *rate > 0)
+                .ok_or_else(|| format!("invalid sample rate '{rate}'"))?;
+            return Ok(InputSource::Pcm {
+                path: (path != "-").then(|| PathBuf::from(path)),
+                sample_rate,
+                speed,
+            });
+        }
+        Err(format!("expected file:PATH or pcm:RATE:PATH, got '{spec}'"))
+    }
+}
+
+/// A virtual input playing on its own thread; stops when dropped.
+pub(super) struct Playback {
+    stop: Arc<AtomicBool>,
+    replay: Arc<AtomicBool>,
+}
+
+impl Playback {
+    /// Start sending `source` to `sample_tx`. Returns the playback with its
+    /// sample rate and a name to show for it.
+    pub(super) fn start(
+        source: InputSource,
+        sample_tx: mpsc::Sender<Vec<f32>>,
+    ) -> Result<(Self, u32, String), Box<dyn std::error::Error>> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let replay = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread_replay = replay.clone();
+
+        let (sample_rate, name) = match source {
+            InputSource::Device(_) => return Err("Not a virtual input".into()),
+            InputSource::File { path, speed } => {
+                let samples = decode_audio_file(&path)?;
+                let name = format!("file:{}", path.display());
+                let thread_name = name.clone();
+                let chunk_len = constants::WHISPER_SAMPLE_RATE as usize * CHUNK_MS / 1000;
+                thread::spawn(move || {
+                    feed(
+                        || Some(samples.chunks(chunk_len).map(<[f32]>::to_vec)),
+                        constants::WHISPER_SAMPLE_RATE,
+                        speed,
+                        &thread_name,
+                        sample_tx,
+                        thread_stop,
+                        thread_replay,
+                    );
+                });
+                (constants::WHISPER_SAMPLE_RATE, name)
+            }
+            InputSource::Pcm {
+                path,
+                sample_rate,
+                speed,
+            } => {
+                let name = match &path {
+                    Some(path) => format!("pcm:{}", path.display()),
+                    None => "pcm:stdin".to_string(),
+                };
+                let thread_name = name.clone();
+                thread::spawn(move || {
+                    // A stream can only be read once, so it is never replayed
+                    let mut path = Some(path);
+                    let open = || -> Option<PcmChunks<Box<dyn Read + Send>>> {
+                        // Opening a named pipe blocks until a writer shows up
+                        let reader: Box<dyn Read + Send> = match path.take()? {
+                            Some(path) => match File::open(&path) {
+                                Ok(file) => Box::new(file),
+                                Err(e) => {
+                                    warn!("Failed to open {}: {}", path.display(), e);
+                                    Box::new(io::empty())
+                                }
+                            },
+                            None => Box::new(io::stdin()),
+                        };
+                        Some(PcmChunks {
+                            reader,
+                            chunk_len: sample_rate as usize * CHUNK_MS / 1000,
+                        })
+                    };
+                    feed(
+                        open,
+                        sample_rate,
+                        speed,
+                        &thread_name,
+                        sample_tx,
+                        thread_stop,
+                        thread_replay,
+                    );
+                });
+                (sample_rate, name)
+            }
+        };
+
+        Ok((Self { stop, replay }, sample_rate, name))
+    }
+
+    /// Play the input again from the top if it has run out.
+    pub(super) fn replay(&self) {
+        self.replay.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Playback {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Send the chunks `open()` returns, then silence, at the pace of `speed`
+/// until stopped or the recorder hangs up. When asked to replay after the
+/// chunks ran out, `open()` is called again; it returns `None` if the input
+/// cannot be played twice.
+fn feed<I: Iterator<Item = Vec<f32>>>(
+    mut open: impl FnMut() -> Option<I>,
+    sample_rate: u32,
+    speed: f32,
+    name: &str,
+    sample_tx: mpsc::Sender<Vec<f32>>,
+    stop: Arc<AtomicBool>,
+    replay: Arc<AtomicBool>,
+) {
+    let silence_len = sample_rate as usize * CHUNK_MS / 1000;
+    let mut chunks = open();
+    let mut clock = Clock::new(sample_rate, speed);
+    let mut ended = false;
+
+    while !stop.load(Ordering::Relaxed) {
+        if replay.swap(false, Ordering::Relaxed) && ended {
+            if let Some(again) = open() {
+                info!("Playing virtual input '{}' again", name);
+                chunks = Some(again);
+                clock = Clock::new(sample_rate, speed);
+                ended = false;
+            }
+        }
+        let next = if ended {
+            None
+        } else {
+            chunks.as_mut().and_then(Iterator::next)
+        };
+        let chunk = match next {
+            Some(chunk) => chunk,
+            None => {
+                if !ended {
+                    info!("Virtual input '{}' ran out, carrying on with silence", name);
+                    ended = true;
+                    clock = Clock::new(sample_rate, 1.0);
+                }
+                vec![0.0; silence_len]
+            }
+        };
+        clock.wait(chunk.len());
+        if sample_tx.send(chunk).is_err() {
+            break;
+        }
+    }
+}
+
+/// Holds chunks back so they arrive no faster than `speed` times real time.
+struct Clock {
+    start: Instant,
+    samples_per_sec: f64,
+    sent: u64,
+}
+
+impl Clock {
+    fn new(sample_rate: u32, speed: f32) -> Self {
+        Self {
+            start: Instant::now(),
+            samples_per_sec: sample_rate as f64 * speed as f64,
+            sent: 0,
+        }
+    }
+
+    /// Wait until a microphone would have delivered `samples` more.
+    fn wait(&mut self, samples: usize) {
+        self.sent += samples as u64;
+        if !self.samples_per_sec.is_finite() {
+            return;
+        }
+        let due = self.start + Duration::from_secs_f64(self.sent as f64 / self.samples_per_sec);
+        if let Some(delay) = due.checked_duration_since(Instant::now()) {
+            thread::sleep(delay);
+        }
+    }
+}
+
+/// Signed 16-bit little-endian samples, `chunk_len` at a time.
+struct PcmChunks<R> {
+    reader: R,
+    chunk_len: usize,
+}
+
+impl<R: Read> Iterator for PcmChunks<R> {
+    type Item = Vec<f32>;
+
+    fn next(&mut self) -> Option<Vec<f32>> {
+        let mut bytes = vec![0u8; self.chunk_len * 2];
+        let mut filled = 0;
+        while filled < bytes.len() {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    warn!("Failed to read PCM input: {}", e);
+                    break;
+                }
+            }
+        }
+        // A trailing odd byte can only be cut off at the end of the input
+        filled -= filled % 2;
+        if filled == 0 {
+            return None;
+        }
+        Some(
+            bytes[..filled]
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_source_specs() {
+        match "file:/tmp/a@b.wav@4".parse() {
+            Ok(InputSource::File { path, speed }) => {
+                assert_eq!(path, PathBuf::from("/tmp/a@b.wav"));
+                assert_eq!(speed, 4.0);
+            }
+            _ => panic!("expected a file source"),
+        }
+        match "pcm:48000:-@max".parse() {
+            Ok(InputSource::Pcm {
+                path,
+                sample_rate,
+                speed,
+            }) => {
+                assert!(path.is_none());
+                assert_eq!(sample_rate, 48000);
+                assert!(speed.is_infinite());
+            }
+            _ => panic!("expected a PCM source"),
+        }
+        match "file:/tmp/a@home.wav".parse() {
+            Ok(InputSource::File { path, speed }) => {
+                assert_eq!(path, PathBuf::from("/tmp/a@home.wav"));
+                assert_eq!(speed, 1.0);
+            }
+            _ => panic!("expected a file source"),
+        }
+        assert!("pcm:fast:/tmp/fifo".parse::<InputSource>().is_err());
+        assert!("/tmp/a.wav".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn reads_pcm_in_chunks() {
+        let bytes: Vec<u8> = [0i16, 16384, -32768, 8192, 1]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .chain([0x7f])
+            .collect();
+        let mut chunks = PcmChunks {
+            reader: bytes.as_slice(),
+            chunk_len: 2,
+        };
+        assert_eq!(chunks.next(), Some(vec![0.0, 0.5]));
+        assert_eq!(chunks.next(), Some(vec![-1.0, 0.25]));
+        assert_eq!(chunks.next(), Some(vec![1.0 / 32768.0]));
+        assert_eq!(chunks.next(), None);
+    }
+}
@@ -1,8 +1,19 @@
 use anyhow::{Context, Result};
 use hound::{WavReader, WavSpec, WavWriter};
 use log::debug;
+use std::io::Cursor;
 use std::path::Path;
 
+/// Format used for every WAV file the app writes: 16 kHz mono 16-bit PCM
+fn wav_spec() -> WavSpec {
+    WavSpec {
+        channels: 1,
+        sample_rate: 16000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    }
+}
+
 /// Load audio samples from a WAV file
 pub fn load_wav_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
     let reader = WavReader::open(file_path.as_ref())
@@ -36,14 +47,7 @@ pub fn load_wav_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
 
 /// Save audio samples as a WAV file
 pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
-    let spec = WavSpec {
-        channels: 1,
-        sample_rate: 16000,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
-
-    let mut writer = WavWriter::create(file_path.as_ref(), spec)?;
+    let mut writer = WavWriter::create(file_path.as_ref(), wav_spec())?;
 
     // Convert f32 samples to i16 for WAV
     for sample in samples {
@@ -55,3 +59,17 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
     debug!("Saved WAV file: {:?}", file_path.as_ref());
     Ok(())
 }
+
+/// Encode audio samples as an in-memory WAV file
+pub fn encode_wav(samples: &[f32]) -> Result<Vec<u8>> {
+    let mut cursor = Cursor::new(Vec::new());
+    {
+        let mut writer = WavWriter::new(&mut cursor, wav_spec())?;
+        for sample in samples {
+            let sample_i16 = (sample * i16::MAX as f32) as i16;
+            writer.write_sample(sample_i16)?;
+        }
+        writer.finalize()?;
+    }
+    Ok(cursor.into_inner())
+}
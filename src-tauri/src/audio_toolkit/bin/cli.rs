@@ -2,11 +2,15 @@ use hound::WavWriter;
 use std::io::{self, Write};
 
 use echo_app_lib::audio_toolkit::{
-    audio::{list_input_devices, CpalDeviceInfo},
-    vad::SmoothedVad,
-    AudioRecorder, SileroVad,
+    audio::{
+        decode_audio_file, dsp::process_offline, list_input_devices, CpalDeviceInfo, DspConfig,
+    },
+    vad::{speech_regions, EnergyVad, GmmVad, SmoothedVad, SpeechRegion, VadParams},
+    AudioRecorder, SileroVad, VoiceActivityDetector,
 };
 
+const VAD_MODEL_PATH: &str = "./resources/models/silero_vad_v4.onnx";
+
 #[derive(Debug, Clone, PartialEq)]
 enum RecorderMode {
     AlwaysOn,
@@ -110,7 +114,7 @@ impl RecorderState {
                     self.current_device_index = device_index;
                     println!("Opened recorder in Always-On mode");
                 }
-                self.recorder.start()?;
+                self.recorder.start(None)?;
             }
             RecorderMode::OnDemand => {
                 // In on-demand mode, open for each recording
@@ -120,7 +124,7 @@ impl RecorderState {
                 self.recorder.open(device)?;
                 self.is_open = true;
                 self.current_device_index = device_index;
-                self.recorder.start()?;
+                self.recorder.start(None)?;
                 println!("Opened and started recorder in On-Demand mode");
             }
         }
@@ -175,8 +179,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=========================");
     print_help();
 
-    let silero = SileroVad::new("./resources/models/silero_vad_v4.onnx", 0.5)?;
-    let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15);
+    let silero = SileroVad::new(VAD_MODEL_PATH, 0.5)?;
+    let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
     let recorder = AudioRecorder::new()?.with_vad(Box::new(smoothed_vad));
     let mut state = RecorderState::new(recorder);
 
@@ -275,6 +279,32 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
                 println!("  Next Recording: recording_{}.wav", state.recording_index);
             }
+            "process" | "p" => {
+                if parts.len() < 3 {
+                    println!("Usage: process <input.wav> <output.wav> [hp] [ns] [agc]");
+                    continue;
+                }
+                let config = match parse_dsp_stages(&parts[3..]) {
+                    Ok(config) => config,
+                    Err(e) => {
+                        println!("{}", e);
+                        continue;
+                    }
+                };
+                match process_file(parts[1], parts[2], config) {
+                    Ok(_) => println!("Processed audio saved as: {}", parts[2]),
+                    Err(e) => println!("Error processing {}: {}", parts[1], e),
+                }
+            }
+            "vad" => {
+                if parts.len() < 2 {
+                    println!("Usage: vad <input.wav> [threshold=0.3] [prefill=15] [hangover=15] [onset=2] [engine=silero] [labels=<out.txt>]");
+                    continue;
+                }
+                if let Err(e) = run_vad(parts[1], &parts[2..]) {
+                    println!("Error running VAD on {}: {}", parts[1], e);
+                }
+            }
             "help" | "h" => {
                 print_help();
             }
@@ -320,6 +350,12 @@ fn print_help() {
     );
     println!("  devices | dev                             - List available audio devices");
     println!("  status                                    - Show current recorder status");
+    println!(
+        "  process <in> <out> [hp] [ns] [agc]        - Clean up a WAV file offline (all stages by default)"
+    );
+    println!(
+        "  vad <in> [key=value ...]                  - Print the speech regions the VAD finds in a WAV file"
+    );
     println!("  help | h                                  - Show this help message");
     println!("  quit | exit | q                           - Exit the program");
     println!();
@@ -327,6 +363,14 @@ fn print_help() {
     println!("  Always-On: Keeps recorder open for quick start/stop cycles");
     println!("  On-Demand: Opens/closes recorder for each recording session");
     println!();
+    println!("VAD options:");
+    println!("  threshold, prefill, hangover, onset: tuning, as in the app's settings");
+    println!("  engine=silero|energy|gmm: the detector to run (default silero)");
+    println!("  labels=<file>: also export the regions as an Audacity label track");
+    println!();
+    println!("Processing stages:");
+    println!("  hp: high-pass filter, ns: noise suppression, agc: automatic gain");
+    println!();
 }
 
 fn print_devices(devices: &[CpalDeviceInfo]) {
@@ -337,6 +381,116 @@ fn print_devices(devices: &[CpalDeviceInfo]) {
     println!();
 }
 
+/// Run the VAD over a file the way the recorder does and report where it
+/// hears speech.
+fn run_vad(input: &str, options: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut params = VadParams::default();
+    let mut engine = "silero";
+    let mut labels_path = None;
+    for option in options {
+        let (key, value) = option
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got '{}'", option))?;
+        match key {
+            "threshold" => params.threshold = value.parse()?,
+            "prefill" => params.prefill_frames = value.parse()?,
+            "hangover" => params.hangover_frames = value.parse()?,
+            "onset" => params.onset_frames = value.parse()?,
+            "engine" => engine = value,
+            "labels" => labels_path = Some(value),
+            _ => return Err(format!("Unknown option '{}'", key).into()),
+        }
+    }
+
+    let detector: Box<dyn VoiceActivityDetector> = match engine {
+        "silero" => Box::new(SileroVad::new(VAD_MODEL_PATH, params.threshold)?),
+        "energy" => Box::new(EnergyVad::new(params.threshold)),
+        "gmm" => Box::new(GmmVad::new(params.threshold)),
+        _ => return Err(format!("Unknown VAD engine '{}'", engine).into()),
+    };
+    let mut vad = SmoothedVad::new(
+        detector,
+        params.prefill_frames,
+        params.hangover_frames,
+        params.onset_frames,
+    );
+    let samples = decode_audio_file(input)?;
+    let regions = speech_regions(&mut vad, &samples)?;
+
+    println!("{} VAD, {:?}", engine, params);
+    for region in &regions {
+        println!(
+            "  {:>8.2}s - {:>8.2}s  ({:.2}s)",
+            region.start_ms as f64 / 1000.0,
+            region.end_ms as f64 / 1000.0,
+            region.duration_ms() as f64 / 1000.0
+        );
+    }
+    let speech_ms: u64 = regions.iter().map(SpeechRegion::duration_ms).sum();
+    let total_ms = samples.len() as u64 * 1000 / 16000;
+    println!(
+        "{} regions, {:.1}s of speech in {:.1}s ({:.0}%)",
+        regions.len(),
+        speech_ms as f64 / 1000.0,
+        total_ms as f64 / 1000.0,
+        speech_ms as f64 * 100.0 / total_ms.max(1) as f64
+    );
+
+    if let Some(path) = labels_path {
+        save_labels(&regions, path)?;
+        println!("Labels saved as: {}", path);
+    }
+    Ok(())
+}
+
+/// Write regions as an Audacity label track, to view them next to the audio.
+fn save_labels(regions: &[SpeechRegion], filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut file = std::fs::File::create(filename)?;
+    for region in regions {
+        writeln!(
+            file,
+            "{:.3}\t{:.3}\tspeech",
+            region.start_ms as f64 / 1000.0,
+            region.end_ms as f64 / 1000.0
+        )?;
+    }
+    Ok(())
+}
+
+/// Stages named on the command line; all of them if none are.
+fn parse_dsp_stages(stages: &[&str]) -> Result<DspConfig, String> {
+    if stages.is_empty() {
+        return Ok(DspConfig {
+            high_pass: true,
+            noise_suppression: true,
+            auto_gain: true,
+        });
+    }
+
+    let mut config = DspConfig::default();
+    for stage in stages {
+        match stage.to_lowercase().as_str() {
+            "hp" | "highpass" => config.high_pass = true,
+            "ns" | "noise" => config.noise_suppression = true,
+            "agc" | "gain" => config.auto_gain = true,
+            other => return Err(format!("Unknown stage '{}'. Use hp, ns or agc", other)),
+        }
+    }
+    Ok(config)
+}
+
+/// Run the recorder's processing chain over a file, so its effect can be
+/// compared with the original.
+fn process_file(
+    input: &str,
+    output: &str,
+    config: DspConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let samples = decode_audio_file(input)?;
+    let processed = process_offline(config, &samples);
+    save_audio(&processed, output)
+}
+
 fn save_audio(samples: &[f32], filename: &str) -> Result<(), Box<dyn std::error::Error>> {
     let spec = hound::WavSpec {
         channels: 1,
@@ -0,0 +1,222 @@
+//! Keyword spotting for wake words, on top of sherpa-onnx's streaming
+//! zipformer transducers.
+
+use anyhow::{Context, Result};
+use sherpa_rs::keyword_spot::{KeywordSpot, KeywordSpotConfig};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// Audio handed to the model at once; smaller batches waste time in the
+/// feature extractor.
+const BATCH_MS: usize = 100;
+const BATCH_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize * BATCH_MS / 1000;
+
+/// Marks the start of a word in the model's BPE tokens.
+const WORD_START: char = '▁';
+
+pub trait KeywordSpotter: Send {
+    /// Feed one frame of 16kHz audio; returns the label of a keyword if one
+    /// was just heard.
+    fn push_frame(&mut self, frame: &[f32]) -> Result<Option<String>>;
+
+    /// Forget any partially heard keyword.
+    fn reset(&mut self) {}
+}
+
+/// A spotter the recorder can run, swapped in and out as settings change.
+pub type SharedKeywordSpotter = Arc<Mutex<Option<Box<dyn KeywordSpotter>>>>;
+
+#[derive(Clone, Debug)]
+pub struct Keyword {
+    /// Reported when the keyword is heard.
+    pub label: String,
+    pub phrase: String,
+    /// Space-separated model tokens, for phrases the built-in tokenizer
+    /// splits badly.
+    pub tokens: Option<String>,
+}
+
+pub struct SherpaKeywordSpotter {
+    spotter: KeywordSpot,
+    pending: Vec<f32>,
+}
+
+impl SherpaKeywordSpotter {
+    /// Load the model in `model_dir` and listen for `keywords`.
+    ///
+    /// `sensitivity` runs from 0.0 (fewest false alarms) to 1.0 (fewest
+    /// missed keywords). sherpa-onnx reads keywords from a file, which is
+    /// written to `keywords_file`.
+    pub fn new(
+        model_dir: &Path,
+        keywords: &[Keyword],
+        sensitivity: f32,
+        keywords_file: &Path,
+    ) -> Result<Self> {
+        if keywords.is_empty() {
+            anyhow::bail!("No wake words configured");
+        }
+
+        let tokens_path = model_dir.join("tokens.txt");
+        let vocabulary = load_tokens(&tokens_path)?;
+        let mut lines = Vec::with_capacity(keywords.len());
+        for keyword in keywords {
+            let tokens = match keyword.tokens.as_deref().map(str::trim) {
+                Some(tokens) if !tokens.is_empty() => tokens.to_string(),
+                _ => tokenize(&keyword.phrase, &vocabulary).with_context(|| {
+                    format!(
+                        "Cannot spell wake word {:?} with the model's tokens",
+                        keyword.phrase
+                    )
+                })?,
+            };
+            lines.push(format!("{} @{}", tokens, keyword.label));
+        }
+        std::fs::write(keywords_file, lines.join("\n") + "\n")
+            .with_context(|| format!("Failed to write keywords file {:?}", keywords_file))?;
+
+        let sensitivity = sensitivity.clamp(0.0, 1.0);
+        let config = KeywordSpotConfig {
+            zipformer_encoder: path_string(&find_model_file(model_dir, "encoder")?),
+            zipformer_decoder: path_string(&find_model_file(model_dir, "decoder")?),
+            zipformer_joiner: path_string(&find_model_file(model_dir, "joiner")?),
+            tokens: path_string(&tokens_path),
+            keywords: path_string(keywords_file),
+            max_active_path: 4,
+            keywords_threshold: 0.5 - 0.45 * sensitivity,
+            keywords_score: 1.0 + 2.0 * sensitivity,
+            num_trailing_blanks: 1,
+            provider: None,
+            num_threads: Some(1),
+            debug: false,
+            ..Default::default()
+        };
+        let spotter = KeywordSpot::new(config)
+            .map_err(|e| anyhow::anyhow!("Failed to load keyword spotter: {e}"))?;
+
+        Ok(Self {
+            spotter,
+            pending: Vec::with_capacity(BATCH_SAMPLES),
+        })
+    }
+}
+
+impl KeywordSpotter for SherpaKeywordSpotter {
+    fn push_frame(&mut self, frame: &[f32]) -> Result<Option<String>> {
+        self.pending.extend_from_slice(frame);
+        if self.pending.len() < BATCH_SAMPLES {
+            return Ok(None);
+        }
+
+        let samples = std::mem::replace(&mut self.pending, Vec::with_capacity(BATCH_SAMPLES));
+        let keyword = self
+            .spotter
+            .extract_keyword(samples, WHISPER_SAMPLE_RATE)
+            .map_err(|e| anyhow::anyhow!("Keyword spotting failed: {e}"))?;
+        Ok(keyword
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty()))
+    }
+
+    /// The stream itself starts over after each keyword, so only the audio
+    /// not yet decoded is dropped.
+    fn reset(&mut self) {
+        self.pending.clear();
+    }
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// The `encoder`, `decoder` or `joiner` ONNX file in a model directory,
+/// preferring the int8 build.
+fn find_model_file(model_dir: &Path, part: &str) -> Result<PathBuf> {
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(model_dir)
+        .with_context(|| format!("Failed to read model directory {:?}", model_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with(part) && name.ends_with(".onnx")
+        })
+        .collect();
+    candidates.sort_by_key(|path| !path.to_string_lossy().contains("int8"));
+    candidates
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No {} model in {:?}", part, model_dir))
+}
+
+fn load_tokens(path: &Path) -> Result<HashSet<String>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read model tokens {:?}", path))?;
+    Ok(parse_tokens(&text))
+}
+
+/// Tokens from a `tokens.txt`, one "<token> <id>" per line.
+fn parse_tokens(text: &str) -> HashSet<String> {
+    text.lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Spell a phrase with the model's BPE tokens, taking the longest token that
+/// fits at each step.
+fn tokenize(phrase: &str, vocabulary: &HashSet<String>) -> Result<String> {
+    let mut tokens = Vec::new();
+    for word in phrase.split_whitespace() {
+        let word: Vec<char> = std::iter::once(WORD_START)
+            .chain(word.to_uppercase().chars())
+            .collect();
+        let mut start = 0;
+        while start < word.len() {
+            let token = (start + 1..=word.len())
+                .rev()
+                .map(|end| word[start..end].iter().collect::<String>())
+                .find(|candidate| vocabulary.contains(candidate))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No token for {:?}",
+                        word[start..].iter().collect::<String>()
+                    )
+                })?;
+            start += token.chars().count();
+            tokens.push(token);
+        }
+    }
+    if tokens.is_empty() {
+        anyhow::bail!("Wake word is empty");
+    }
+    Ok(tokens.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocabulary() -> HashSet<String> {
+        parse_tokens("<blk> 0\n▁HE 1\n▁E 2\nY 3\nCH 4\nO 5\nC 6\nH 7\n▁ 8\n")
+    }
+
+    #[test]
+    fn phrases_are_spelled_with_the_longest_tokens() {
+        assert_eq!(
+            tokenize("hey echo", &vocabulary()).unwrap(),
+            "▁HE Y ▁E CH O"
+        );
+        assert_eq!(
+            tokenize("  Hey   ECHO ", &vocabulary()).unwrap(),
+            "▁HE Y ▁E CH O"
+        );
+    }
+
+    #[test]
+    fn unknown_letters_and_empty_phrases_are_rejected() {
+        assert!(tokenize("hey zed", &vocabulary()).is_err());
+        assert!(tokenize("   ", &vocabulary()).is_err());
+    }
+}
@@ -1,13 +1,17 @@
 pub mod audio;
 pub mod constants;
+pub mod kws;
+#[cfg(test)]
+mod signals;
 pub mod text;
 pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, load_wav_file, save_wav_file, AudioRecorder,
-    CpalDeviceInfo,
+    encode_wav, list_input_devices, list_output_devices, load_wav_file, save_wav_file,
+    AudioRecorder, CpalDeviceInfo,
 };
-pub use text::apply_custom_words;
+pub use kws::{Keyword, KeywordSpotter, SharedKeywordSpotter, SherpaKeywordSpotter};
+pub use text::{apply_custom_word_spans, apply_custom_words, detect_text_language};
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
@@ -0,0 +1,67 @@
+//! Synthetic audio for testing the processing stages and detectors.
+
+use std::f32::consts::PI;
+
+use super::constants::WHISPER_SAMPLE_RATE;
+use super::VoiceActivityDetector;
+
+const RATE: f32 = WHISPER_SAMPLE_RATE as f32;
+const FRAME_SAMPLES: usize = (WHISPER_SAMPLE_RATE / 1000 * 30) as usize;
+
+/// A pure tone.
+pub fn sine(hz: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+    (0..(RATE * seconds) as usize)
+        .map(|i| amplitude * (2.0 * PI * hz * i as f32 / RATE).sin())
+        .collect()
+}
+
+/// Deterministic white noise in [-amplitude, amplitude].
+pub fn noise(amplitude: f32, seconds: f32, seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..(RATE * seconds) as usize)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            amplitude * ((state >> 8) as f32 / (1 << 23) as f32 - 1.0)
+        })
+        .collect()
+}
+
+/// A steady vowel-like sound: a pitch with harmonics falling off up to 4kHz.
+pub fn voiced(pitch_hz: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+    let harmonics = (4000.0 / pitch_hz) as usize;
+    let norm: f32 = (1..=harmonics).map(|k| 1.0 / k as f32).sum();
+    (0..(RATE * seconds) as usize)
+        .map(|i| {
+            let t = i as f32 / RATE;
+            let sum: f32 = (1..=harmonics)
+                .map(|k| (2.0 * PI * pitch_hz * k as f32 * t).sin() / k as f32)
+                .sum();
+            1.5 * amplitude * sum / norm
+        })
+        .collect()
+}
+
+/// `voice` added onto `background`, starting `at_seconds` in.
+pub fn mix(background: &[f32], voice: &[f32], at_seconds: f32) -> Vec<f32> {
+    let start = (RATE * at_seconds) as usize;
+    let mut mixed = background.to_vec();
+    for (sample, v) in mixed[start..].iter_mut().zip(voice) {
+        *sample += v;
+    }
+    mixed
+}
+
+/// The detector's verdict on each 30ms frame.
+pub fn decisions(vad: &mut dyn VoiceActivityDetector, samples: &[f32]) -> Vec<bool> {
+    samples
+        .chunks_exact(FRAME_SAMPLES)
+        .map(|frame| vad.is_voice(frame).unwrap())
+        .collect()
+}
+
+/// Share of frames between two points in time judged to be speech.
+pub fn speech_share(decisions: &[bool], from_seconds: f32, to_seconds: f32) -> f32 {
+    let frame = |seconds: f32| (seconds * 1000.0 / 30.0) as usize;
+    let window = &decisions[frame(from_seconds)..frame(to_seconds).min(decisions.len())];
+    window.iter().filter(|&&speech| speech).count() as f32 / window.len() as f32
+}
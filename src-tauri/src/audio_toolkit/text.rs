@@ -93,6 +93,110 @@ pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -
     corrected_words.join(" ")
 }
 
+/// Longest run of transcribed words that can be merged into a single custom word
+const MAX_SPAN_WORDS: usize = 4;
+
+/// Applies custom words over spans of transcribed words
+///
+/// Post-hoc fuzzy replacement like [`apply_custom_words`], but runs of
+/// consecutive words are compared against each custom word with spaces
+/// removed, so a term split by the recognizer ("chat gpt", "pie torch") is
+/// rejoined into the configured spelling. Single words are corrected too.
+///
+/// # Arguments
+/// * `text` - The input text to correct
+/// * `custom_words` - List of custom words or phrases
+/// * `threshold` - Maximum similarity score to accept (0.0 = exact match, 1.0 = any match)
+///
+/// # Returns
+/// The text with matching spans replaced by their custom word
+pub fn apply_custom_word_spans(text: &str, custom_words: &[String], threshold: f64) -> String {
+    if custom_words.is_empty() {
+        return text.to_string();
+    }
+
+    let keys: Vec<(String, usize)> = custom_words
+        .iter()
+        .map(|h| (normalize_key(h), h.split_whitespace().count().max(1)))
+        .collect();
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut result = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        // (span length, custom word index, score)
+        let mut best: Option<(usize, usize, f64)> = None;
+
+        for (h, (key, key_words)) in keys.iter().enumerate() {
+            if key.is_empty() {
+                continue;
+            }
+            let max_span = (key_words + 1).min(MAX_SPAN_WORDS).min(words.len() - i);
+
+            for span in 1..=max_span {
+                let joined: String = words[i..i + span]
+                    .iter()
+                    .map(|w| normalize_key(w))
+                    .collect();
+                if joined.is_empty() {
+                    continue;
+                }
+
+                let score = if joined == *key {
+                    0.0
+                } else {
+                    // Short fragments match too many ordinary words
+                    if joined.len() < 3 {
+                        continue;
+                    }
+                    let max_len = joined.len().max(key.len()) as f64;
+                    let levenshtein_score = levenshtein(&joined, key) as f64 / max_len;
+                    if soundex(&joined, key) {
+                        levenshtein_score * 0.3
+                    } else {
+                        levenshtein_score
+                    }
+                };
+
+                let better = match best {
+                    None => true,
+                    Some((best_span, _, best_score)) => {
+                        score < best_score || (score == best_score && span > best_span)
+                    }
+                };
+                if score < threshold && better {
+                    best = Some((span, h, score));
+                }
+            }
+        }
+
+        match best {
+            Some((span, h, _)) => {
+                let (prefix, _) = extract_punctuation(words[i]);
+                let (_, suffix) = extract_punctuation(words[i + span - 1]);
+                let replacement = preserve_case_pattern(words[i], &custom_words[h]);
+                result.push(format!("{}{}{}", prefix, replacement, suffix));
+                i += span;
+            }
+            None => {
+                result.push(words[i].to_string());
+                i += 1;
+            }
+        }
+    }
+
+    result.join(" ")
+}
+
+/// Lowercased alphanumeric characters only, used to compare spans with custom words
+fn normalize_key(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
 /// Preserves the case pattern of the original word when applying a replacement
 fn preserve_case_pattern(original: &str, replacement: &str) -> String {
     if original.chars().all(|c| c.is_uppercase()) {
@@ -132,6 +236,35 @@ fn extract_punctuation(word: &str) -> (&str, &str) {
     (prefix, suffix)
 }
 
+/// Guess the language of a text, as an ISO 639-1 code.
+///
+/// For engines that don't report the language they recognised. Returns
+/// `None` for text without letters.
+pub fn detect_text_language(text: &str) -> Option<&'static str> {
+    if !text.chars().any(char::is_alphabetic) {
+        return None;
+    }
+    let code = match whichlang::detect_language(text) {
+        whichlang::Lang::Ara => "ar",
+        whichlang::Lang::Cmn => "zh",
+        whichlang::Lang::Deu => "de",
+        whichlang::Lang::Eng => "en",
+        whichlang::Lang::Fra => "fr",
+        whichlang::Lang::Hin => "hi",
+        whichlang::Lang::Ita => "it",
+        whichlang::Lang::Jpn => "ja",
+        whichlang::Lang::Kor => "ko",
+        whichlang::Lang::Nld => "nl",
+        whichlang::Lang::Por => "pt",
+        whichlang::Lang::Rus => "ru",
+        whichlang::Lang::Spa => "es",
+        whichlang::Lang::Swe => "sv",
+        whichlang::Lang::Tur => "tr",
+        whichlang::Lang::Vie => "vi",
+    };
+    Some(code)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +299,36 @@ mod tests {
         assert_eq!(extract_punctuation("...hello..."), ("...", "..."));
     }
 
+    #[test]
+    fn test_apply_custom_word_spans_joins_split_terms() {
+        let custom_words = vec!["ChatGPT".to_string(), "PyTorch".to_string()];
+        let result = apply_custom_word_spans("ask chat gpt about pie torch.", &custom_words, 0.18);
+        assert_eq!(result, "ask ChatGPT about PyTorch.");
+    }
+
+    #[test]
+    fn test_apply_custom_word_spans_multi_word_phrase() {
+        let custom_words = vec!["New York".to_string()];
+        let result = apply_custom_word_spans("flying to newyork today", &custom_words, 0.18);
+        assert_eq!(result, "flying to New York today");
+    }
+
+    #[test]
+    fn test_apply_custom_word_spans_leaves_unrelated_words() {
+        let custom_words = vec!["Kubernetes".to_string()];
+        let result = apply_custom_word_spans("the cat sat on a mat", &custom_words, 0.18);
+        assert_eq!(result, "the cat sat on a mat");
+    }
+
+    #[test]
+    fn test_detect_text_language() {
+        assert_eq!(
+            detect_text_language("Bonjour, je voudrais un café s'il vous plaît."),
+            Some("fr")
+        );
+        assert_eq!(detect_text_language(" 42. "), None);
+    }
+
     #[test]
     fn test_empty_custom_words() {
         let text = "hello world";
@@ -0,0 +1,102 @@
+//! A detector that only looks at loudness and zero crossings: speech is
+//! clearly louder than the recent background and, unlike hiss, crosses zero
+//! fairly rarely. Crude, but it costs next to nothing.
+
+use anyhow::Result;
+
+use super::level::{energy_db, NoiseFloor, MIN_ENERGY_DB};
+use super::{VadFrame, VadParams, VoiceActivityDetector};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+const FRAME_SAMPLES: usize = (WHISPER_SAMPLE_RATE / 1000 * 30) as usize;
+
+/// Margin over the noise floor needed at a threshold of 0.0...
+const MIN_SNR_DB: f32 = 3.0;
+/// ...growing by this much up to a threshold of 1.0.
+const SNR_RANGE_DB: f32 = 20.0;
+
+/// Share of samples that change sign above which a frame is taken for
+/// noise. White noise sits around 0.5, voiced speech well below 0.2.
+const MAX_ZERO_CROSSING_RATE: f32 = 0.3;
+
+pub struct EnergyVad {
+    threshold: f32,
+    floor: NoiseFloor,
+}
+
+impl EnergyVad {
+    /// `threshold` works as for the other detectors: higher needs louder
+    /// speech.
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold: threshold.clamp(0.0, 1.0),
+            floor: NoiseFloor::new(),
+        }
+    }
+
+    fn required_snr_db(&self) -> f32 {
+        MIN_SNR_DB + SNR_RANGE_DB * self.threshold
+    }
+}
+
+fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    let crossings = frame
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
+
+impl VoiceActivityDetector for EnergyVad {
+    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+        if frame.len() != FRAME_SAMPLES {
+            anyhow::bail!("expected {FRAME_SAMPLES} samples, got {}", frame.len());
+        }
+
+        let level = energy_db(frame);
+        if level < MIN_ENERGY_DB {
+            return Ok(VadFrame::Noise);
+        }
+        let floor = self.floor.push(level);
+
+        if level - floor > self.required_snr_db()
+            && zero_crossing_rate(frame) < MAX_ZERO_CROSSING_RATE
+        {
+            Ok(VadFrame::Speech(frame))
+        } else {
+            Ok(VadFrame::Noise)
+        }
+    }
+
+    fn configure(&mut self, params: &VadParams) {
+        self.threshold = params.threshold.clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::signals::{decisions, noise, speech_share, voiced};
+
+    #[test]
+    fn voice_stands_out_from_quiet_noise() {
+        let mut samples = noise(0.01, 1.0, 1);
+        samples.extend(voiced(150.0, 0.2, 1.0));
+        samples.extend(noise(0.01, 1.0, 2));
+
+        let speech = decisions(&mut EnergyVad::new(0.3), &samples);
+        assert!(speech_share(&speech, 0.3, 1.0) < 0.1);
+        assert!(speech_share(&speech, 1.05, 2.0) > 0.9);
+        assert!(speech_share(&speech, 2.3, 3.0) < 0.1);
+    }
+
+    #[test]
+    fn loud_hiss_and_silence_are_not_voice() {
+        let mut samples = noise(0.005, 1.0, 1);
+        samples.extend(noise(0.2, 1.0, 2));
+        samples.extend(vec![0.0; 16000]);
+
+        let speech = decisions(&mut EnergyVad::new(0.3), &samples);
+        assert!(speech.iter().all(|&voice| !voice));
+    }
+}
@@ -0,0 +1,262 @@
+//! A WebRTC-style detector: the log energy of six frequency bands is scored
+//! against two Gaussian mixtures per band, one for noise and one for speech,
+//! which keep adapting to the audio. Needs no model file and far less CPU
+//! than Silero.
+
+use anyhow::Result;
+use rustfft::{num_complex::Complex32, Fft, FftPlanner};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+use super::level::{energy_db, NoiseFloor, MIN_ENERGY_DB};
+use super::{VadFrame, VadParams, VoiceActivityDetector};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+const FRAME_SAMPLES: usize = (WHISPER_SAMPLE_RATE / 1000 * 30) as usize;
+const FFT_SIZE: usize = 512;
+
+/// Frequency bands in Hz, as in WebRTC.
+const BANDS: [(f32, f32); 6] = [
+    (80.0, 250.0),
+    (250.0, 500.0),
+    (500.0, 1000.0),
+    (1000.0, 2000.0),
+    (2000.0, 3000.0),
+    (3000.0, 4000.0),
+];
+/// How much each band counts towards the decision.
+const BAND_WEIGHTS: [f32; 6] = [6.0, 8.0, 10.0, 12.0, 14.0, 16.0];
+
+/// Starting mean and spread of the two components of each mixture, in dB.
+const NOISE_START: [(f32, f32); 2] = [(-70.0, 6.0), (-60.0, 6.0)];
+const SPEECH_START: [(f32, f32); 2] = [(-45.0, 10.0), (-30.0, 10.0)];
+
+/// How fast a mixture follows the frames it is judged to have produced.
+const NOISE_RATE: f32 = 0.05;
+const SPEECH_RATE: f32 = 0.02;
+const MIN_STD: f32 = 2.0;
+const MAX_STD: f32 = 15.0;
+
+/// Noise components stay at least this far above the band's noise floor,
+/// and drift back down towards it, so the noise model catches up with a
+/// background that got louder even while it is being mistaken for speech.
+const FLOOR_OFFSETS_DB: [f32; 2] = [2.0, 5.0];
+const FLOOR_PULL: f32 = 0.05;
+
+/// Speech components stay at least this far above the loudest noise one.
+const MIN_SEPARATION_DB: f32 = 8.0;
+
+/// No single band can swing the decision by more than this.
+const MAX_BAND_LLR: f32 = 5.0;
+const MIN_FEATURE_DB: f32 = -100.0;
+
+#[derive(Clone, Copy)]
+struct Gaussian {
+    mean: f32,
+    std: f32,
+}
+
+impl Gaussian {
+    fn log_density(&self, x: f32) -> f32 {
+        let z = (x - self.mean) / self.std;
+        -0.5 * z * z - self.std.ln() - 0.5 * (2.0 * PI).ln()
+    }
+
+    /// Move mean and spread towards `x` at `rate`.
+    fn adapt(&mut self, x: f32, rate: f32) {
+        let delta = x - self.mean;
+        self.mean += rate * delta;
+        let variance = self.std * self.std + rate * (delta * delta - self.std * self.std);
+        self.std = variance.max(0.0).sqrt().clamp(MIN_STD, MAX_STD);
+    }
+}
+
+/// Two equally weighted components.
+type Mixture = [Gaussian; 2];
+
+fn mixture(start: [(f32, f32); 2]) -> Mixture {
+    start.map(|(mean, std)| Gaussian { mean, std })
+}
+
+fn log_sum_exp(values: [f32; 2]) -> f32 {
+    let max = values[0].max(values[1]);
+    max + values.iter().map(|v| (v - max).exp()).sum::<f32>().ln()
+}
+
+fn log_likelihood(mixture: &Mixture, x: f32) -> f32 {
+    log_sum_exp(mixture.map(|g| g.log_density(x)))
+}
+
+/// Adapt each component in proportion to how well it explains `x`.
+fn adapt_mixture(mixture: &mut Mixture, x: f32, rate: f32) {
+    let densities = mixture.map(|g| g.log_density(x));
+    let total = log_sum_exp(densities);
+    for (gaussian, density) in mixture.iter_mut().zip(densities) {
+        gaussian.adapt(x, rate * (density - total).exp());
+    }
+}
+
+struct BandModel {
+    noise: Mixture,
+    speech: Mixture,
+    floor: NoiseFloor,
+}
+
+impl BandModel {
+    fn new() -> Self {
+        Self {
+            noise: mixture(NOISE_START),
+            speech: mixture(SPEECH_START),
+            floor: NoiseFloor::new(),
+        }
+    }
+
+    /// Log-likelihood ratio of speech over noise for a band level.
+    fn llr(&self, x: f32) -> f32 {
+        (log_likelihood(&self.speech, x) - log_likelihood(&self.noise, x))
+            .clamp(-MAX_BAND_LLR, MAX_BAND_LLR)
+    }
+
+    fn adapt(&mut self, x: f32, speech: bool) {
+        if speech {
+            adapt_mixture(&mut self.speech, x, SPEECH_RATE);
+        } else {
+            adapt_mixture(&mut self.noise, x, NOISE_RATE);
+        }
+
+        let floor = self.floor.push(x);
+        for (gaussian, offset) in self.noise.iter_mut().zip(FLOOR_OFFSETS_DB) {
+            let target = floor + offset;
+            gaussian.mean = gaussian.mean.max(target);
+            gaussian.mean += FLOOR_PULL * (target - gaussian.mean);
+        }
+        let loudest_noise = self.noise[0].mean.max(self.noise[1].mean);
+        for gaussian in &mut self.speech {
+            gaussian.mean = gaussian.mean.max(loudest_noise + MIN_SEPARATION_DB);
+        }
+    }
+}
+
+pub struct GmmVad {
+    threshold: f32,
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    window_power: f32,
+    spectrum: Vec<Complex32>,
+    /// FFT bins of each band, end exclusive.
+    band_bins: [(usize, usize); 6],
+    bands: Vec<BandModel>,
+}
+
+impl GmmVad {
+    /// `threshold` is compared against the probability of speech, as for
+    /// Silero.
+    pub fn new(threshold: f32) -> Self {
+        let window: Vec<f32> = (0..FRAME_SAMPLES)
+            .map(|i| (PI * (i as f32 + 0.5) / FRAME_SAMPLES as f32).sin().powi(2))
+            .collect();
+        let window_power = window.iter().map(|w| w * w).sum();
+        let bin = |hz: f32| (hz * FFT_SIZE as f32 / WHISPER_SAMPLE_RATE as f32).ceil() as usize;
+
+        Self {
+            threshold: threshold.clamp(0.0, 1.0),
+            fft: FftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE),
+            window,
+            window_power,
+            spectrum: vec![Complex32::new(0.0, 0.0); FFT_SIZE],
+            band_bins: BANDS.map(|(low, high)| (bin(low), bin(high))),
+            bands: BANDS.iter().map(|_| BandModel::new()).collect(),
+        }
+    }
+
+    /// Mean power per bin of each band, in dB, scaled so that white noise
+    /// reads as its variance.
+    fn band_levels(&mut self, frame: &[f32]) -> [f32; 6] {
+        for (bin, (x, w)) in self.spectrum.iter_mut().zip(frame.iter().zip(&self.window)) {
+            *bin = Complex32::new(x * w, 0.0);
+        }
+        for bin in &mut self.spectrum[FRAME_SAMPLES..] {
+            *bin = Complex32::new(0.0, 0.0);
+        }
+        self.fft.process(&mut self.spectrum);
+
+        self.band_bins.map(|(start, end)| {
+            let power = self.spectrum[start..end]
+                .iter()
+                .map(|c| c.norm_sqr())
+                .sum::<f32>()
+                / (end - start) as f32
+                / self.window_power;
+            (10.0 * (power + 1e-20).log10()).max(MIN_FEATURE_DB)
+        })
+    }
+}
+
+impl VoiceActivityDetector for GmmVad {
+    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+        if frame.len() != FRAME_SAMPLES {
+            anyhow::bail!("expected {FRAME_SAMPLES} samples, got {}", frame.len());
+        }
+        if energy_db(frame) < MIN_ENERGY_DB {
+            return Ok(VadFrame::Noise);
+        }
+
+        let levels = self.band_levels(frame);
+        let score = self
+            .bands
+            .iter()
+            .zip(levels)
+            .zip(BAND_WEIGHTS)
+            .map(|((band, x), weight)| weight * band.llr(x))
+            .sum::<f32>()
+            / BAND_WEIGHTS.iter().sum::<f32>();
+        let probability = 1.0 / (1.0 + (-score).exp());
+        let speech = probability > self.threshold;
+
+        for (band, x) in self.bands.iter_mut().zip(levels) {
+            band.adapt(x, speech);
+        }
+
+        if speech {
+            Ok(VadFrame::Speech(frame))
+        } else {
+            Ok(VadFrame::Noise)
+        }
+    }
+
+    fn configure(&mut self, params: &VadParams) {
+        self.threshold = params.threshold.clamp(0.0, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::signals::{decisions, mix, noise, speech_share, voiced};
+
+    #[test]
+    fn voice_stands_out_from_quiet_noise() {
+        let mut samples = noise(0.01, 1.0, 1);
+        samples.extend(voiced(150.0, 0.2, 1.0));
+        samples.extend(noise(0.01, 1.0, 2));
+
+        let speech = decisions(&mut GmmVad::new(0.3), &samples);
+        assert!(speech_share(&speech, 0.3, 1.0) < 0.1);
+        assert!(speech_share(&speech, 1.05, 2.0) > 0.9);
+        assert!(speech_share(&speech, 2.3, 3.0) < 0.1);
+    }
+
+    #[test]
+    fn learns_loud_steady_noise() {
+        let speech = decisions(&mut GmmVad::new(0.3), &noise(0.1, 4.0, 1));
+        assert!(speech_share(&speech, 1.0, 4.0) < 0.1);
+    }
+
+    #[test]
+    fn hears_voice_over_learned_noise() {
+        let samples = mix(&noise(0.05, 3.0, 1), &voiced(150.0, 0.3, 1.0), 2.0);
+        let speech = decisions(&mut GmmVad::new(0.3), &samples);
+        assert!(speech_share(&speech, 1.0, 2.0) < 0.1);
+        assert!(speech_share(&speech, 2.05, 3.0) > 0.9);
+    }
+}
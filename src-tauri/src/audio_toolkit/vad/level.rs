@@ -0,0 +1,42 @@
+//! Loudness measurements shared by the detectors that work on the signal
+//! itself rather than a model.
+
+use std::collections::VecDeque;
+
+/// Frames quieter than this are digital silence or a muted input; they are
+/// never speech and are kept out of the noise estimates.
+pub(super) const MIN_ENERGY_DB: f32 = -70.0;
+
+/// How far back the noise floor looks: long enough to reach past a sentence,
+/// short enough to follow a fan switching on.
+const FLOOR_WINDOW_FRAMES: usize = 100;
+
+/// Mean power of a frame in dB relative to full scale.
+pub(super) fn energy_db(frame: &[f32]) -> f32 {
+    let power = frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32;
+    10.0 * (power + 1e-12).log10()
+}
+
+/// Minimum of a level over the last few seconds. Speech has gaps between
+/// words, so the quietest frame of a window is a fair estimate of the
+/// background even while someone talks.
+pub(super) struct NoiseFloor {
+    history: VecDeque<f32>,
+}
+
+impl NoiseFloor {
+    pub(super) fn new() -> Self {
+        Self {
+            history: VecDeque::with_capacity(FLOOR_WINDOW_FRAMES + 1),
+        }
+    }
+
+    /// Add one frame's level and return the floor including it.
+    pub(super) fn push(&mut self, level_db: f32) -> f32 {
+        self.history.push_back(level_db);
+        if self.history.len() > FLOOR_WINDOW_FRAMES {
+            self.history.pop_front();
+        }
+        self.history.iter().copied().fold(f32::INFINITY, f32::min)
+    }
+}
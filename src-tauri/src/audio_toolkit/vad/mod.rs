@@ -14,6 +14,30 @@ impl<'a> VadFrame<'a> {
     }
 }
 
+/// Tuning shared by the detectors; each one uses the parts that apply to it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VadParams {
+    /// Speech probability above which a frame counts as voice.
+    pub threshold: f32,
+    /// Frames kept from before speech starts and put in front of it.
+    pub prefill_frames: usize,
+    /// Frames still treated as speech after the voice stops.
+    pub hangover_frames: usize,
+    /// Consecutive voiced frames needed before speech starts.
+    pub onset_frames: usize,
+}
+
+impl Default for VadParams {
+    fn default() -> Self {
+        Self {
+            threshold: 0.3,
+            prefill_frames: 15,
+            hangover_frames: 15,
+            onset_frames: 2,
+        }
+    }
+}
+
 pub trait VoiceActivityDetector: Send + Sync {
     /// Primary streaming API: feed one 30-ms frame, get keep/drop decision.
     fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>>;
@@ -23,10 +47,28 @@ pub trait VoiceActivityDetector: Send + Sync {
     }
 
     fn reset(&mut self) {}
+
+    /// Apply new tuning without reloading the detector.
+    fn configure(&mut self, _params: &VadParams) {}
+
+    /// Frames classified as voice since the last reset, before any smoothing,
+    /// for detectors that keep count.
+    fn voiced_frames(&self) -> Option<usize> {
+        None
+    }
 }
 
+mod energy;
+mod gmm;
+mod level;
+mod regions;
 mod silero;
 mod smoothed;
+mod utterance;
 
+pub use energy::EnergyVad;
+pub use gmm::GmmVad;
+pub use regions::{speech_regions, SpeechRegion};
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
+pub use utterance::{UtteranceConfig, UtteranceEvent, UtteranceSegmenter};
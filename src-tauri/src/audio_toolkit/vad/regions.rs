@@ -0,0 +1,114 @@
+//! Offline speech detection over a whole recording, for tuning the VAD.
+
+use anyhow::Result;
+
+use super::{VadFrame, VoiceActivityDetector};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+const FRAME_SAMPLES: usize = (WHISPER_SAMPLE_RATE / 1000 * 30) as usize;
+
+/// A stretch of audio the VAD kept, in milliseconds from the start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpeechRegion {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl SpeechRegion {
+    pub fn duration_ms(&self) -> u64 {
+        self.end_ms - self.start_ms
+    }
+}
+
+/// Run `vad` over 16kHz `samples` in 30ms frames, the way the recorder does,
+/// and return the regions it keeps. Prefill counts towards the region it
+/// leads into; a trailing partial frame is ignored.
+pub fn speech_regions(
+    vad: &mut dyn VoiceActivityDetector,
+    samples: &[f32],
+) -> Result<Vec<SpeechRegion>> {
+    let to_ms = |sample: usize| sample as u64 * 1000 / WHISPER_SAMPLE_RATE as u64;
+
+    vad.reset();
+    let mut regions: Vec<SpeechRegion> = Vec::new();
+    for (index, frame) in samples.chunks_exact(FRAME_SAMPLES).enumerate() {
+        let end = (index + 1) * FRAME_SAMPLES;
+        let VadFrame::Speech(kept) = vad.push_frame(frame)? else {
+            continue;
+        };
+        let region = SpeechRegion {
+            start_ms: to_ms(end.saturating_sub(kept.len())),
+            end_ms: to_ms(end),
+        };
+        match regions.last_mut() {
+            Some(last) if region.start_ms <= last.end_ms => last.end_ms = region.end_ms,
+            _ => regions.push(region),
+        }
+    }
+    Ok(regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::vad::SmoothedVad;
+
+    /// Calls any frame with a loud sample voice.
+    struct LoudVad;
+
+    impl VoiceActivityDetector for LoudVad {
+        fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+            if frame.iter().any(|s| s.abs() > 0.1) {
+                Ok(VadFrame::Speech(frame))
+            } else {
+                Ok(VadFrame::Noise)
+            }
+        }
+    }
+
+    /// `pattern` has one entry per frame: true for speech.
+    fn signal(pattern: &[bool]) -> Vec<f32> {
+        pattern
+            .iter()
+            .flat_map(|&speech| [if speech { 0.5 } else { 0.0 }; FRAME_SAMPLES])
+            .collect()
+    }
+
+    #[test]
+    fn adjacent_speech_frames_form_one_region() {
+        let samples = signal(&[false, true, true, false, false, true]);
+        assert_eq!(
+            speech_regions(&mut LoudVad, &samples).unwrap(),
+            vec![
+                SpeechRegion {
+                    start_ms: 30,
+                    end_ms: 90
+                },
+                SpeechRegion {
+                    start_ms: 150,
+                    end_ms: 180
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn smoothing_extends_regions() {
+        // Two frames of prefill, one of hangover and an onset of two frames
+        let mut vad = SmoothedVad::new(Box::new(LoudVad), 2, 1, 2);
+        let mut pattern = vec![false; 5];
+        pattern.extend([true; 3]);
+        pattern.extend([false; 4]);
+        let regions = speech_regions(&mut vad, &signal(&pattern)).unwrap();
+        // Speech starts on the second voiced frame, which ends at 210ms, with
+        // the two frames before it, and runs one frame past the last voiced one
+        assert_eq!(
+            regions,
+            vec![SpeechRegion {
+                start_ms: 120,
+                end_ms: 270
+            }]
+        );
+        assert_eq!(regions[0].duration_ms(), 150);
+    }
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 
 use vad_rs::Vad;
 
-use super::{VadFrame, VoiceActivityDetector};
+use super::{VadFrame, VadParams, VoiceActivityDetector};
 use crate::audio_toolkit::constants;
 
 const SILERO_FRAME_MS: u32 = 30;
@@ -51,4 +51,8 @@ impl VoiceActivityDetector for SileroVad {
             Ok(VadFrame::Noise)
         }
     }
+
+    fn configure(&mut self, params: &VadParams) {
+        self.threshold = params.threshold.clamp(0.0, 1.0);
+    }
 }
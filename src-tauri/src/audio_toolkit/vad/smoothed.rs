@@ -1,4 +1,4 @@
-use super::{VadFrame, VoiceActivityDetector};
+use super::{VadFrame, VadParams, VoiceActivityDetector};
 use anyhow::Result;
 use std::collections::VecDeque;
 
@@ -12,6 +12,7 @@ pub struct SmoothedVad {
     hangover_counter: usize,
     onset_counter: usize,
     in_speech: bool,
+    voiced_frames: usize,
 
     temp_out: Vec<f32>,
 }
@@ -32,6 +33,7 @@ impl SmoothedVad {
             hangover_counter: 0,
             onset_counter: 0,
             in_speech: false,
+            voiced_frames: 0,
             temp_out: Vec::new(),
         }
     }
@@ -47,6 +49,9 @@ impl VoiceActivityDetector for SmoothedVad {
 
         // 2. Delegate to the wrapped boolean VAD
         let is_voice = self.inner_vad.is_voice(frame)?;
+        if is_voice {
+            self.voiced_frames += 1;
+        }
         // println!("Is Voice: {}", is_voice);
 
         match (self.in_speech, is_voice) {
@@ -101,6 +106,19 @@ impl VoiceActivityDetector for SmoothedVad {
         self.hangover_counter = 0;
         self.onset_counter = 0;
         self.in_speech = false;
+        self.voiced_frames = 0;
         self.temp_out.clear();
     }
+
+    fn configure(&mut self, params: &VadParams) {
+        self.prefill_frames = params.prefill_frames;
+        self.hangover_frames = params.hangover_frames;
+        self.onset_frames = params.onset_frames;
+        self.hangover_counter = self.hangover_counter.min(self.hangover_frames);
+        self.inner_vad.configure(params);
+    }
+
+    fn voiced_frames(&self) -> Option<usize> {
+        Some(self.voiced_frames)
+    }
 }
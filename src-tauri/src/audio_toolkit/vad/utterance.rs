@@ -0,0 +1,199 @@
+//! Splits an open microphone stream into utterances for hands-free dictation.
+//!
+//! An utterance starts when the smoothed VAD reports speech, so it includes
+//! the VAD's prefill, and ends once the raw VAD has heard nothing but silence
+//! for the configured time, or when it reaches the maximum length.
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+#[derive(Clone, Copy, Debug)]
+pub struct UtteranceConfig {
+    /// Silence after the last voiced frame that ends an utterance.
+    pub trailing_silence_ms: u32,
+    /// Utterances are cut at this length even if the speaker carries on.
+    pub max_utterance_ms: u32,
+    /// Utterances with less voiced audio than this are dropped, so a cough or
+    /// a door closing isn't transcribed.
+    pub min_speech_ms: u32,
+}
+
+#[derive(Debug)]
+pub enum UtteranceEvent {
+    /// Speech started and a new utterance is being collected.
+    Started,
+    /// An utterance ended with enough speech in it.
+    Finished { samples: Vec<f32>, speech_ms: u32 },
+    /// An utterance ended with too little speech to be worth transcribing.
+    Discarded,
+}
+
+pub struct UtteranceSegmenter {
+    config: UtteranceConfig,
+    frame_ms: u32,
+    samples: Vec<f32>,
+    active: bool,
+    voiced_ms: u32,
+    silence_ms: u32,
+}
+
+impl UtteranceSegmenter {
+    pub fn new(config: UtteranceConfig, frame_ms: u32) -> Self {
+        Self {
+            config,
+            frame_ms,
+            samples: Vec::new(),
+            active: false,
+            voiced_ms: 0,
+            silence_ms: 0,
+        }
+    }
+
+    /// Feed one frame.
+    ///
+    /// `speech` is what the smoothed VAD kept of the frame, which can include
+    /// the frames before the onset, and `voiced` is the raw VAD decision.
+    /// Once an utterance ends the VAD should be reset, so its hangover doesn't
+    /// start the next one.
+    pub fn push(
+        &mut self,
+        frame: &[f32],
+        speech: Option<&[f32]>,
+        voiced: bool,
+    ) -> Option<UtteranceEvent> {
+        let started = !self.active && speech.is_some();
+        if started {
+            self.active = true;
+            self.samples.clear();
+            self.voiced_ms = 0;
+            self.silence_ms = 0;
+        } else if !self.active {
+            return None;
+        }
+
+        self.samples.extend_from_slice(speech.unwrap_or(frame));
+        if voiced {
+            self.voiced_ms += self.frame_ms;
+            self.silence_ms = 0;
+        } else {
+            self.silence_ms += self.frame_ms;
+        }
+
+        let length_ms = self.samples.len() as u64 * 1000 / WHISPER_SAMPLE_RATE as u64;
+        if self.silence_ms >= self.config.trailing_silence_ms
+            || length_ms >= self.config.max_utterance_ms as u64
+        {
+            Some(self.finish())
+        } else if started {
+            Some(UtteranceEvent::Started)
+        } else {
+            None
+        }
+    }
+
+    /// End the current utterance, if there is one, e.g. when listening stops.
+    pub fn flush(&mut self) -> Option<UtteranceEvent> {
+        self.active.then(|| self.finish())
+    }
+
+    fn finish(&mut self) -> UtteranceEvent {
+        self.active = false;
+        let samples = std::mem::take(&mut self.samples);
+        if self.voiced_ms < self.config.min_speech_ms {
+            return UtteranceEvent::Discarded;
+        }
+        UtteranceEvent::Finished {
+            samples,
+            speech_ms: self.voiced_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_MS: u32 = 30;
+    const FRAME: [f32; 480] = [0.0; 480];
+
+    fn segmenter() -> UtteranceSegmenter {
+        UtteranceSegmenter::new(
+            UtteranceConfig {
+                trailing_silence_ms: 300,
+                max_utterance_ms: 3_000,
+                min_speech_ms: 150,
+            },
+            FRAME_MS,
+        )
+    }
+
+    /// Feed `frames` frames that the VAD calls speech or silence.
+    fn feed(
+        segmenter: &mut UtteranceSegmenter,
+        frames: usize,
+        voiced: bool,
+    ) -> Vec<UtteranceEvent> {
+        (0..frames)
+            .filter_map(|_| segmenter.push(&FRAME, voiced.then_some(&FRAME[..]), voiced))
+            .collect()
+    }
+
+    #[test]
+    fn utterance_ends_after_trailing_silence() {
+        let mut segmenter = segmenter();
+        assert!(feed(&mut segmenter, 5, false).is_empty());
+
+        let events = feed(&mut segmenter, 20, true);
+        assert!(matches!(events[..], [UtteranceEvent::Started]));
+
+        // 300ms of silence is ten frames
+        assert!(feed(&mut segmenter, 9, false).is_empty());
+        match &feed(&mut segmenter, 1, false)[..] {
+            [UtteranceEvent::Finished { samples, speech_ms }] => {
+                assert_eq!(*speech_ms, 600);
+                assert_eq!(samples.len(), 30 * FRAME.len());
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+
+    #[test]
+    fn short_noises_are_discarded() {
+        let mut segmenter = segmenter();
+        let mut events = feed(&mut segmenter, 3, true);
+        events.extend(feed(&mut segmenter, 10, false));
+        assert!(matches!(
+            events[..],
+            [UtteranceEvent::Started, UtteranceEvent::Discarded]
+        ));
+    }
+
+    #[test]
+    fn long_utterances_are_cut() {
+        let mut segmenter = segmenter();
+        let events = feed(&mut segmenter, 120, true);
+        // Cut at 3s, then a new utterance starts with the next voiced frame
+        assert!(matches!(
+            events[..],
+            [
+                UtteranceEvent::Started,
+                UtteranceEvent::Finished {
+                    speech_ms: 3_000,
+                    ..
+                },
+                UtteranceEvent::Started
+            ]
+        ));
+    }
+
+    #[test]
+    fn flush_ends_the_current_utterance() {
+        let mut segmenter = segmenter();
+        assert!(segmenter.flush().is_none());
+        feed(&mut segmenter, 10, true);
+        assert!(matches!(
+            segmenter.flush(),
+            Some(UtteranceEvent::Finished { speech_ms: 300, .. })
+        ));
+        assert!(segmenter.flush().is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::audio_feedback;
 use crate::audio_toolkit::audio::{list_input_devices, list_output_devices};
 use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
+use crate::managers::wake_word::WakeWordManager;
 use crate::settings;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -51,7 +52,13 @@ pub fn update_microphone_mode(app: AppHandle, always_on: bool) -> Result<(), Str
     };
 
     rm.update_mode(new_mode)
-        .map_err(|e| format!("Failed to update microphone mode: {}", e))
+        .map_err(|e| format!("Failed to update microphone mode: {}", e))?;
+
+    // Wake words only run on the always-on stream
+    if let Err(e) = app.state::<Arc<WakeWordManager>>().reload() {
+        log::warn!("Failed to load wake words: {}", e);
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -116,6 +123,25 @@ pub fn set_clamshell_microphone(app: AppHandle, device_name: String) -> Result<(
     Ok(())
 }
 
+#[tauri::command]
+pub fn set_microphone_priority(app: AppHandle, patterns: Vec<String>) -> Result<(), String> {
+    let patterns: Vec<String> = patterns
+        .iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    settings::update_settings(&app, |s| {
+        s.microphone_priority = patterns.clone();
+    });
+
+    // Side effect outside lock: move to the best microphone now available
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to update microphone priority: {}", e))?;
+
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_selected_microphone(app: AppHandle) -> Result<String, String> {
     let s = settings::get_settings(&app);
@@ -1,16 +1,32 @@
 use crate::audio_toolkit::audio::decode_audio_file;
+use crate::audio_toolkit::vad::SpeechRegion;
+use crate::managers::audio::detect_speech_regions;
 use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{CancelToken, JobKind, Transcript, TranscriptionManager};
 use log::{error, info};
+use once_cell::sync::Lazy;
 use rusqlite::params;
 use serde::Serialize;
 use serde_json::json;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager, State};
 
+/// Cancel tokens of the files being transcribed, by path.
+static FILE_JOBS: Lazy<Mutex<HashMap<String, CancelToken>>> = Lazy::new(Default::default);
+
+/// Files are transcribed in pieces of at most this length, so a dictation
+/// started meanwhile never waits for more than one piece.
+const FILE_CHUNK_SAMPLES: usize = 30 * 16_000;
+
+/// How far back from a chunk boundary to look for the quietest frame, when
+/// the VAD found no pause to cut at.
+const PAUSE_SEARCH_SAMPLES: usize = 5 * 16_000;
+
+/// 20ms frames when looking for the quietest point.
+const PAUSE_FRAME_SAMPLES: usize = 320;
+
 #[derive(Clone, Serialize)]
 pub struct FileTranscriptionProgress {
     pub status: String,
@@ -55,9 +71,6 @@ pub async fn transcribe_audio_file(
         .and_then(|n| n.to_str())
         .map(ToString::to_string);
 
-    // Mark file transcription as active
-    crate::set_file_transcription_active(true);
-
     let is_video = is_video_file(&path);
 
     // Emit progress: Starting - different message for video vs audio
@@ -109,79 +122,38 @@ pub async fn transcribe_audio_file(
         format!("{}s", duration_secs)
     };
 
-    // Emit progress: Decoding complete, starting transcription
-    emit_progress(
-        &app,
-        &FileTranscriptionProgress {
-            status: "transcribing".to_string(),
-            progress: -1.0, // Indeterminate progress
-            message: format!("Transcribing {} of audio...", duration_str),
-            file_name: file_name.clone(),
-        },
-    );
-
     // Ensure model is loaded
     transcription_manager.initiate_model_load();
 
-    // Wait a moment for model to start loading if it wasn't loaded
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // Start elapsed time tracking thread
-    let progress_complete = Arc::new(AtomicBool::new(false));
-    let progress_complete_clone = progress_complete.clone();
-    let app_clone = app.clone();
-    let file_name_clone = file_name.clone();
-    let duration_str_clone = duration_str.clone();
-
-    // Spawn elapsed time display thread
-    let progress_handle = std::thread::spawn(move || {
-        let start_time = Instant::now();
-        let update_interval = Duration::from_secs(5);
-
-        // Wait a bit before first update
-        std::thread::sleep(update_interval);
-
-        while !progress_complete_clone.load(Ordering::SeqCst) {
-            let elapsed = start_time.elapsed().as_secs();
-            let elapsed_str = if elapsed >= 60 {
-                format!("{}m {}s elapsed", elapsed / 60, elapsed % 60)
-            } else {
-                format!("{}s elapsed", elapsed)
-            };
-
-            emit_progress(
-                &app_clone,
-                &FileTranscriptionProgress {
-                    status: "transcribing".to_string(),
-                    progress: -1.0, // Indeterminate progress
-                    message: format!(
-                        "Transcribing {} of audio... ({})",
-                        duration_str_clone, elapsed_str
-                    ),
-                    file_name: file_name_clone.clone(),
-                },
-            );
-
-            std::thread::sleep(update_interval);
-        }
-    });
-
-    // Transcribe the audio
-    let transcription_result = transcription_manager.transcribe(audio_samples.clone());
-
-    // Signal progress thread to stop
-    progress_complete.store(true, Ordering::SeqCst);
-    let _ = progress_handle.join();
-
-    let transcription_text = match transcription_result {
-        Ok(text) => text,
-        Err(e) => {
-            let err = format!("Transcription failed: {}", e);
+    // Transcribe chunk by chunk; each chunk is a background job, so dictation
+    // can run in between, and a cancelled chunk stops the rest of the file
+    let speech = detect_speech_regions(&app, &audio_samples);
+    let chunks = chunk_boundaries(&audio_samples, speech.as_deref());
+    let cancel = CancelToken::new();
+    FILE_JOBS
+        .lock()
+        .unwrap()
+        .insert(file_path.clone(), cancel.clone());
+    let transcribed = transcribe_chunks(
+        &app,
+        &transcription_manager,
+        &audio_samples,
+        &chunks,
+        &cancel,
+        &duration_str,
+        &file_name,
+    );
+    FILE_JOBS.lock().unwrap().remove(&file_path);
+    let transcript = match transcribed {
+        Ok(transcript) => transcript,
+        Err(err) => {
             emit_error(&app, &err, file_name.clone());
             return Err(err);
         }
     };
 
+    let transcription_text = transcript.text.clone();
+
     if transcription_text.trim().is_empty() {
         let err = "No speech detected in the audio".to_string();
         emit_error(&app, &err, file_name.clone());
@@ -212,7 +184,7 @@ pub async fn transcribe_audio_file(
     if let Err(e) = history_manager
         .save_transcription(
             audio_samples,
-            transcription_text.clone(),
+            transcript.clone(),
             None, // post_processed_text
             None, // post_process_prompt
         )
@@ -249,14 +221,12 @@ pub async fn transcribe_audio_file(
         },
     );
 
-    // Clear file transcription active flag
-    crate::set_file_transcription_active(false);
-
     // Emit completion event with details for the dialog
     if let Err(e) = app.emit(
         "transcription-complete",
         json!({
             "text": transcription_text,
+            "segments": transcript.segments,
             "fileName": file_name.clone().unwrap_or_else(|| "Unknown file".to_string())
         }),
     ) {
@@ -277,6 +247,56 @@ pub async fn transcribe_audio_file(
     Ok(transcription_text)
 }
 
+/// Stop transcribing `file_path`; returns false if it isn't being transcribed.
+#[tauri::command]
+pub fn cancel_file_transcription(
+    file_path: String,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> bool {
+    let Some(cancel) = FILE_JOBS.lock().unwrap().get(&file_path).cloned() else {
+        return false;
+    };
+    info!("Cancelling transcription of {}", file_path);
+    transcription_manager.cancel_token(&cancel);
+    true
+}
+
+/// Run each chunk as a file job and join the results.
+fn transcribe_chunks(
+    app: &AppHandle,
+    transcription_manager: &TranscriptionManager,
+    audio_samples: &[f32],
+    chunks: &[(usize, usize)],
+    cancel: &CancelToken,
+    duration_str: &str,
+    file_name: &Option<String>,
+) -> Result<Transcript, String> {
+    let mut transcript = Transcript::default();
+    for (index, (start, end)) in chunks.iter().enumerate() {
+        emit_progress(
+            app,
+            &FileTranscriptionProgress {
+                status: "transcribing".to_string(),
+                progress: 0.1 + 0.8 * index as f64 / chunks.len() as f64,
+                message: format!("Transcribing {} of audio...", duration_str),
+                file_name: file_name.clone(),
+            },
+        );
+
+        let chunk_transcript = transcription_manager
+            .transcribe_cancellable(audio_samples[*start..*end].to_vec(), JobKind::File, cancel)
+            .map_err(|e| {
+                if cancel.is_cancelled() {
+                    "Transcription cancelled".to_string()
+                } else {
+                    format!("Transcription failed: {}", e)
+                }
+            })?;
+        transcript.append(chunk_transcript.offset_by((*start / 16) as i64));
+    }
+    Ok(transcript)
+}
+
 fn emit_progress(app: &AppHandle, progress: &FileTranscriptionProgress) {
     if let Err(e) = app.emit("file-transcription-progress", progress) {
         error!("Failed to emit progress event: {}", e);
@@ -284,9 +304,6 @@ fn emit_progress(app: &AppHandle, progress: &FileTranscriptionProgress) {
 }
 
 fn emit_error(app: &AppHandle, error_message: &str, file_name: Option<String>) {
-    // Clear file transcription active flag
-    crate::set_file_transcription_active(false);
-
     // Emit as file-transcription-error for the listener
     if let Err(e) = app.emit("file-transcription-error", error_message.to_string()) {
         error!("Failed to emit error event: {}", e);
@@ -303,3 +320,94 @@ fn emit_error(app: &AppHandle, error_message: &str, file_name: Option<String>) {
         },
     );
 }
+
+/// Split audio into `(start, end)` sample ranges of at most `FILE_CHUNK_SAMPLES`.
+///
+/// Each cut goes in the middle of the latest pause between the `speech`
+/// regions in the second half of the chunk, so sentences stay whole and
+/// each chunk starts on a fresh one. Without such a pause, or without a
+/// VAD, it goes at the quietest frame shortly before the limit.
+fn chunk_boundaries(samples: &[f32], speech: Option<&[SpeechRegion]>) -> Vec<(usize, usize)> {
+    let pauses: Vec<usize> = speech
+        .unwrap_or_default()
+        .windows(2)
+        .map(|pair| (pair[0].end_ms + pair[1].start_ms) as usize / 2 * 16)
+        .collect();
+    let frame_energy = |start: usize| -> f32 {
+        samples[start..start + PAUSE_FRAME_SAMPLES]
+            .iter()
+            .map(|s| s * s)
+            .sum()
+    };
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < samples.len() {
+        let limit = start + FILE_CHUNK_SAMPLES;
+        let end = if limit >= samples.len() {
+            samples.len()
+        } else if let Some(pause) = pauses
+            .iter()
+            .rev()
+            .copied()
+            .find(|pause| *pause <= limit && *pause > start + FILE_CHUNK_SAMPLES / 2)
+        {
+            pause
+        } else {
+            let search_start = limit.saturating_sub(PAUSE_SEARCH_SAMPLES).max(start);
+            (search_start..limit - PAUSE_FRAME_SAMPLES)
+                .step_by(PAUSE_FRAME_SAMPLES)
+                .min_by(|a, b| frame_energy(*a).total_cmp(&frame_energy(*b)))
+                .map(|frame| frame + PAUSE_FRAME_SAMPLES / 2)
+                .unwrap_or(limit)
+        };
+        chunks.push((start, end));
+        start = end;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_audio_is_one_chunk() {
+        assert_eq!(chunk_boundaries(&[0.5; 16_000], None), vec![(0, 16_000)]);
+        assert!(chunk_boundaries(&[], None).is_empty());
+    }
+
+    #[test]
+    fn long_audio_is_cut_at_a_pause() {
+        let mut samples = vec![0.5; 70 * 16_000];
+        // Half a second of silence at 27s
+        samples[27 * 16_000..27 * 16_000 + 8_000].fill(0.0);
+
+        let chunks = chunk_boundaries(&samples, None);
+        let first_end = chunks[0].1;
+        assert!(first_end > 27 * 16_000 && first_end < 27 * 16_000 + 8_000);
+        assert!(chunks
+            .iter()
+            .all(|(start, end)| end - start <= FILE_CHUNK_SAMPLES));
+        assert_eq!(chunks.last().unwrap().1, samples.len());
+        assert!(chunks.windows(2).all(|w| w[0].1 == w[1].0));
+    }
+
+    #[test]
+    fn long_audio_is_cut_between_sentences() {
+        let samples = vec![0.5; 70 * 16_000];
+        let region = |start_ms, end_ms| SpeechRegion { start_ms, end_ms };
+        // Pauses at 10s, 20s and 40s; only the second and the last fit
+        let speech = [
+            region(0, 9_800),
+            region(10_200, 19_800),
+            region(20_200, 39_800),
+            region(40_200, 70_000),
+        ];
+
+        let chunks = chunk_boundaries(&samples, Some(&speech));
+        assert_eq!(chunks[0], (0, 20 * 16_000));
+        assert_eq!(chunks[1], (20 * 16_000, 40 * 16_000));
+        assert_eq!(chunks.last().unwrap().1, samples.len());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::managers::history::{HistoryEntry, HistoryManager};
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{JobKind, TranscriptionManager};
 use crate::managers::tts::TtsManager;
 use crate::settings::get_settings;
 use std::sync::Arc;
@@ -76,16 +76,18 @@ pub async fn retranscribe_history_entry(
 
     // Transcribe the audio
     let new_transcription = transcription_manager
-        .transcribe(audio_samples)
+        .transcribe(audio_samples, JobKind::Retranscription)
         .map_err(|e| format!("Transcription failed: {}", e))?;
 
+    let new_text = new_transcription.text.clone();
+
     // Update the history entry with the new transcription
     history_manager
-        .retranscribe_entry(id, new_transcription.clone())
+        .retranscribe_entry(id, new_transcription)
         .await
         .map_err(|e| format!("Failed to update history entry: {}", e))?;
 
-    Ok(new_transcription)
+    Ok(new_text)
 }
 
 #[tauri::command]
@@ -160,7 +162,10 @@ pub async fn reprocess_history_entry(
     let mut post_process_prompt: Option<String> = None;
 
     // Try post-processing
-    match crate::actions::maybe_post_process_transcription(&app, &settings, transcription).await {
+    let language = entry.language.as_deref();
+    match crate::actions::maybe_post_process_transcription(&app, &settings, transcription, language)
+        .await
+    {
         crate::tools::PostProcessOutcome::Text(processed_text) => {
             final_text = processed_text.clone();
             post_processed_text = Some(processed_text);
@@ -192,9 +197,10 @@ pub async fn reprocess_history_entry(
     if settings.tts_enabled && post_processed_text.is_some() {
         let tts_manager_clone = Arc::clone(&tts_manager);
         let text_to_speak = final_text.clone();
+        let tts_language = crate::actions::output_language(&settings, language);
         info!("Triggering TTS for reprocessed text: {}", text_to_speak);
         std::thread::spawn(move || {
-            if let Err(e) = tts_manager_clone.speak(&text_to_speak) {
+            if let Err(e) = tts_manager_clone.speak(&text_to_speak, tts_language.as_deref()) {
                 error!("TTS failed: {}", e);
             }
         });
@@ -1,6 +1,11 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::benchmark::{BenchmarkManager, ModelBenchmark};
+use crate::managers::catalog;
+use crate::managers::model::{
+    EngineType, ModelInfo, ModelManager, ModelVerification, StorageReport,
+};
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -119,8 +124,197 @@ pub async fn cancel_download(
         .map_err(|e| e.to_string())
 }
 
+/// Import a local model file or directory into the user catalog.
+///
+/// The engine is detected when `engine_type` is not given; with `link` the
+/// model is symlinked into the models directory instead of copied.
+#[tauri::command]
+pub async fn import_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    source_path: String,
+    name: String,
+    engine_type: Option<EngineType>,
+    link: bool,
+) -> Result<ModelInfo, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        manager.import_model(&PathBuf::from(source_path), &name, engine_type, link)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Re-hash downloaded models against their expected checksums.
+#[tauri::command]
+pub async fn verify_installed_models(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelVerification>, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || manager.verify_installed_models())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_recommended_first_model() -> Result<String, String> {
     // Recommend Parakeet V3 model for first-time users - fastest and most accurate
     Ok("parakeet-tdt-0.6b-v3".to_string())
 }
+
+/// Disk usage of each model, plus leftovers of interrupted downloads and imports.
+#[tauri::command]
+pub async fn get_model_storage_report(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<StorageReport, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || manager.storage_report())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Delete orphaned model files, returning the number of bytes freed.
+#[tauri::command]
+pub async fn cleanup_orphaned_model_files(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<u64, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || manager.cleanup_orphans())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Move the models directory to `path`, returning the new location.
+#[tauri::command]
+pub async fn relocate_models_dir(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+) -> Result<String, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        manager
+            .relocate_models_dir(&PathBuf::from(path))
+            .map(|dir| dir.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Load the model catalog from a JSON manifest at a path, `file://` URL or
+/// HTTP(S) URL, or from the built-in manifest when `source` is empty.
+#[tauri::command]
+pub async fn set_model_catalog_source(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    source: Option<String>,
+) -> Result<Vec<ModelInfo>, String> {
+    let source = source
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty());
+
+    // Check the manifest before switching to it
+    if let Some(source) = &source {
+        catalog::fetch_manifest(source)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    settings::update_settings(&app_handle, |s| {
+        s.model_catalog_source = source;
+    });
+    model_manager
+        .refresh_catalog()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(model_manager.get_transcription_models())
+}
+
+/// Set the mirrors model downloads are tried from before the catalog's URLs.
+#[tauri::command]
+pub fn update_model_mirrors(app_handle: AppHandle, mirrors: Vec<String>) -> Result<(), String> {
+    let mirrors: Vec<String> = mirrors
+        .into_iter()
+        .map(|mirror| mirror.trim().to_string())
+        .filter(|mirror| !mirror.is_empty())
+        .collect();
+
+    if let Some(invalid) = mirrors
+        .iter()
+        .find(|mirror| !catalog::is_remote(mirror) && !mirror.starts_with("file://"))
+    {
+        return Err(format!(
+            "Mirrors must be http://, https:// or file:// URLs: {}",
+            invalid
+        ));
+    }
+
+    settings::update_settings(&app_handle, |s| {
+        s.model_mirrors = mirrors;
+    });
+    Ok(())
+}
+
+/// Set how many model downloads run at once.
+#[tauri::command]
+pub fn change_max_concurrent_downloads_setting(
+    app_handle: AppHandle,
+    max_concurrent: usize,
+) -> Result<(), String> {
+    if max_concurrent == 0 {
+        return Err("At least one download must be allowed".to_string());
+    }
+    settings::update_settings(&app_handle, |s| {
+        s.max_concurrent_downloads = max_concurrent;
+    });
+    Ok(())
+}
+
+/// Cap the combined model download speed, in bytes per second; `None` removes the cap.
+#[tauri::command]
+pub fn change_download_bandwidth_limit_setting(
+    app_handle: AppHandle,
+    bytes_per_second: Option<u64>,
+) -> Result<(), String> {
+    settings::update_settings(&app_handle, |s| {
+        s.download_bandwidth_limit = bytes_per_second.filter(|limit| *limit > 0);
+    });
+    Ok(())
+}
+
+/// Benchmark downloaded models against a reference recording and transcript,
+/// or the recording bundled with the app when no `audio_path` is given.
+///
+/// Runs every downloaded transcription model unless `model_ids` is given.
+#[tauri::command]
+pub async fn run_model_benchmark(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+    audio_path: Option<String>,
+    reference_text: Option<String>,
+    language: Option<String>,
+    model_ids: Option<Vec<String>>,
+) -> Result<Vec<ModelBenchmark>, String> {
+    let manager = benchmark_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || match audio_path {
+        Some(audio_path) => manager.run(
+            &PathBuf::from(audio_path),
+            reference_text.as_deref().unwrap_or_default(),
+            language.as_deref(),
+            model_ids,
+        ),
+        None => manager.run_bundled(model_ids),
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_model_benchmarks(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<Vec<ModelBenchmark>, String> {
+    benchmark_manager.get_results().map_err(|e| e.to_string())
+}
@@ -1,5 +1,6 @@
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{BackendCapabilities, JobInfo, TranscriptionManager};
 use crate::settings::{self, ModelUnloadTimeout};
+use std::sync::Arc;
 use tauri::{AppHandle, State};
 
 #[tauri::command]
@@ -22,6 +23,34 @@ pub fn get_model_load_status(
     }))
 }
 
+/// Capabilities of the backend running the loaded model, or `None` if no model is loaded.
+#[tauri::command]
+pub fn get_transcription_capabilities(
+    transcription_manager: State<Arc<TranscriptionManager>>,
+) -> Option<BackendCapabilities> {
+    transcription_manager.get_capabilities()
+}
+
+/// Running and queued transcription jobs, running job first.
+#[tauri::command]
+pub fn get_transcription_queue(
+    transcription_manager: State<Arc<TranscriptionManager>>,
+) -> Vec<JobInfo> {
+    transcription_manager.get_job_queue()
+}
+
+#[tauri::command]
+pub fn cancel_transcription_job(
+    transcription_manager: State<Arc<TranscriptionManager>>,
+    id: u64,
+) -> Result<(), String> {
+    if transcription_manager.cancel_job(id) {
+        Ok(())
+    } else {
+        Err(format!("Transcription job {} not found", id))
+    }
+}
+
 #[tauri::command]
 pub fn unload_model_manually(
     transcription_manager: State<TranscriptionManager>,
@@ -4,5 +4,5 @@ use tauri::State;
 
 #[tauri::command]
 pub fn preview_tts(state: State<'_, Arc<TtsManager>>, text: String) -> Result<(), String> {
-    state.speak(&text).map_err(|e| e.to_string())
+    state.speak(&text, None).map_err(|e| e.to_string())
 }
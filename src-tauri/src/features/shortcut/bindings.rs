@@ -13,7 +13,7 @@ use serde::Serialize;
 use tauri::AppHandle;
 
 use super::init::{register_shortcut, unregister_shortcut, validate_shortcut_string};
-use crate::settings::{self, ShortcutBinding};
+use crate::settings::{self, ShortcutBinding, PROFILE_BINDING_PREFIX};
 
 #[derive(Serialize)]
 pub struct BindingResponse {
@@ -137,6 +137,128 @@ pub async fn reset_binding(app: AppHandle, id: String) -> Result<BindingResponse
     change_binding(app, id, binding.default_binding).await
 }
 
+/// Add a shortcut that transcribes with the given profile.
+#[tauri::command]
+pub async fn add_profile_binding(
+    app: AppHandle,
+    profile_id: String,
+    binding: String,
+) -> Result<BindingResponse, String> {
+    let profile = settings::get_settings(&app)
+        .transcription_profile(&profile_id)
+        .cloned()
+        .ok_or_else(|| format!("Profile with id '{}' not found", profile_id))?;
+
+    if let Err(e) = validate_shortcut_string(&binding) {
+        warn!("add_profile_binding validation error: {}", e);
+        return Err(e);
+    }
+
+    let new_binding = ShortcutBinding {
+        id: format!(
+            "{}{}",
+            PROFILE_BINDING_PREFIX,
+            chrono::Utc::now().timestamp_millis()
+        ),
+        name: profile.name.clone(),
+        description: format!("Transcribes with the '{}' profile.", profile.name),
+        default_binding: binding.clone(),
+        current_binding: binding,
+        profile_id: Some(profile.id),
+    };
+
+    let nb = new_binding.clone();
+    settings::update_settings(&app, |s| {
+        s.bindings.insert(nb.id.clone(), nb);
+    });
+
+    #[cfg(target_os = "linux")]
+    if super::wayland::is_wayland_session() {
+        return Ok(match super::wayland::request_rebind(&app).await {
+            Ok(()) => BindingResponse {
+                success: true,
+                binding: Some(new_binding),
+                error: None,
+            },
+            Err(e) => {
+                error!("[Shortcuts] Failed to rebind Wayland shortcuts: {}", e);
+                BindingResponse {
+                    success: false,
+                    binding: Some(new_binding),
+                    error: Some(e),
+                }
+            }
+        });
+    }
+
+    if let Err(e) = register_shortcut(&app, new_binding.clone()) {
+        error!("add_profile_binding error: {}", e);
+        return Ok(BindingResponse {
+            success: false,
+            binding: Some(new_binding),
+            error: Some(e),
+        });
+    }
+
+    Ok(BindingResponse {
+        success: true,
+        binding: Some(new_binding),
+        error: None,
+    })
+}
+
+/// Remove a shortcut added with [`add_profile_binding`].
+#[tauri::command]
+pub async fn remove_profile_binding(app: AppHandle, id: String) -> Result<(), String> {
+    if !id.starts_with(PROFILE_BINDING_PREFIX) {
+        return Err(format!("Binding '{}' cannot be removed", id));
+    }
+
+    let mut removed = None;
+    settings::update_settings(&app, |s| {
+        removed = s.bindings.remove(&id);
+    });
+    let Some(removed) = removed else {
+        return Err(format!("Binding with id '{}' not found", id));
+    };
+
+    #[cfg(target_os = "linux")]
+    if super::wayland::is_wayland_session() {
+        return super::wayland::request_rebind(&app).await;
+    }
+
+    if let Err(e) = unregister_shortcut(&app, removed) {
+        // The binding is gone from settings either way
+        warn!(
+            "remove_profile_binding: failed to unregister '{}': {}",
+            id, e
+        );
+    }
+    Ok(())
+}
+
+/// Set or clear the transcription profile a binding uses.
+#[tauri::command]
+pub fn change_binding_profile(
+    app: AppHandle,
+    id: String,
+    profile_id: Option<String>,
+) -> Result<(), String> {
+    settings::try_update_settings(&app, |s| {
+        if let Some(profile_id) = &profile_id {
+            if s.transcription_profile(profile_id).is_none() {
+                return Err(format!("Profile with id '{}' not found", profile_id));
+            }
+        }
+        let binding = s
+            .bindings
+            .get_mut(&id)
+            .ok_or_else(|| format!("Binding with id '{}' not found", id))?;
+        binding.profile_id = profile_id.clone();
+        Ok(())
+    })
+}
+
 /// Temporarily unregister a binding while the user is editing it in the UI.
 /// This avoids firing the action while keys are being recorded.
 ///
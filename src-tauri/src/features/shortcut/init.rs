@@ -9,7 +9,7 @@ use log::{error, info, warn};
 use tauri::{AppHandle, Manager};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-use crate::actions::ACTION_MAP;
+use crate::actions::action_for_binding;
 use crate::settings::{self, get_settings, ShortcutBinding};
 use crate::ManagedToggleState;
 
@@ -53,13 +53,17 @@ fn init_x11_shortcuts(app: &AppHandle) {
 
     for (_id, binding) in settings.bindings {
         // Skip bindings that don't have corresponding actions
-        if !ACTION_MAP.contains_key(&binding.id) {
+        if action_for_binding(&binding.id).is_none() {
             warn!(
                 "Skipping binding '{}' - no action defined in ACTION_MAP",
                 binding.id
             );
             continue;
         }
+        if binding.current_binding.is_empty() {
+            info!("[Shortcuts] '{}' has no shortcut assigned", binding.id);
+            continue;
+        }
         if let Err(e) = register_shortcut(app, binding.clone()) {
             error!("Failed to register shortcut {}: {}", binding.id, e);
         } else {
@@ -111,7 +115,7 @@ pub fn validate_shortcut_string(raw: &str) -> Result<(), String> {
 /// Register a single shortcut binding.
 pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
     // Ensure the binding has a corresponding action in ACTION_MAP
-    if !ACTION_MAP.contains_key(&binding.id) {
+    if action_for_binding(&binding.id).is_none() {
         let error_msg = format!(
             "No action defined in ACTION_MAP for binding ID '{}'",
             binding.id
@@ -120,6 +124,11 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
         return Err(error_msg);
     }
 
+    // Nothing to register for a binding the user hasn't assigned a key
+    if binding.current_binding.is_empty() {
+        return Ok(());
+    }
+
     // Validate human-level rules first
     if let Err(e) = validate_shortcut_string(&binding.current_binding) {
         warn!(
@@ -158,8 +167,12 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
                 let shortcut_string = scut.into_string();
                 let settings = get_settings(ah);
 
-                if let Some(action) = ACTION_MAP.get(&binding_id_for_closure) {
-                    if settings.push_to_talk {
+                if let Some(action) = action_for_binding(&binding_id_for_closure) {
+                    if action.is_toggle() {
+                        if event.state == ShortcutState::Pressed {
+                            action.start(ah, &binding_id_for_closure, &shortcut_string);
+                        }
+                    } else if settings.push_to_talk {
                         if event.state == ShortcutState::Pressed {
                             action.start(ah, &binding_id_for_closure, &shortcut_string);
                         } else if event.state == ShortcutState::Released {
@@ -207,6 +220,10 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
 
 /// Unregister a single shortcut binding.
 pub fn unregister_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    if binding.current_binding.is_empty() {
+        return Ok(());
+    }
+
     let shortcut = match binding.current_binding.parse::<Shortcut>() {
         Ok(s) => s,
         Err(e) => {
@@ -1,9 +1,15 @@
 //! Audio-related settings commands.
 
 use log::warn;
-use tauri::AppHandle;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
 
-use crate::settings::{self, SoundTheme};
+use crate::audio_toolkit::audio::recorder::MAX_PRE_ROLL_MS;
+use crate::managers::audio::{self, AudioRecordingManager};
+use crate::settings::{self, AppSettings, SoundTheme, VadEngine};
+
+/// Longest VAD prefill or hangover, three seconds of 30ms frames.
+const MAX_VAD_FRAMES: u32 = 100;
 
 /// Change push-to-talk setting.
 #[tauri::command]
@@ -62,3 +68,161 @@ pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Res
 
     Ok(())
 }
+
+/// Change how much audio from before the hotkey press starts a recording.
+#[tauri::command]
+pub fn change_pre_roll_ms_setting(app: AppHandle, pre_roll_ms: u32) -> Result<(), String> {
+    let pre_roll_ms = pre_roll_ms.min(MAX_PRE_ROLL_MS);
+    settings::update_settings(&app, |s| {
+        s.pre_roll_ms = pre_roll_ms;
+    });
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.set_pre_roll_ms(pre_roll_ms);
+    Ok(())
+}
+
+fn update_audio_processing(app: &AppHandle, update: impl FnOnce(&mut AppSettings)) {
+    settings::update_settings(app, update);
+    let settings = settings::get_settings(app);
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.set_dsp_config(audio::dsp_config(&settings));
+}
+
+fn update_vad(app: &AppHandle, update: impl FnOnce(&mut AppSettings)) {
+    settings::update_settings(app, update);
+    let settings = settings::get_settings(app);
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.set_vad_params(audio::vad_params(&settings));
+}
+
+/// Change which voice activity detector the recorder runs.
+#[tauri::command]
+pub fn change_vad_engine_setting(app: AppHandle, engine: String) -> Result<(), String> {
+    let parsed = match engine.as_str() {
+        "silero" => VadEngine::Silero,
+        "energy" => VadEngine::Energy,
+        "gmm" => VadEngine::Gmm,
+        "none" => VadEngine::None,
+        other => {
+            warn!("Invalid VAD engine '{}', defaulting to silero", other);
+            VadEngine::Silero
+        }
+    };
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if parsed == VadEngine::None && rm.is_hands_free() {
+        return Err("Hands-free dictation needs a VAD".to_string());
+    }
+    settings::update_settings(&app, |s| {
+        s.vad_engine = parsed;
+    });
+
+    rm.update_vad_engine()
+        .map_err(|e| format!("Failed to switch VAD: {}", e))
+}
+
+/// Change the speech probability above which a frame counts as voice.
+#[tauri::command]
+pub fn change_vad_threshold_setting(app: AppHandle, threshold: f32) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("VAD threshold must be between 0 and 1".to_string());
+    }
+    update_vad(&app, |s| s.vad_threshold = threshold);
+    Ok(())
+}
+
+/// Change how many frames from before speech starts are kept.
+#[tauri::command]
+pub fn change_vad_prefill_frames_setting(app: AppHandle, frames: u32) -> Result<(), String> {
+    if frames > MAX_VAD_FRAMES {
+        return Err(format!("Prefill can be at most {} frames", MAX_VAD_FRAMES));
+    }
+    update_vad(&app, |s| s.vad_prefill_frames = frames);
+    Ok(())
+}
+
+/// Change how many frames are kept after speech stops.
+#[tauri::command]
+pub fn change_vad_hangover_frames_setting(app: AppHandle, frames: u32) -> Result<(), String> {
+    if frames > MAX_VAD_FRAMES {
+        return Err(format!("Hangover can be at most {} frames", MAX_VAD_FRAMES));
+    }
+    update_vad(&app, |s| s.vad_hangover_frames = frames);
+    Ok(())
+}
+
+/// Change how many voiced frames in a row start speech.
+#[tauri::command]
+pub fn change_vad_onset_frames_setting(app: AppHandle, frames: u32) -> Result<(), String> {
+    if !(1..=10).contains(&frames) {
+        return Err("Onset must be between 1 and 10 frames".to_string());
+    }
+    update_vad(&app, |s| s.vad_onset_frames = frames);
+    Ok(())
+}
+
+/// Turn the high-pass filter against low rumble on or off.
+#[tauri::command]
+pub fn change_high_pass_filter_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_audio_processing(&app, |s| s.high_pass_filter_enabled = enabled);
+    Ok(())
+}
+
+/// Turn background noise suppression on or off.
+#[tauri::command]
+pub fn change_noise_suppression_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_audio_processing(&app, |s| s.noise_suppression_enabled = enabled);
+    Ok(())
+}
+
+/// Turn automatic gain on or off.
+#[tauri::command]
+pub fn change_auto_gain_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_audio_processing(&app, |s| s.auto_gain_enabled = enabled);
+    Ok(())
+}
+
+/// Change the silence that ends a hands-free utterance.
+#[tauri::command]
+pub fn change_hands_free_silence_ms_setting(app: AppHandle, silence_ms: u32) -> Result<(), String> {
+    if silence_ms < 100 {
+        return Err("Trailing silence must be at least 100ms".to_string());
+    }
+    settings::update_settings(&app, |s| {
+        s.hands_free_silence_ms = silence_ms;
+    });
+    Ok(())
+}
+
+/// Change the longest hands-free utterance.
+#[tauri::command]
+pub fn change_hands_free_max_utterance_ms_setting(
+    app: AppHandle,
+    max_utterance_ms: u32,
+) -> Result<(), String> {
+    if max_utterance_ms < 1_000 {
+        return Err("Utterances must be allowed at least one second".to_string());
+    }
+    if max_utterance_ms < settings::get_settings(&app).hands_free_min_speech_ms {
+        return Err("Utterances must be allowed their minimum speech length".to_string());
+    }
+    settings::update_settings(&app, |s| {
+        s.hands_free_max_utterance_ms = max_utterance_ms;
+    });
+    Ok(())
+}
+
+/// Change how much speech a hands-free utterance needs to be transcribed.
+#[tauri::command]
+pub fn change_hands_free_min_speech_ms_setting(
+    app: AppHandle,
+    min_speech_ms: u32,
+) -> Result<(), String> {
+    if min_speech_ms > settings::get_settings(&app).hands_free_max_utterance_ms {
+        return Err("Minimum speech can't be longer than the longest utterance".to_string());
+    }
+    settings::update_settings(&app, |s| {
+        s.hands_free_min_speech_ms = min_speech_ms;
+    });
+    Ok(())
+}
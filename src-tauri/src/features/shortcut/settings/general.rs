@@ -131,6 +131,18 @@ pub fn change_word_correction_threshold_setting(
     Ok(())
 }
 
+/// Change custom words fuzzy correction setting.
+#[tauri::command]
+pub fn change_custom_words_fuzzy_correction_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.custom_words_fuzzy_correction = enabled;
+    });
+    Ok(())
+}
+
 /// Change paste method setting.
 #[tauri::command]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
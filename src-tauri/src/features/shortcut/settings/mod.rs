@@ -5,10 +5,14 @@
 //! - `general` - General application settings (language, overlay, clipboard, etc.)
 //! - `post_process` - LLM/post-processing settings (providers, prompts, models)
 //! - `input_tracking` - Input tracking settings
+//! - `transcription` - Remote transcription settings and transcription profiles
+//! - `wake_word` - Wake words and their sensitivity
 
 pub mod audio;
 pub mod general;
 pub mod input_tracking;
 pub mod meeting;
 pub mod post_process;
+pub mod transcription;
 pub mod tts;
+pub mod wake_word;
@@ -0,0 +1,195 @@
+//! Remote transcription, transcription profile, hallucination filter and
+//! whisper decoding settings commands.
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+
+use crate::managers::model::ModelManager;
+use crate::settings::{self, TranscriptionProfile, WhisperDecodingParams};
+
+/// Change remote transcription enabled setting.
+#[tauri::command]
+pub fn change_remote_transcription_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.remote_transcription_enabled = enabled;
+    });
+    Ok(())
+}
+
+/// Change the provider whose base URL and API key remote transcription uses.
+#[tauri::command]
+pub fn change_remote_transcription_provider_setting(
+    app: AppHandle,
+    provider_id: String,
+) -> Result<(), String> {
+    settings::try_update_settings(&app, |s| {
+        if s.post_process_provider(&provider_id).is_none() {
+            return Err(format!("Provider '{}' not found", provider_id));
+        }
+        s.remote_transcription_provider_id = provider_id.clone();
+        Ok(())
+    })
+}
+
+/// Change remote transcription model setting.
+#[tauri::command]
+pub fn change_remote_transcription_model_setting(
+    app: AppHandle,
+    model: String,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.remote_transcription_model = model.trim().to_string();
+    });
+    Ok(())
+}
+
+/// Add a transcription profile that overrides nothing yet.
+#[tauri::command]
+pub fn add_transcription_profile(
+    app: AppHandle,
+    name: String,
+) -> Result<TranscriptionProfile, String> {
+    let profile = TranscriptionProfile {
+        id: format!("profile_{}", chrono::Utc::now().timestamp_millis()),
+        name,
+        model: None,
+        language: None,
+        translate_to_english: None,
+        post_process_enabled: None,
+        post_process_prompt_id: None,
+    };
+
+    let result = profile.clone();
+    settings::update_settings(&app, |s| {
+        s.transcription_profiles.push(profile);
+    });
+
+    Ok(result)
+}
+
+/// Update an existing transcription profile.
+#[tauri::command]
+pub fn update_transcription_profile(
+    app: AppHandle,
+    profile: TranscriptionProfile,
+) -> Result<(), String> {
+    settings::try_update_settings(&app, |s| {
+        if let Some(prompt_id) = &profile.post_process_prompt_id {
+            if !s.post_process_prompts.iter().any(|p| &p.id == prompt_id) {
+                return Err(format!("Prompt with id '{}' not found", prompt_id));
+            }
+        }
+
+        match s
+            .transcription_profiles
+            .iter_mut()
+            .find(|p| p.id == profile.id)
+        {
+            Some(existing) => {
+                *existing = profile.clone();
+                Ok(())
+            }
+            None => Err(format!("Profile with id '{}' not found", profile.id)),
+        }
+    })
+}
+
+/// Delete a transcription profile. Bindings using it fall back to the global settings.
+#[tauri::command]
+pub fn delete_transcription_profile(app: AppHandle, id: String) -> Result<(), String> {
+    settings::try_update_settings(&app, |s| {
+        let original_len = s.transcription_profiles.len();
+        s.transcription_profiles.retain(|p| p.id != id);
+
+        if s.transcription_profiles.len() == original_len {
+            return Err(format!("Profile with id '{}' not found", id));
+        }
+
+        for binding in s.bindings.values_mut() {
+            if binding.profile_id.as_ref() == Some(&id) {
+                binding.profile_id = None;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Change hallucination filter enabled setting.
+#[tauri::command]
+pub fn change_hallucination_filter_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.hallucination_filter_enabled = enabled;
+    });
+    Ok(())
+}
+
+/// Replace the phrases the hallucination filter drops.
+#[tauri::command]
+pub fn update_hallucination_phrases(app: AppHandle, phrases: Vec<String>) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.hallucination_phrases = phrases
+            .iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+    });
+    Ok(())
+}
+
+/// Change the minimum VAD-measured speech below which a recording produces no text.
+#[tauri::command]
+pub fn change_hallucination_min_speech_ms_setting(
+    app: AppHandle,
+    min_speech_ms: u32,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.hallucination_min_speech_ms = min_speech_ms;
+    });
+    Ok(())
+}
+
+/// Set the decoding parameters of a whisper model.
+#[tauri::command]
+pub fn set_whisper_decoding_params(
+    app: AppHandle,
+    model_id: String,
+    params: WhisperDecodingParams,
+) -> Result<(), String> {
+    let model = app
+        .state::<Arc<ModelManager>>()
+        .get_model_info(&model_id)
+        .ok_or_else(|| format!("Model '{}' not found", model_id))?;
+    if model.backend.as_deref() != Some("whisper") {
+        return Err(format!("Model '{}' is not a whisper model", model_id));
+    }
+    params.validate()?;
+
+    let params = WhisperDecodingParams {
+        initial_prompt: params
+            .initial_prompt
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty()),
+        ..params
+    };
+    settings::update_settings(&app, |s| {
+        s.whisper_decoding_params.insert(model_id, params);
+    });
+    Ok(())
+}
+
+/// Restore the default decoding parameters of a whisper model.
+#[tauri::command]
+pub fn reset_whisper_decoding_params(app: AppHandle, model_id: String) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.whisper_decoding_params.remove(&model_id);
+    });
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Wake word settings commands.
+
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+use crate::managers::model::ModelManager;
+use crate::managers::wake_word::{WakeWordManager, KWS_MODEL_ID};
+use crate::settings::{self, is_known_binding, WakeWord};
+
+fn reload(app: &AppHandle) -> Result<(), String> {
+    app.state::<Arc<WakeWordManager>>()
+        .reload()
+        .map_err(|e| format!("Failed to load wake words: {}", e))
+}
+
+#[tauri::command]
+pub fn change_wake_word_enabled_setting(
+    app: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.wake_word_enabled = enabled;
+    });
+
+    // Auto-download the keyword spotting model when enabling
+    let needs_download = model_manager
+        .get_model_info(KWS_MODEL_ID)
+        .map(|m| !m.is_downloaded && !m.is_downloading)
+        .unwrap_or(false);
+    if enabled && needs_download {
+        let mm = model_manager.inner().clone();
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = mm.download_model(KWS_MODEL_ID).await {
+                log::error!("Failed to download wake word model: {}", e);
+                return;
+            }
+            if let Err(e) = reload(&app) {
+                log::error!("{}", e);
+            }
+        });
+        return Ok(());
+    }
+
+    reload(&app)
+}
+
+#[tauri::command]
+pub fn update_wake_words(app: AppHandle, wake_words: Vec<WakeWord>) -> Result<(), String> {
+    let wake_words: Vec<WakeWord> = wake_words
+        .into_iter()
+        .filter(|w| !w.phrase.trim().is_empty())
+        .collect();
+    settings::try_update_settings(&app, |s| {
+        // A wake word pointing at a missing binding would never fire
+        if let Some(unknown) = wake_words
+            .iter()
+            .find(|w| !is_known_binding(&w.binding_id) || !s.bindings.contains_key(&w.binding_id))
+        {
+            return Err(format!(
+                "Binding with id '{}' not found",
+                unknown.binding_id
+            ));
+        }
+        s.wake_words = wake_words;
+        Ok(())
+    })?;
+    reload(&app)
+}
+
+#[tauri::command]
+pub fn change_wake_word_sensitivity_setting(
+    app: AppHandle,
+    sensitivity: f32,
+) -> Result<(), String> {
+    settings::update_settings(&app, |s| {
+        s.wake_word_sensitivity = sensitivity.clamp(0.0, 1.0);
+    });
+    reload(&app)
+}
+
+#[tauri::command]
+pub fn get_wake_word_status(app: AppHandle) -> Result<WakeWordStatus, String> {
+    let model_manager = app.state::<Arc<ModelManager>>();
+    let model = model_manager.get_model_info(KWS_MODEL_ID);
+
+    Ok(WakeWordStatus {
+        model_downloaded: model.as_ref().map(|m| m.is_downloaded).unwrap_or(false),
+        model_downloading: model.as_ref().map(|m| m.is_downloading).unwrap_or(false),
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct WakeWordStatus {
+    pub model_downloaded: bool,
+    pub model_downloading: bool,
+}
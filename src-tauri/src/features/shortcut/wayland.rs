@@ -32,7 +32,7 @@ use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::{mpsc, oneshot};
 
-use crate::actions::ACTION_MAP;
+use crate::actions::action_for_binding;
 use crate::settings::{self, ShortcutBinding};
 use crate::ManagedToggleState;
 
@@ -49,6 +49,11 @@ pub(crate) enum WaylandCommand {
         window_identifier: Option<WindowIdentifier>,
         respond: oneshot::Sender<Result<(), String>>,
     },
+    /// Recreate the session and bind the current set of bindings, after
+    /// bindings were added or removed.
+    Rebind {
+        respond: oneshot::Sender<Result<(), String>>,
+    },
 }
 
 /// Stores the actual triggers assigned by the Wayland portal.
@@ -264,6 +269,11 @@ async fn wayland_manager_task(
                             }
                         }
                     }
+                    WaylandCommand::Rebind { respond } => {
+                        info!("[Wayland] Processing rebind request");
+                        let result = do_rebind_fallback(&portal, &mut session, &app, None).await;
+                        let _ = respond.send(result.map(|_| ()));
+                    }
                 }
             }
             else => {
@@ -302,7 +312,7 @@ async fn do_bind_shortcuts(
     let bindings: Vec<(String, ShortcutBinding)> = settings
         .bindings
         .into_iter()
-        .filter(|(_, b)| ACTION_MAP.contains_key(&b.id))
+        .filter(|(_, b)| action_for_binding(&b.id).is_some() && !b.current_binding.is_empty())
         .collect();
 
     if bindings.is_empty() {
@@ -487,7 +497,7 @@ fn update_dconf_shortcuts(app: &AppHandle) -> Result<(), String> {
     let bindings: Vec<(String, settings::ShortcutBinding)> = settings
         .bindings
         .into_iter()
-        .filter(|(_, b)| ACTION_MAP.contains_key(&b.id))
+        .filter(|(_, b)| action_for_binding(&b.id).is_some() && !b.current_binding.is_empty())
         .collect();
 
     if bindings.is_empty() {
@@ -720,6 +730,32 @@ pub async fn request_configure(
     respond_rx.await.map_err(|_| "Response lost".to_string())?
 }
 
+/// Rebind all shortcuts so the portal picks up added or removed bindings.
+///
+/// If the manager task is not running, it will be started lazily.
+pub async fn request_rebind(app: &AppHandle) -> Result<(), String> {
+    ensure_manager_running(app).await?;
+
+    let tx = {
+        let state = app
+            .try_state::<ManagedWaylandCommandSender>()
+            .ok_or("Wayland manager not initialized")?;
+        let guard = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        guard
+            .clone()
+            .ok_or("Wayland manager failed to start".to_string())?
+    };
+
+    let (respond_tx, respond_rx) = oneshot::channel();
+    tx.send(WaylandCommand::Rebind {
+        respond: respond_tx,
+    })
+    .await
+    .map_err(|_| "Manager task not responding".to_string())?;
+
+    respond_rx.await.map_err(|_| "Response lost".to_string())?
+}
+
 /// Open the system dialog to configure Wayland shortcuts.
 /// Uses portal v2 ConfigureShortcuts to show the native configuration UI.
 /// Falls back to rebind (session recreation) on portal v1.
@@ -881,7 +917,7 @@ pub fn check_wayland_shortcut_conflict(binding: String) -> Option<String> {
 
 /// Handle shortcut activation (key pressed).
 fn handle_shortcut_activated(app: &AppHandle, shortcut_id: &str) {
-    let Some(action) = ACTION_MAP.get(shortcut_id) else {
+    let Some(action) = action_for_binding(shortcut_id) else {
         warn!("[Wayland] No action found for shortcut ID: {}", shortcut_id);
         return;
     };
@@ -898,7 +934,11 @@ fn handle_shortcut_activated(app: &AppHandle, shortcut_id: &str) {
 
     let settings = settings::get_settings(app);
 
-    if settings.push_to_talk {
+    if action.is_toggle() {
+        // The action switches itself on and off
+        info!("[Wayland] Toggling action for '{}'", shortcut_id);
+        action.start(app, shortcut_id, shortcut_id);
+    } else if settings.push_to_talk {
         // Push-to-talk mode: start on press
         info!("[Wayland] PTT mode: starting action for '{}'", shortcut_id);
         action.start(app, shortcut_id, shortcut_id);
@@ -935,13 +975,13 @@ fn handle_shortcut_activated(app: &AppHandle, shortcut_id: &str) {
 
 /// Handle shortcut deactivation (key released).
 fn handle_shortcut_deactivated(app: &AppHandle, shortcut_id: &str) {
-    let Some(action) = ACTION_MAP.get(shortcut_id) else {
+    let Some(action) = action_for_binding(shortcut_id) else {
         return;
     };
 
     let settings = settings::get_settings(app);
 
-    if settings.push_to_talk {
+    if settings.push_to_talk && !action.is_toggle() {
         // Push-to-talk mode: stop on release
         info!("[Wayland] PTT mode: stopping action for '{}'", shortcut_id);
         action.stop(app, shortcut_id, shortcut_id);
@@ -25,6 +25,7 @@ use features::shortcut;
 
 use env_filter::Builder as EnvFilterBuilder;
 use managers::audio::AudioRecordingManager;
+use managers::benchmark::BenchmarkManager;
 use managers::diarization::DiarizationManager;
 use managers::history::HistoryManager;
 use managers::input_tracker::InputTrackerManager;
@@ -32,6 +33,7 @@ use managers::meeting::MeetingManager;
 use managers::model::ModelManager;
 use managers::transcription::TranscriptionManager;
 use managers::tts::TtsManager;
+use managers::wake_word::WakeWordManager;
 use startup::show_main_window;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -42,7 +44,7 @@ use signal_hook::consts::SIGUSR2;
 #[cfg(unix)]
 use signal_hook::iterator::Signals;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicU8, Ordering};
 use tauri::tray::TrayIconBuilder;
 use tauri::Emitter;
 use tauri::{AppHandle, Manager};
@@ -57,19 +59,6 @@ struct ShortcutToggleStates {
 
 type ManagedToggleState = Mutex<ShortcutToggleStates>;
 
-/// Global flag to track if a file transcription is currently in progress
-static FILE_TRANSCRIPTION_ACTIVE: AtomicBool = AtomicBool::new(false);
-
-/// Check if a file transcription is currently active
-pub fn is_file_transcription_active() -> bool {
-    FILE_TRANSCRIPTION_ACTIVE.load(Ordering::SeqCst)
-}
-
-/// Set the file transcription active state
-pub fn set_file_transcription_active(active: bool) {
-    FILE_TRANSCRIPTION_ACTIVE.store(active, Ordering::SeqCst);
-}
-
 fn initialize_core_logic(app_handle: &AppHandle) {
     // First, initialize the managers
     let recording_manager = Arc::new(
@@ -77,6 +66,20 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     );
     let model_manager =
         Arc::new(ModelManager::new(app_handle).expect("Failed to initialize model manager"));
+
+    // A remote model catalog starts from its cached copy and is refreshed here
+    let catalog_source = settings::get_settings(app_handle).model_catalog_source;
+    if catalog_source
+        .as_deref()
+        .map_or(false, managers::catalog::is_remote)
+    {
+        let model_manager = model_manager.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = model_manager.refresh_catalog().await {
+                log::warn!("Failed to refresh the model catalog: {}", e);
+            }
+        });
+    }
     let transcription_manager = Arc::new(
         TranscriptionManager::new(app_handle, model_manager.clone())
             .expect("Failed to initialize transcription manager"),
@@ -106,6 +109,15 @@ fn initialize_core_logic(app_handle: &AppHandle) {
             .expect("Failed to initialize diarization manager"),
     );
 
+    let benchmark_manager = Arc::new(
+        BenchmarkManager::new(
+            app_handle,
+            model_manager.clone(),
+            transcription_manager.clone(),
+        )
+        .expect("Failed to initialize benchmark manager"),
+    );
+
     // Add managers to Tauri's managed state
     app_handle.manage(recording_manager.clone());
     app_handle.manage(model_manager.clone());
@@ -115,6 +127,14 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(tts_manager.clone());
     app_handle.manage(meeting_manager.clone());
     app_handle.manage(diarization_manager.clone());
+    app_handle.manage(benchmark_manager.clone());
+
+    // Wake words listen on the recorder's stream, so load them once it is managed
+    let wake_word_manager = Arc::new(WakeWordManager::new(app_handle, model_manager.clone()));
+    app_handle.manage(wake_word_manager.clone());
+    if let Err(e) = wake_word_manager.reload() {
+        log::warn!("Failed to load wake words: {}", e);
+    }
 
     // Start input tracker if enabled in settings
     {
@@ -611,6 +631,9 @@ pub fn run() {
             shortcut::bindings::reset_binding,
             shortcut::bindings::suspend_binding,
             shortcut::bindings::resume_binding,
+            shortcut::bindings::add_profile_binding,
+            shortcut::bindings::remove_profile_binding,
+            shortcut::bindings::change_binding_profile,
             shortcut::check_wayland_shortcut_conflict,
             shortcut::is_wayland_session,
             shortcut::get_wayland_shortcuts,
@@ -624,6 +647,23 @@ pub fn run() {
             shortcut::settings::audio::change_audio_feedback_volume_setting,
             shortcut::settings::audio::change_sound_theme_setting,
             shortcut::settings::audio::change_mute_while_recording_setting,
+            shortcut::settings::audio::change_pre_roll_ms_setting,
+            shortcut::settings::audio::change_hands_free_silence_ms_setting,
+            shortcut::settings::audio::change_hands_free_max_utterance_ms_setting,
+            shortcut::settings::audio::change_hands_free_min_speech_ms_setting,
+            shortcut::settings::audio::change_vad_engine_setting,
+            shortcut::settings::audio::change_vad_threshold_setting,
+            shortcut::settings::audio::change_vad_prefill_frames_setting,
+            shortcut::settings::audio::change_vad_hangover_frames_setting,
+            shortcut::settings::audio::change_vad_onset_frames_setting,
+            shortcut::settings::audio::change_high_pass_filter_setting,
+            shortcut::settings::audio::change_noise_suppression_setting,
+            shortcut::settings::audio::change_auto_gain_setting,
+            // Wake word settings commands
+            shortcut::settings::wake_word::change_wake_word_enabled_setting,
+            shortcut::settings::wake_word::update_wake_words,
+            shortcut::settings::wake_word::change_wake_word_sensitivity_setting,
+            shortcut::settings::wake_word::get_wake_word_status,
             // General settings commands
             shortcut::settings::general::change_start_hidden_setting,
             shortcut::settings::general::change_autostart_setting,
@@ -633,6 +673,7 @@ pub fn run() {
             shortcut::settings::general::change_debug_mode_setting,
             shortcut::settings::general::change_debug_logging_setting,
             shortcut::settings::general::change_word_correction_threshold_setting,
+            shortcut::settings::general::change_custom_words_fuzzy_correction_setting,
             shortcut::settings::general::change_paste_method_setting,
             shortcut::settings::general::change_clipboard_handling_setting,
             shortcut::settings::general::update_custom_words,
@@ -649,6 +690,18 @@ pub fn run() {
             shortcut::settings::post_process::set_post_process_selected_prompt,
             shortcut::settings::post_process::check_model_tool_support,
             shortcut::settings::post_process::change_voice_commands_enabled_setting,
+            // Remote transcription settings commands
+            shortcut::settings::transcription::change_remote_transcription_enabled_setting,
+            shortcut::settings::transcription::change_remote_transcription_provider_setting,
+            shortcut::settings::transcription::change_remote_transcription_model_setting,
+            shortcut::settings::transcription::add_transcription_profile,
+            shortcut::settings::transcription::update_transcription_profile,
+            shortcut::settings::transcription::delete_transcription_profile,
+            shortcut::settings::transcription::change_hallucination_filter_enabled_setting,
+            shortcut::settings::transcription::update_hallucination_phrases,
+            shortcut::settings::transcription::change_hallucination_min_speech_ms_setting,
+            shortcut::settings::transcription::set_whisper_decoding_params,
+            shortcut::settings::transcription::reset_whisper_decoding_params,
             // Input tracking settings commands
             shortcut::settings::input_tracking::change_input_tracking_setting,
             shortcut::settings::input_tracking::change_input_tracking_excluded_apps,
@@ -662,7 +715,16 @@ pub fn run() {
             commands::models::get_model_info,
             commands::models::download_model,
             commands::models::delete_model,
+            commands::models::import_model,
+            commands::models::set_model_catalog_source,
+            commands::models::update_model_mirrors,
+            commands::models::change_max_concurrent_downloads_setting,
+            commands::models::change_download_bandwidth_limit_setting,
             commands::models::cancel_download,
+            commands::models::verify_installed_models,
+            commands::models::get_model_storage_report,
+            commands::models::cleanup_orphaned_model_files,
+            commands::models::relocate_models_dir,
             commands::models::set_active_model,
             commands::models::get_current_model,
             commands::models::get_transcription_model_status,
@@ -670,12 +732,15 @@ pub fn run() {
             commands::models::has_any_models_available,
             commands::models::has_any_models_or_downloads,
             commands::models::get_recommended_first_model,
+            commands::models::run_model_benchmark,
+            commands::models::get_model_benchmarks,
             commands::audio::update_microphone_mode,
             commands::audio::get_microphone_mode,
             commands::audio::get_available_microphones,
             commands::audio::set_selected_microphone,
             commands::audio::get_selected_microphone,
             commands::audio::set_clamshell_microphone,
+            commands::audio::set_microphone_priority,
             commands::audio::get_clamshell_microphone,
             commands::audio::get_available_output_devices,
             commands::audio::set_selected_output_device,
@@ -686,6 +751,9 @@ pub fn run() {
             helpers::clamshell::is_laptop,
             commands::transcription::set_model_unload_timeout,
             commands::transcription::get_model_load_status,
+            commands::transcription::get_transcription_capabilities,
+            commands::transcription::get_transcription_queue,
+            commands::transcription::cancel_transcription_job,
             commands::transcription::unload_model_manually,
             commands::history::get_history_entries,
             commands::history::toggle_history_entry_saved,
@@ -696,6 +764,7 @@ pub fn run() {
             commands::history::update_history_limit,
             commands::history::update_recording_retention_period,
             commands::file_transcription::transcribe_audio_file,
+            commands::file_transcription::cancel_file_transcription,
             commands::input_tracking::get_input_entries,
             commands::input_tracking::delete_input_entry,
             commands::input_tracking::clear_all_input_entries,
@@ -727,6 +796,14 @@ pub fn run() {
             commands::meeting::retranscribe_meeting,
             commands::tts::preview_tts,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Stop the transcription worker so no job outlives the app
+                if let Some(manager) = app.try_state::<Arc<TranscriptionManager>>() {
+                    manager.shutdown();
+                }
+            }
+        });
 }
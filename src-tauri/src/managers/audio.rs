@@ -1,14 +1,23 @@
 use crate::actions::OPERATION_GENERATION;
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::audio::{device_name_matches, DspConfig, InputSource};
+use crate::audio_toolkit::vad::{
+    speech_regions, EnergyVad, GmmVad, SmoothedVad, SpeechRegion, UtteranceConfig, UtteranceEvent,
+    VadParams,
+};
+use crate::audio_toolkit::{
+    list_input_devices, AudioRecorder, KeywordSpotter, SharedKeywordSpotter, SileroVad,
+    VoiceActivityDetector,
+};
 use crate::helpers::clamshell;
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, AppSettings};
+use crate::managers::wake_word;
+use crate::settings::{get_settings, AppSettings, VadEngine};
 use crate::utils;
-use log::{debug, info};
+use log::{debug, info, warn};
 use std::sync::atomic::Ordering;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::Instant;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 fn set_mute(mute: bool) {
     #[cfg(target_os = "windows")]
@@ -93,7 +102,11 @@ const WHISPER_SAMPLE_RATE: usize = 16000;
 #[derive(Clone, Debug)]
 pub enum RecordingState {
     Idle,
-    Recording { binding_id: String },
+    Recording {
+        binding_id: String,
+    },
+    /// Hands-free dictation: the VAD decides when each utterance starts and ends.
+    Listening,
 }
 
 #[derive(Clone, Debug)]
@@ -104,19 +117,174 @@ pub enum MicrophoneMode {
 
 /* ──────────────────────────────────────────────────────────────── */
 
+/// Processing stages switched on in the settings.
+pub fn dsp_config(settings: &AppSettings) -> DspConfig {
+    DspConfig {
+        high_pass: settings.high_pass_filter_enabled,
+        noise_suppression: settings.noise_suppression_enabled,
+        auto_gain: settings.auto_gain_enabled,
+    }
+}
+
+/// VAD tuning from the settings.
+pub fn vad_params(settings: &AppSettings) -> VadParams {
+    VadParams {
+        threshold: settings.vad_threshold,
+        prefill_frames: settings.vad_prefill_frames as usize,
+        hangover_frames: settings.vad_hangover_frames as usize,
+        onset_frames: settings.vad_onset_frames as usize,
+    }
+}
+
+/// The smoothed detector picked in the settings, or `None` to record
+/// everything.
+fn create_vad(
+    vad_path: &str,
+    settings: &AppSettings,
+) -> Result<Option<Box<dyn VoiceActivityDetector>>, anyhow::Error> {
+    let params = vad_params(settings);
+    let detector: Box<dyn VoiceActivityDetector> = match settings.vad_engine {
+        VadEngine::Silero => Box::new(
+            SileroVad::new(vad_path, params.threshold)
+                .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?,
+        ),
+        VadEngine::Energy => Box::new(EnergyVad::new(params.threshold)),
+        VadEngine::Gmm => Box::new(GmmVad::new(params.threshold)),
+        VadEngine::None => return Ok(None),
+    };
+    Ok(Some(Box::new(SmoothedVad::new(
+        detector,
+        params.prefill_frames,
+        params.hangover_frames,
+        params.onset_frames,
+    ))))
+}
+
+/// Where the VAD picked in the settings hears speech in 16kHz `samples`, for
+/// audio that didn't come through the recorder. `None` when the VAD is off
+/// or can't be loaded.
+pub fn detect_speech_regions(
+    app_handle: &tauri::AppHandle,
+    samples: &[f32],
+) -> Option<Vec<SpeechRegion>> {
+    app_handle
+        .try_state::<Arc<AudioRecordingManager>>()?
+        .detect_speech_regions(samples)
+}
+
+/// Milliseconds of speech in `samples`; see [`detect_speech_regions`].
+pub fn measure_speech_ms(app_handle: &tauri::AppHandle, samples: &[f32]) -> Option<i64> {
+    detect_speech_regions(app_handle, samples)
+        .map(|regions| regions.iter().map(SpeechRegion::duration_ms).sum::<u64>() as i64)
+}
+
+/// Microphones the settings ask for that are plugged in, most preferred
+/// first: the clamshell microphone while the lid is closed, then devices
+/// matching the priority list in its order, then the selected microphone.
+/// Empty when the system default should be used, without listing devices.
+pub fn preferred_input_devices(settings: &AppSettings) -> Vec<cpal::Device> {
+    let should_use_clamshell_device = if settings.clamshell_microphone.is_some() {
+        match clamshell::is_clamshell() {
+            Ok(is_clamshell) => is_clamshell,
+            Err(err) => {
+                debug!("Failed to determine clamshell state: {}", err);
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    let (pinned, fallback) = if should_use_clamshell_device {
+        (settings.clamshell_microphone.as_deref(), None)
+    } else {
+        (None, settings.selected_microphone.as_deref())
+    };
+    if pinned.is_none() && fallback.is_none() && settings.microphone_priority.is_empty() {
+        return Vec::new();
+    }
+
+    let mut remaining = match list_input_devices() {
+        Ok(devices) => devices,
+        Err(e) => {
+            debug!("Failed to list devices, using default: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut ranked = Vec::new();
+    let mut take = |matches: &dyn Fn(&str) -> bool| {
+        while let Some(i) = remaining.iter().position(|d| matches(d.name.as_str())) {
+            ranked.push(remaining.remove(i).device);
+        }
+    };
+
+    if let Some(name) = pinned {
+        take(&|device| device == name);
+    }
+    for pattern in &settings.microphone_priority {
+        take(&|device| device_name_matches(pattern, device));
+    }
+    if let Some(name) = fallback {
+        take(&|device| device == name);
+    }
+    ranked
+}
+
+/// Names audio to record instead of the microphone, in the form
+/// [`InputSource`] parses, e.g. `file:/tmp/hello.wav@max`.
+const INPUT_SOURCE_VAR: &str = "ECHO_INPUT_SOURCE";
+
+/// `device`, unless `ECHO_INPUT_SOURCE` asks for a virtual input so that
+/// dictation and meetings can run on machines without sound hardware.
+pub fn input_source(device: Option<cpal::Device>) -> InputSource {
+    match std::env::var(INPUT_SOURCE_VAR) {
+        Ok(spec) if !spec.trim().is_empty() => match spec.parse() {
+            Ok(source) => return source,
+            Err(err) => warn!(
+                "Ignoring invalid {} value '{}': {}",
+                INPUT_SOURCE_VAR, spec, err
+            ),
+        },
+        _ => {}
+    }
+    InputSource::Device(device)
+}
+
+/// Let the frontend know the recorder moved to another microphone.
+pub fn emit_microphone_changed(app_handle: &tauri::AppHandle, device_name: String) {
+    let _ = app_handle.emit("microphone-changed", device_name);
+}
+
 fn create_audio_recorder(
     vad_path: &str,
     app_handle: &tauri::AppHandle,
+    keyword_spotter: SharedKeywordSpotter,
 ) -> Result<AudioRecorder, anyhow::Error> {
-    let silero = SileroVad::new(vad_path, 0.3)
-        .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
-    let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
+    let settings = get_settings(app_handle);
+    debug!("Using the {:?} VAD", settings.vad_engine);
 
     // Recorder with VAD plus a spectrum-level callback that forwards updates to
     // the frontend.
-    let recorder = AudioRecorder::new()
-        .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?
-        .with_vad(Box::new(smoothed_vad))
+    let mut recorder = AudioRecorder::new()
+        .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?;
+    if let Some(vad) = create_vad(vad_path, &settings)? {
+        recorder = recorder.with_vad(vad);
+    }
+    let recorder = recorder
+        .with_pre_roll(settings.pre_roll_ms)
+        .with_dsp(dsp_config(&settings))
+        .with_keyword_spotter(keyword_spotter, {
+            let app_handle = app_handle.clone();
+            move |label| wake_word::handle_keyword(&app_handle, label)
+        })
+        .with_device_chooser({
+            let app_handle = app_handle.clone();
+            move || preferred_input_devices(&get_settings(&app_handle))
+        })
+        .with_device_callback({
+            let app_handle = app_handle.clone();
+            move |name| emit_microphone_changed(&app_handle, name)
+        })
         .with_level_callback({
             let app_handle = app_handle.clone();
             move |levels| {
@@ -139,6 +307,11 @@ pub struct AudioRecordingManager {
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
+    hands_free_events: Arc<Mutex<Option<mpsc::Sender<UtteranceEvent>>>>,
+    keyword_spotter: SharedKeywordSpotter,
+    /// VAD for audio that didn't come through the recorder, kept loaded
+    /// between uses along with the engine it was created for.
+    offline_vad: Arc<Mutex<Option<(VadEngine, Box<dyn VoiceActivityDetector>)>>>,
 }
 
 impl AudioRecordingManager {
@@ -161,6 +334,9 @@ impl AudioRecordingManager {
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
+            hands_free_events: Arc::new(Mutex::new(None)),
+            keyword_spotter: Arc::new(Mutex::new(None)),
+            offline_vad: Arc::new(Mutex::new(None)),
         };
 
         // Always-on?  Open immediately.
@@ -197,7 +373,11 @@ impl AudioRecordingManager {
         debug!("Preloading VAD model from {:?}", vad_path);
 
         // Create recorder (this loads the heavy VAD model)
-        let recorder = create_audio_recorder(vad_path.to_str().unwrap(), &self.app_handle)?;
+        let recorder = create_audio_recorder(
+            vad_path.to_str().unwrap(),
+            &self.app_handle,
+            self.keyword_spotter.clone(),
+        )?;
 
         // Store it if not already set by start_microphone_stream
         let mut recorder_guard = self.recorder.lock().unwrap();
@@ -211,35 +391,40 @@ impl AudioRecordingManager {
 
     /* ---------- helper methods --------------------------------------------- */
 
-    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
-        let should_use_clamshell_device = if settings.clamshell_microphone.is_some() {
-            match clamshell::is_clamshell() {
-                Ok(is_clamshell) => is_clamshell,
-                Err(err) => {
-                    debug!("Failed to determine clamshell state: {}", err);
-                    false
+    /// See [`detect_speech_regions`]. The detector is only created again when
+    /// the VAD engine setting changes; new tuning is applied to it in place.
+    pub fn detect_speech_regions(&self, samples: &[f32]) -> Option<Vec<SpeechRegion>> {
+        let settings = get_settings(&self.app_handle);
+        let mut offline_vad = self.offline_vad.lock().unwrap();
+        if !matches!(&*offline_vad, Some((engine, _)) if *engine == settings.vad_engine) {
+            let vad_path = self
+                .app_handle
+                .path()
+                .resolve(
+                    "resources/models/silero_vad_v4.onnx",
+                    tauri::path::BaseDirectory::Resource,
+                )
+                .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e));
+            let vad = vad_path.and_then(|path| create_vad(path.to_str().unwrap(), &settings));
+            *offline_vad = match vad {
+                Ok(vad) => vad.map(|vad| (settings.vad_engine, vad)),
+                Err(e) => {
+                    warn!("Failed to detect speech: {}", e);
+                    None
                 }
-            }
-        } else {
-            false
-        };
-
-        let device_name = if should_use_clamshell_device {
-            settings.clamshell_microphone.as_deref()
-        } else {
-            settings.selected_microphone.as_deref()
-        }?;
-
-        match list_input_devices() {
-            Ok(devices) => devices
-                .into_iter()
-                .find(|d| d.name == device_name)
-                .map(|d| d.device),
-            Err(e) => {
-                debug!("Failed to list devices, using default: {}", e);
-                None
-            }
+            };
         }
+
+        let (_, vad) = offline_vad.as_mut()?;
+        vad.configure(&vad_params(&settings));
+        // Resets the detector before it starts
+        speech_regions(vad.as_mut(), samples)
+            .map_err(|e| warn!("Failed to detect speech: {}", e))
+            .ok()
+    }
+
+    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
+        preferred_input_devices(settings).into_iter().next()
     }
 
     /* ---------- microphone life-cycle -------------------------------------- */
@@ -288,6 +473,7 @@ impl AudioRecordingManager {
             *recorder_opt = Some(create_audio_recorder(
                 vad_path.to_str().unwrap(),
                 &self.app_handle,
+                self.keyword_spotter.clone(),
             )?);
         }
 
@@ -296,7 +482,7 @@ impl AudioRecordingManager {
         let selected_device = self.get_effective_microphone_device(&settings);
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device)
+            rec.open(input_source(selected_device))
                 .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
         }
 
@@ -329,6 +515,31 @@ impl AudioRecordingManager {
         debug!("Microphone stream stopped");
     }
 
+    /// Rebuild the recorder so a new VAD engine takes effect, reopening the
+    /// stream if it was open.
+    pub fn update_vad_engine(&self) -> Result<(), anyhow::Error> {
+        if matches!(
+            *self.state.lock().unwrap(),
+            RecordingState::Recording { .. }
+        ) {
+            return Err(anyhow::anyhow!("Cannot switch the VAD while recording"));
+        }
+
+        let was_open = *self.is_open.lock().unwrap();
+        self.stop_microphone_stream();
+        self.recorder.lock().unwrap().take();
+        if !was_open {
+            return self.preload_recorder();
+        }
+
+        self.start_microphone_stream()?;
+        let events = self.hands_free_events.lock().unwrap().clone();
+        if let Some(events) = events {
+            self.listen(events)?;
+        }
+        Ok(())
+    }
+
     /* ---------- mode switching --------------------------------------------- */
 
     pub fn update_mode(&self, new_mode: MicrophoneMode) -> Result<(), anyhow::Error> {
@@ -406,11 +617,78 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Start hands-free dictation. Utterances are sent to `events` as they end,
+    /// until [`stop_hands_free`](Self::stop_hands_free) closes the channel.
+    pub fn start_hands_free(
+        &self,
+        events: mpsc::Sender<UtteranceEvent>,
+    ) -> Result<(), anyhow::Error> {
+        let mut state = self.state.lock().unwrap();
+        if !matches!(*state, RecordingState::Idle) {
+            return Err(anyhow::anyhow!("A recording is already in progress"));
+        }
+
+        self.start_microphone_stream()?;
+        self.listen(events.clone())?;
+
+        *self.hands_free_events.lock().unwrap() = Some(events);
+        *state = RecordingState::Listening;
+        info!("Hands-free dictation started");
+        Ok(())
+    }
+
+    fn listen(&self, events: mpsc::Sender<UtteranceEvent>) -> Result<(), anyhow::Error> {
+        let settings = get_settings(&self.app_handle);
+        let config = UtteranceConfig {
+            trailing_silence_ms: settings.hands_free_silence_ms,
+            max_utterance_ms: settings.hands_free_max_utterance_ms,
+            min_speech_ms: settings.hands_free_min_speech_ms,
+        };
+
+        let recorder = self.recorder.lock().unwrap();
+        let rec = recorder
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Recorder not available"))?;
+        rec.listen(config, events)
+            .map_err(|e| anyhow::anyhow!("Failed to start listening: {}", e))
+    }
+
+    pub fn stop_hands_free(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !matches!(*state, RecordingState::Listening) {
+            return;
+        }
+        *state = RecordingState::Idle;
+        drop(state);
+
+        // The channel closes once the recorder lets go of its sender too
+        self.hands_free_events.lock().unwrap().take();
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            let _ = rec.stop_listening();
+        }
+
+        // In on-demand mode turn the mic off again
+        if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
+            self.stop_microphone_stream();
+        }
+        info!("Hands-free dictation stopped");
+    }
+
+    pub fn is_hands_free(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), RecordingState::Listening)
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
         if *self.is_open.lock().unwrap() {
             self.stop_microphone_stream();
             self.start_microphone_stream()?;
+
+            // Keep listening hands-free on the new device
+            let events = self.hands_free_events.lock().unwrap().clone();
+            if let Some(events) = events {
+                self.listen(events)?;
+            }
         }
         Ok(())
     }
@@ -461,6 +739,64 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Change how much audio from before the start of a recording is kept.
+    pub fn set_pre_roll_ms(&self, ms: u32) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.set_pre_roll_ms(ms);
+        }
+    }
+
+    /// Apply the VAD threshold and smoothing settings.
+    pub fn set_vad_params(&self, params: VadParams) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.set_vad_params(params);
+        }
+    }
+
+    /// Apply the noise suppression, high-pass and gain settings.
+    pub fn set_dsp_config(&self, config: DspConfig) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.set_dsp_config(config);
+        }
+    }
+
+    /// Install or remove the wake word spotter. It only hears the always-on
+    /// stream, and only while nothing is being recorded.
+    pub fn set_keyword_spotter(&self, spotter: Option<Box<dyn KeywordSpotter>>) {
+        *self.keyword_spotter.lock().unwrap() = spotter;
+    }
+
+    /// Notify `done` when the recording for `binding_id` goes quiet, so a
+    /// recording started by voice can also end without a hotkey.
+    pub fn stop_on_silence(
+        &self,
+        binding_id: &str,
+        silence_ms: u32,
+        done: mpsc::Sender<()>,
+    ) -> Result<(), anyhow::Error> {
+        let state = self.state.lock().unwrap();
+        if !matches!(&*state, RecordingState::Recording { binding_id: active } if active == binding_id)
+        {
+            return Err(anyhow::anyhow!("Not recording for {}", binding_id));
+        }
+
+        let recorder = self.recorder.lock().unwrap();
+        let rec = recorder
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Recorder not available"))?;
+        rec.stop_on_silence(silence_ms, done)
+            .map_err(|e| anyhow::anyhow!("Failed to watch for silence: {}", e))
+    }
+
+    /// Speech the VAD detected in the last stopped recording, in milliseconds.
+    pub fn last_speech_ms(&self) -> Option<i64> {
+        self.recorder
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|rec| rec.last_speech_ms())
+    }
+
     /// Cancel any ongoing recording without returning audio samples
     pub fn cancel_recording(&self) {
         let mut state = self.state.lock().unwrap();
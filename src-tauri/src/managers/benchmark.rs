@@ -0,0 +1,327 @@
+//! Measured speed and accuracy of the downloaded models on this machine.
+//!
+//! Each model is loaded in turn and run on a reference recording whose
+//! transcript is known, either one the user picks or the clip bundled with
+//! the app. Load time, real-time factor and word error rate are
+//! kept in the history database, one row per model, so the model picker can
+//! show them next to the catalog's estimates.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use log::{info, warn};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::database;
+use super::model::ModelManager;
+use super::transcription::TranscriptionManager;
+use crate::audio_toolkit::audio::decode_audio_file;
+
+/// Reference recording bundled with the app, with its transcript. Fetched
+/// into the resources by `scripts/fetch-benchmark-clip.ts`.
+const BUNDLED_REFERENCE: &str = "resources/benchmark/jfk.wav";
+const BUNDLED_TRANSCRIPT: &str = include_str!("../../resources/benchmark/jfk.txt");
+
+/// Latest benchmark result of one model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    pub timestamp: i64,
+    /// File name of the reference recording.
+    pub reference_name: String,
+    pub audio_ms: i64,
+    pub load_ms: i64,
+    pub transcribe_ms: i64,
+    /// Transcription time over audio duration; below 1 is faster than real time.
+    pub real_time_factor: f64,
+    /// Word-level edit distance to the reference transcript, over its word count.
+    pub word_error_rate: f64,
+    /// What the model heard, for checking the error rate by eye.
+    pub transcript: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BenchmarkProgress {
+    pub model_id: String,
+    /// Position of the model in this run, starting at 0.
+    pub index: usize,
+    pub total: usize,
+    /// "running", "completed" or "failed".
+    pub status: String,
+    pub error: Option<String>,
+}
+
+pub struct BenchmarkManager {
+    app_handle: AppHandle,
+    db_path: PathBuf,
+    model_manager: Arc<ModelManager>,
+    transcription_manager: Arc<TranscriptionManager>,
+    running: AtomicBool,
+}
+
+impl BenchmarkManager {
+    pub fn new(
+        app_handle: &AppHandle,
+        model_manager: Arc<ModelManager>,
+        transcription_manager: Arc<TranscriptionManager>,
+    ) -> Result<Self> {
+        let db_path = app_handle.path().app_data_dir()?.join("history.db");
+        database::initialize_database(&db_path)
+            .context("Failed to initialize database for benchmarks")?;
+
+        Ok(Self {
+            app_handle: app_handle.clone(),
+            db_path,
+            model_manager,
+            transcription_manager,
+            running: AtomicBool::new(false),
+        })
+    }
+
+    fn get_connection(&self) -> Result<Connection> {
+        Connection::open(&self.db_path)
+            .with_context(|| format!("Failed to open database at {:?}", self.db_path))
+    }
+
+    /// Benchmark the given models, or every downloaded transcription model,
+    /// against a reference recording and its transcript.
+    ///
+    /// Models that fail are reported through `model-benchmark-progress` and
+    /// skipped; the results of the others are stored and returned.
+    pub fn run(
+        &self,
+        audio_path: &Path,
+        reference_text: &str,
+        language: Option<&str>,
+        model_ids: Option<Vec<String>>,
+    ) -> Result<Vec<ModelBenchmark>> {
+        if normalized_words(reference_text).is_empty() {
+            return Err(anyhow::anyhow!("The reference transcript is empty"));
+        }
+        if self.running.swap(true, Ordering::SeqCst) {
+            return Err(anyhow::anyhow!("A benchmark is already running"));
+        }
+        let was_loaded = self.transcription_manager.is_model_loaded();
+        let result = self.run_models(audio_path, reference_text, language, model_ids);
+
+        // Benchmarks swap models in the shared engine, so put back what was there
+        if was_loaded {
+            self.transcription_manager.restore_selected_model();
+        } else if let Err(e) = self.transcription_manager.unload_model() {
+            warn!("Failed to unload the last benchmarked model: {}", e);
+        }
+        self.running.store(false, Ordering::SeqCst);
+        result
+    }
+
+    /// Like [`run`](Self::run), on the reference recording bundled with the app.
+    pub fn run_bundled(&self, model_ids: Option<Vec<String>>) -> Result<Vec<ModelBenchmark>> {
+        let audio_path = self
+            .app_handle
+            .path()
+            .resolve(BUNDLED_REFERENCE, tauri::path::BaseDirectory::Resource)
+            .context("Failed to resolve the bundled reference recording")?;
+        if !audio_path.exists() {
+            return Err(anyhow::anyhow!(
+                "The bundled reference recording is missing from this build"
+            ));
+        }
+        self.run(&audio_path, BUNDLED_TRANSCRIPT, Some("en"), model_ids)
+    }
+
+    fn run_models(
+        &self,
+        audio_path: &Path,
+        reference_text: &str,
+        language: Option<&str>,
+        model_ids: Option<Vec<String>>,
+    ) -> Result<Vec<ModelBenchmark>> {
+        let audio = decode_audio_file(audio_path)
+            .with_context(|| format!("Failed to decode {:?}", audio_path))?;
+        if audio.is_empty() {
+            return Err(anyhow::anyhow!("The reference recording is empty"));
+        }
+        let audio_ms = (audio.len() / 16).max(1) as i64;
+        let reference_name = audio_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let model_ids = model_ids.unwrap_or_else(|| {
+            self.model_manager
+                .get_transcription_models()
+                .into_iter()
+                .filter(|m| m.is_downloaded)
+                .map(|m| m.id)
+                .collect()
+        });
+
+        let mut results = Vec::new();
+        for (index, model_id) in model_ids.iter().enumerate() {
+            let mut progress = BenchmarkProgress {
+                model_id: model_id.clone(),
+                index,
+                total: model_ids.len(),
+                status: "running".to_string(),
+                error: None,
+            };
+            let _ = self.app_handle.emit("model-benchmark-progress", &progress);
+
+            let run = self
+                .transcription_manager
+                .benchmark_model(model_id, audio.clone(), language);
+            let benchmark = match run {
+                Ok(run) => ModelBenchmark {
+                    model_id: model_id.clone(),
+                    timestamp: Utc::now().timestamp(),
+                    reference_name: reference_name.clone(),
+                    audio_ms,
+                    load_ms: run.load_ms,
+                    transcribe_ms: run.transcribe_ms,
+                    real_time_factor: run.transcribe_ms as f64 / audio_ms as f64,
+                    word_error_rate: word_error_rate(reference_text, &run.transcript.text),
+                    transcript: run.transcript.text,
+                },
+                Err(e) => {
+                    warn!("Benchmark of {} failed: {}", model_id, e);
+                    progress.status = "failed".to_string();
+                    progress.error = Some(e.to_string());
+                    let _ = self.app_handle.emit("model-benchmark-progress", &progress);
+                    continue;
+                }
+            };
+
+            info!(
+                "Benchmarked {}: loaded in {}ms, RTF {:.3}, WER {:.1}%",
+                model_id,
+                benchmark.load_ms,
+                benchmark.real_time_factor,
+                benchmark.word_error_rate * 100.0
+            );
+            self.save(&benchmark)?;
+            progress.status = "completed".to_string();
+            let _ = self.app_handle.emit("model-benchmark-progress", &progress);
+            results.push(benchmark);
+        }
+
+        Ok(results)
+    }
+
+    fn save(&self, benchmark: &ModelBenchmark) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT OR REPLACE INTO model_benchmarks (model_id, timestamp, reference_name, audio_ms, load_ms, transcribe_ms, real_time_factor, word_error_rate, transcript) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                benchmark.model_id,
+                benchmark.timestamp,
+                benchmark.reference_name,
+                benchmark.audio_ms,
+                benchmark.load_ms,
+                benchmark.transcribe_ms,
+                benchmark.real_time_factor,
+                benchmark.word_error_rate,
+                benchmark.transcript,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The latest stored result of every benchmarked model.
+    pub fn get_results(&self) -> Result<Vec<ModelBenchmark>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT model_id, timestamp, reference_name, audio_ms, load_ms, transcribe_ms, real_time_factor, word_error_rate, transcript FROM model_benchmarks ORDER BY model_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ModelBenchmark {
+                model_id: row.get("model_id")?,
+                timestamp: row.get("timestamp")?,
+                reference_name: row.get("reference_name")?,
+                audio_ms: row.get("audio_ms")?,
+                load_ms: row.get("load_ms")?,
+                transcribe_ms: row.get("transcribe_ms")?,
+                real_time_factor: row.get("real_time_factor")?,
+                word_error_rate: row.get("word_error_rate")?,
+                transcript: row.get("transcript")?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+}
+
+/// Lowercase words with punctuation removed; apostrophes inside words are kept.
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Word error rate of `hypothesis` against `reference`: substitutions,
+/// deletions and insertions over the number of reference words.
+fn word_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let reference = normalized_words(reference);
+    let hypothesis = normalized_words(hypothesis);
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    // Levenshtein distance over words, one row at a time
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (i, ref_word) in reference.iter().enumerate() {
+        let mut current = vec![i + 1; hypothesis.len() + 1];
+        for (j, hyp_word) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ref_word != hyp_word);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[hypothesis.len()] as f64 / reference.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_error_rate_ignores_case_and_punctuation() {
+        assert_eq!(
+            word_error_rate("Hello there, General Kenobi.", "hello there general kenobi"),
+            0.0
+        );
+        assert_eq!(word_error_rate("It's fine.", "it's fine"), 0.0);
+    }
+
+    #[test]
+    fn word_error_rate_counts_every_kind_of_error() {
+        // One substitution out of four words
+        assert_eq!(
+            word_error_rate("the cat sat down", "the bat sat down"),
+            0.25
+        );
+        // One deletion and one insertion
+        assert_eq!(word_error_rate("the cat sat down", "cat sat down now"), 0.5);
+        // Nothing recognised
+        assert_eq!(word_error_rate("the cat sat down", ""), 1.0);
+        // Insertions can push the rate above 1
+        assert_eq!(word_error_rate("yes", "yes yes yes"), 2.0);
+    }
+
+    #[test]
+    fn word_error_rate_with_empty_reference() {
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "hello"), 1.0);
+    }
+}
@@ -0,0 +1,250 @@
+//! The model catalog: which models can be downloaded and from where.
+//!
+//! The catalog is a JSON manifest. A default one is compiled into the app; the
+//! `model_catalog_source` setting can point at a replacement on disk, behind a
+//! `file://` URL or on an HTTP server. Downloads try the `model_mirrors`
+//! setting first, then the URLs the manifest lists.
+
+use anyhow::{Context, Result};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::model::{EngineType, ModelInfo};
+
+const DEFAULT_MANIFEST: &str = include_str!("../../resources/model_catalog.json");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogManifest {
+    #[serde(default)]
+    pub version: u32,
+    pub models: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogEngine {
+    Whisper,
+    Parakeet,
+    Diarization,
+    Kws,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub engine: CatalogEngine,
+    /// File or directory name inside the models directory.
+    pub filename: String,
+    /// Where to download the model, tried in order.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    pub size_mb: u64,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Downloaded as an archive and extracted into a directory.
+    #[serde(default)]
+    pub is_directory: bool,
+    #[serde(default)]
+    pub accuracy_score: f32,
+    #[serde(default)]
+    pub speed_score: f32,
+}
+
+impl CatalogEntry {
+    pub fn into_model_info(self) -> ModelInfo {
+        let (engine_type, backend) = match self.engine {
+            CatalogEngine::Whisper => (EngineType::Whisper, Some("whisper")),
+            CatalogEngine::Parakeet => (EngineType::Parakeet, Some("parakeet")),
+            CatalogEngine::Diarization => (EngineType::Diarization, None),
+            CatalogEngine::Kws => (EngineType::KeywordSpotting, None),
+        };
+
+        ModelInfo {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            filename: self.filename,
+            urls: self.urls,
+            sha256: self.sha256,
+            size_mb: self.size_mb,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: self.is_directory,
+            is_custom: false,
+            engine_type,
+            backend: backend.map(str::to_string),
+            accuracy_score: self.accuracy_score,
+            speed_score: self.speed_score,
+        }
+    }
+}
+
+/// Parse and check a manifest.
+pub fn parse_manifest(json: &str) -> Result<Vec<CatalogEntry>> {
+    let manifest: CatalogManifest =
+        serde_json::from_str(json).context("Invalid model catalog manifest")?;
+
+    let mut ids = HashSet::new();
+    for entry in &manifest.models {
+        if entry.id.trim().is_empty() {
+            return Err(anyhow::anyhow!("Model catalog has an entry without an id"));
+        }
+        if !ids.insert(entry.id.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Model catalog lists {} more than once",
+                entry.id
+            ));
+        }
+        // The filename is joined onto the models directory, so it must stay inside it
+        let filename = Path::new(&entry.filename);
+        if entry.filename.is_empty()
+            || filename.components().count() != 1
+            || filename.file_name().is_none()
+        {
+            return Err(anyhow::anyhow!(
+                "Model {} has an invalid filename {:?}",
+                entry.id,
+                entry.filename
+            ));
+        }
+    }
+
+    Ok(manifest.models)
+}
+
+/// The catalog compiled into the app.
+pub fn default_entries() -> Vec<CatalogEntry> {
+    parse_manifest(DEFAULT_MANIFEST).expect("the built-in model catalog is valid")
+}
+
+/// Whether `source` is fetched over HTTP rather than read from disk.
+pub fn is_remote(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// The local path behind a `file://` URL, or `source` itself if it is a plain path.
+pub fn local_path(source: &str) -> Option<PathBuf> {
+    if source.starts_with("file://") {
+        Url::parse(source).ok()?.to_file_path().ok()
+    } else if is_remote(source) {
+        None
+    } else {
+        Some(PathBuf::from(source))
+    }
+}
+
+/// Read a manifest from a path or `file://` URL.
+pub fn read_manifest(source: &str) -> Result<Vec<CatalogEntry>> {
+    let path = local_path(source)
+        .ok_or_else(|| anyhow::anyhow!("Invalid model catalog location {}", source))?;
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read model catalog {:?}", path))?;
+    parse_manifest(&json)
+}
+
+/// Read a manifest from disk, a `file://` URL or an HTTP server.
+pub async fn fetch_manifest(source: &str) -> Result<Vec<CatalogEntry>> {
+    if !is_remote(source) {
+        return read_manifest(source);
+    }
+
+    let response = reqwest::get(source).await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to fetch model catalog: HTTP {}",
+            response.status()
+        ));
+    }
+    parse_manifest(&response.text().await?)
+}
+
+/// Download URLs for a model: the same file on each mirror, in order, followed by
+/// the manifest's own URLs.
+pub fn candidate_urls(urls: &[String], mirrors: &[String]) -> Vec<String> {
+    let file_name = urls
+        .first()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|name| !name.is_empty());
+
+    let mut candidates: Vec<String> = Vec::new();
+    if let Some(file_name) = file_name {
+        for mirror in mirrors {
+            let mirror = mirror.trim().trim_end_matches('/');
+            if !mirror.is_empty() {
+                candidates.push(format!("{}/{}", mirror, file_name));
+            }
+        }
+    }
+    for url in urls {
+        if !candidates.contains(url) {
+            candidates.push(url.clone());
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_manifest_is_valid() {
+        let entries = default_entries();
+        assert!(entries.iter().any(|e| e.id == "small"));
+        assert!(entries.iter().all(|e| !e.urls.is_empty()));
+    }
+
+    #[test]
+    fn manifest_rejects_duplicates_and_unsafe_filenames() {
+        let entry = |id: &str, filename: &str| {
+            format!(
+                r#"{{"id": "{}", "name": "x", "engine": "whisper", "filename": "{}", "size_mb": 1}}"#,
+                id, filename
+            )
+        };
+        let manifest = |entries: &[String]| format!(r#"{{"models": [{}]}}"#, entries.join(","));
+
+        assert!(parse_manifest(&manifest(&[entry("a", "a.bin"), entry("b", "b.bin")])).is_ok());
+        assert!(parse_manifest(&manifest(&[entry("a", "a.bin"), entry("a", "b.bin")])).is_err());
+        assert!(parse_manifest(&manifest(&[entry("a", "../a.bin")])).is_err());
+        assert!(parse_manifest(&manifest(&[entry("a", "")])).is_err());
+    }
+
+    #[test]
+    fn mirrors_are_tried_before_manifest_urls() {
+        let urls = vec!["https://example.com/models/ggml-small.bin".to_string()];
+        let mirrors = vec![
+            "http://lan.local/models/".to_string(),
+            "file:///srv/models".to_string(),
+        ];
+        assert_eq!(
+            candidate_urls(&urls, &mirrors),
+            vec![
+                "http://lan.local/models/ggml-small.bin",
+                "file:///srv/models/ggml-small.bin",
+                "https://example.com/models/ggml-small.bin",
+            ]
+        );
+        assert_eq!(candidate_urls(&urls, &[]), urls);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn local_paths_from_file_urls() {
+        assert_eq!(
+            local_path("file:///srv/models/catalog.json"),
+            Some(PathBuf::from("/srv/models/catalog.json"))
+        );
+        assert_eq!(
+            local_path("/srv/catalog.json"),
+            Some(PathBuf::from("/srv/catalog.json"))
+        );
+        assert_eq!(local_path("https://example.com/catalog.json"), None);
+    }
+}
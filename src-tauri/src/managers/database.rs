@@ -17,7 +17,7 @@ use rusqlite::Connection;
 use std::path::Path;
 
 /// Current schema version. Increment this when adding new migrations.
-const CURRENT_SCHEMA_VERSION: u32 = 6;
+const CURRENT_SCHEMA_VERSION: u32 = 9;
 
 /// A database migration with version and SQL statement.
 struct Migration {
@@ -99,6 +99,33 @@ const MIGRATIONS: &[Migration] = &[
         CREATE INDEX idx_segments_meeting ON meeting_segments(meeting_id);
         CREATE INDEX idx_segments_time ON meeting_segments(start_ms)",
     },
+    Migration {
+        version: 7,
+        description: "add_transcript_timing_columns",
+        sql: "ALTER TABLE meeting_segments ADD COLUMN words_json TEXT;
+        ALTER TABLE transcription_history ADD COLUMN segments_json TEXT",
+    },
+    Migration {
+        version: 8,
+        description: "add_detected_language_columns",
+        sql: "ALTER TABLE transcription_history ADD COLUMN language TEXT;
+        ALTER TABLE transcription_history ADD COLUMN language_probability REAL",
+    },
+    Migration {
+        version: 9,
+        description: "create_model_benchmarks_table",
+        sql: "CREATE TABLE model_benchmarks (
+            model_id TEXT PRIMARY KEY,
+            timestamp INTEGER NOT NULL,
+            reference_name TEXT NOT NULL,
+            audio_ms INTEGER NOT NULL,
+            load_ms INTEGER NOT NULL,
+            transcribe_ms INTEGER NOT NULL,
+            real_time_factor REAL NOT NULL,
+            word_error_rate REAL NOT NULL,
+            transcript TEXT NOT NULL
+        )",
+    },
 ];
 
 /// Initialize the database at the given path, creating schema and running migrations.
@@ -253,8 +280,17 @@ fn detect_schema_version(conn: &Connection) -> Result<u32> {
         check_column_exists(conn, "transcription_history", "post_processed_text")?;
 
     let has_meetings = check_table_exists(conn, "meetings")?;
-
-    if has_meetings {
+    let has_segments_json = check_column_exists(conn, "transcription_history", "segments_json")?;
+    let has_language = check_column_exists(conn, "transcription_history", "language")?;
+    let has_model_benchmarks = check_table_exists(conn, "model_benchmarks")?;
+
+    if has_model_benchmarks {
+        Ok(9)
+    } else if has_language {
+        Ok(8)
+    } else if has_segments_json {
+        Ok(7)
+    } else if has_meetings {
         Ok(6)
     } else if has_input_entries && has_app_pid {
         Ok(5)
@@ -377,6 +413,13 @@ mod tests {
         assert!(
             check_column_exists(&conn, "transcription_history", "post_process_prompt").unwrap()
         );
+        assert!(check_column_exists(&conn, "transcription_history", "segments_json").unwrap());
+        assert!(check_column_exists(&conn, "meeting_segments", "words_json").unwrap());
+        assert!(check_column_exists(&conn, "transcription_history", "language").unwrap());
+        assert!(
+            check_column_exists(&conn, "transcription_history", "language_probability").unwrap()
+        );
+        assert!(check_table_exists(&conn, "model_benchmarks").unwrap());
     }
 
     #[test]
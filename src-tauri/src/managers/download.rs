@@ -0,0 +1,297 @@
+//! Scheduling for model downloads: a first-come queue limited to a number of
+//! concurrent downloads, a bandwidth cap shared by all of them, retry backoff
+//! and speed/ETA estimates for progress events.
+
+use anyhow::Result;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Automatic resumes of one download from one source before giving up on it.
+pub const MAX_FETCH_RETRIES: u32 = 5;
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How often the download speed estimate is updated.
+const SPEED_WINDOW: Duration = Duration::from_millis(500);
+
+/// The download was cancelled by the user.
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Download cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// The server answered with an error status.
+#[derive(Debug)]
+pub struct HttpStatus(pub reqwest::StatusCode);
+
+impl fmt::Display for HttpStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to download model: HTTP {}", self.0)
+    }
+}
+
+impl std::error::Error for HttpStatus {}
+
+/// Whether a failed fetch is worth resuming: dropped connections, timeouts
+/// and server errors are. Client errors, cancellation and local failures
+/// such as a full disk are not, since they would fail the same way again.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    if let Some(HttpStatus(status)) = error.downcast_ref::<HttpStatus>() {
+        return status.is_server_error()
+            || *status == reqwest::StatusCode::REQUEST_TIMEOUT
+            || *status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    }
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|e| e.is_connect() || e.is_timeout() || e.is_request() || e.is_body() || e.is_decode())
+}
+
+/// Delay before the given retry (starting at 1): doubling from one second, capped.
+pub fn backoff_delay(retry: u32) -> Duration {
+    let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+    INITIAL_RETRY_DELAY
+        .saturating_mul(factor)
+        .min(MAX_RETRY_DELAY)
+}
+
+#[derive(Default)]
+struct QueueState {
+    waiting: VecDeque<String>,
+    active: HashSet<String>,
+    cancelled: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct DownloadQueue {
+    state: Mutex<QueueState>,
+    /// When the bandwidth cap next allows data through.
+    next_free: Mutex<Option<Instant>>,
+}
+
+impl DownloadQueue {
+    /// Put a model in line for downloading.
+    pub fn enqueue(&self, model_id: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.active.contains(model_id) || state.waiting.iter().any(|id| id == model_id) {
+            return Err(anyhow::anyhow!("Model {} is already downloading", model_id));
+        }
+        state.cancelled.remove(model_id);
+        state.waiting.push_back(model_id.to_string());
+        Ok(())
+    }
+
+    /// Start the download of a queued model if it is next in line and fewer than
+    /// `limit` downloads are running. The slot is freed when the guard is dropped.
+    pub fn try_start(
+        self: &Arc<Self>,
+        model_id: &str,
+        limit: usize,
+    ) -> Result<Option<DownloadSlot>> {
+        let mut state = self.state.lock().unwrap();
+        let Some(position) = state.waiting.iter().position(|id| id == model_id) else {
+            return Err(Cancelled.into());
+        };
+        if position > 0 || state.active.len() >= limit.max(1) {
+            return Ok(None);
+        }
+
+        state.waiting.pop_front();
+        state.active.insert(model_id.to_string());
+        Ok(Some(DownloadSlot {
+            queue: Arc::clone(self),
+            model_id: model_id.to_string(),
+        }))
+    }
+
+    /// Stop a running download, or take a waiting one out of the queue.
+    pub fn cancel(&self, model_id: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.waiting.retain(|id| id != model_id);
+        if state.active.contains(model_id) {
+            state.cancelled.insert(model_id.to_string());
+        }
+    }
+
+    /// Whether the model is downloading or extracting right now.
+    pub fn is_active(&self, model_id: &str) -> bool {
+        self.state.lock().unwrap().active.contains(model_id)
+    }
+
+    /// Whether the model is waiting for a slot, downloading or extracting.
+    pub fn is_pending(&self, model_id: &str) -> bool {
+        let state = self.state.lock().unwrap();
+        state.active.contains(model_id) || state.waiting.iter().any(|id| id == model_id)
+    }
+
+    pub fn is_cancelled(&self, model_id: &str) -> bool {
+        self.state.lock().unwrap().cancelled.contains(model_id)
+    }
+
+    /// How long to pause after receiving `bytes` to stay under `limit` bytes per
+    /// second across all downloads.
+    pub fn throttle(&self, bytes: usize, limit: u64) -> Duration {
+        let mut next_free = self.next_free.lock().unwrap();
+        reserve_bandwidth(&mut next_free, Instant::now(), bytes, limit)
+    }
+}
+
+/// A running download; dropping it lets the next queued one start.
+pub struct DownloadSlot {
+    queue: Arc<DownloadQueue>,
+    model_id: String,
+}
+
+impl Drop for DownloadSlot {
+    fn drop(&mut self) {
+        let mut state = self.queue.state.lock().unwrap();
+        state.active.remove(&self.model_id);
+        state.cancelled.remove(&self.model_id);
+    }
+}
+
+/// Book `bytes` on a link limited to `limit` bytes per second and return how
+/// long to wait so the average stays under it.
+fn reserve_bandwidth(
+    next_free: &mut Option<Instant>,
+    now: Instant,
+    bytes: usize,
+    limit: u64,
+) -> Duration {
+    if limit == 0 {
+        return Duration::ZERO;
+    }
+    let start = next_free.map_or(now, |free| free.max(now));
+    let end = start + Duration::from_secs_f64(bytes as f64 / limit as f64);
+    *next_free = Some(end);
+    end.saturating_duration_since(now)
+}
+
+/// Smoothed download speed for progress events.
+pub struct SpeedEstimator {
+    window_start: Instant,
+    window_bytes: u64,
+    speed: f64,
+}
+
+impl SpeedEstimator {
+    pub fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_bytes: 0,
+            speed: 0.0,
+        }
+    }
+
+    /// Record `bytes` received and return the speed in bytes per second.
+    pub fn record(&mut self, bytes: u64) -> f64 {
+        self.window_bytes += bytes;
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= SPEED_WINDOW {
+            let current = self.window_bytes as f64 / elapsed.as_secs_f64();
+            self.speed = if self.speed > 0.0 {
+                self.speed * 0.7 + current * 0.3
+            } else {
+                current
+            };
+            self.window_start = Instant::now();
+            self.window_bytes = 0;
+        }
+        self.speed
+    }
+}
+
+impl Default for SpeedEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Seconds left at `speed` bytes per second, if it can be estimated.
+pub fn eta_seconds(downloaded: u64, total: u64, speed: f64) -> Option<u64> {
+    (total > downloaded && speed > 0.0).then(|| ((total - downloaded) as f64 / speed).ceil() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff_delay(1), Duration::from_secs(1));
+        assert_eq!(backoff_delay(2), Duration::from_secs(2));
+        assert_eq!(backoff_delay(4), Duration::from_secs(8));
+        assert_eq!(backoff_delay(10), MAX_RETRY_DELAY);
+        assert_eq!(backoff_delay(100), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn only_transient_failures_are_retried() {
+        let status = |code: u16| {
+            anyhow::Error::from(HttpStatus(reqwest::StatusCode::from_u16(code).unwrap()))
+        };
+        assert!(is_retryable(&status(503)));
+        assert!(is_retryable(&status(429)));
+        assert!(!is_retryable(&status(404)));
+        assert!(!is_retryable(&Cancelled.into()));
+        let disk_full = std::io::Error::new(std::io::ErrorKind::StorageFull, "disk full");
+        assert!(!is_retryable(&disk_full.into()));
+    }
+
+    #[test]
+    fn bandwidth_cap_spreads_chunks_over_time() {
+        let now = Instant::now();
+        let mut next_free = None;
+        // 1000 bytes at 1000 B/s take a second each, back to back
+        assert_eq!(
+            reserve_bandwidth(&mut next_free, now, 1000, 1000),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            reserve_bandwidth(&mut next_free, now, 1000, 1000),
+            Duration::from_secs(2)
+        );
+        // An idle link does not bank credit
+        let later = now + Duration::from_secs(10);
+        assert_eq!(
+            reserve_bandwidth(&mut next_free, later, 500, 1000),
+            Duration::from_millis(500)
+        );
+        assert_eq!(reserve_bandwidth(&mut None, now, 1000, 0), Duration::ZERO);
+    }
+
+    #[test]
+    fn queue_starts_downloads_in_order_up_to_the_limit() {
+        let queue = Arc::new(DownloadQueue::default());
+        queue.enqueue("a").unwrap();
+        queue.enqueue("b").unwrap();
+        queue.enqueue("c").unwrap();
+        assert!(queue.enqueue("a").is_err());
+
+        // "b" waits behind "a", and then for a free slot
+        assert!(queue.try_start("b", 1).unwrap().is_none());
+        let a = queue.try_start("a", 1).unwrap().unwrap();
+        assert!(queue.try_start("b", 1).unwrap().is_none());
+        drop(a);
+        let _b = queue.try_start("b", 1).unwrap().unwrap();
+
+        // Cancelling a waiting download takes it out of the queue
+        queue.cancel("c");
+        assert!(queue.try_start("c", 2).is_err());
+    }
+
+    #[test]
+    fn eta_needs_a_speed_and_a_known_total() {
+        assert_eq!(eta_seconds(500, 1500, 100.0), Some(10));
+        assert_eq!(eta_seconds(500, 0, 100.0), None);
+        assert_eq!(eta_seconds(500, 1500, 0.0), None);
+    }
+}
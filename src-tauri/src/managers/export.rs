@@ -112,6 +112,7 @@ mod tests {
                 text: "Good morning everyone.".to_string(),
                 confidence: Some(0.95),
                 audio_source: "mic".to_string(),
+                words: Vec::new(),
             },
             MeetingSegment {
                 id: 2,
@@ -122,6 +123,7 @@ mod tests {
                 text: "Morning! Let's get started.".to_string(),
                 confidence: None,
                 audio_source: "system".to_string(),
+                words: Vec::new(),
             },
         ]
     }
@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 
 use super::database;
+use super::transcription::{Transcript, TranscriptSegment};
 use crate::audio_toolkit::{load_wav_file, save_wav_file};
 use crate::settings::RecordingRetentionPeriod;
 
@@ -21,6 +22,14 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// Timed segments of the raw transcription, empty for entries recorded before timings were stored.
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    /// Spoken language as an ISO 639-1 code, when the engine reported one.
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub language_probability: Option<f64>,
 }
 
 pub struct HistoryManager {
@@ -63,7 +72,7 @@ impl HistoryManager {
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
-        transcription_text: String,
+        transcript: Transcript,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
     ) -> Result<()> {
@@ -80,7 +89,7 @@ impl HistoryManager {
             file_name,
             timestamp,
             title,
-            transcription_text,
+            transcript,
             post_processed_text,
             post_process_prompt,
         )?;
@@ -101,14 +110,15 @@ impl HistoryManager {
         file_name: String,
         timestamp: i64,
         title: String,
-        transcription_text: String,
+        transcript: Transcript,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
     ) -> Result<()> {
         let conn = self.get_connection()?;
+        let segments_json = segments_to_json(&transcript.segments)?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, segments_json, language, language_probability) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![file_name, timestamp, false, title, transcript.text, post_processed_text, post_process_prompt, segments_json, transcript.language, transcript.language_probability.map(f64::from)],
         )?;
 
         debug!("Saved transcription to database");
@@ -223,7 +233,7 @@ impl HistoryManager {
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, segments_json, language, language_probability FROM transcription_history ORDER BY timestamp DESC"
         )?;
 
         let rows = stmt.query_map([], |row| {
@@ -236,6 +246,9 @@ impl HistoryManager {
                 transcription_text: row.get("transcription_text")?,
                 post_processed_text: row.get("post_processed_text")?,
                 post_process_prompt: row.get("post_process_prompt")?,
+                segments: segments_from_json(row.get("segments_json")?),
+                language: row.get("language")?,
+                language_probability: row.get("language_probability")?,
             })
         })?;
 
@@ -281,7 +294,7 @@ impl HistoryManager {
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
+            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, segments_json, language, language_probability
              FROM transcription_history WHERE id = ?1",
         )?;
 
@@ -296,6 +309,9 @@ impl HistoryManager {
                     transcription_text: row.get("transcription_text")?,
                     post_processed_text: row.get("post_processed_text")?,
                     post_process_prompt: row.get("post_process_prompt")?,
+                    segments: segments_from_json(row.get("segments_json")?),
+                    language: row.get("language")?,
+                    language_probability: row.get("language_probability")?,
                 })
             })
             .optional()?;
@@ -345,13 +361,20 @@ impl HistoryManager {
     }
 
     /// Retranscribe a history entry using its stored audio file
-    pub async fn retranscribe_entry(&self, id: i64, new_transcription: String) -> Result<()> {
+    pub async fn retranscribe_entry(&self, id: i64, new_transcription: Transcript) -> Result<()> {
         let conn = self.get_connection()?;
+        let segments_json = segments_to_json(&new_transcription.segments)?;
 
         // Update the transcription text in the database
         conn.execute(
-            "UPDATE transcription_history SET transcription_text = ?1, post_processed_text = NULL, post_process_prompt = NULL WHERE id = ?2",
-            params![new_transcription, id],
+            "UPDATE transcription_history SET transcription_text = ?1, segments_json = ?2, language = ?3, language_probability = ?4, post_processed_text = NULL, post_process_prompt = NULL WHERE id = ?5",
+            params![
+                new_transcription.text,
+                segments_json,
+                new_transcription.language,
+                new_transcription.language_probability.map(f64::from),
+                id
+            ],
         )?;
 
         debug!("Retranscribed history entry with id: {}", id);
@@ -397,3 +420,15 @@ impl HistoryManager {
         Ok(())
     }
 }
+
+fn segments_to_json(segments: &[TranscriptSegment]) -> Result<Option<String>> {
+    if segments.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(segments)?))
+}
+
+fn segments_from_json(json: Option<String>) -> Vec<TranscriptSegment> {
+    json.and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
@@ -14,12 +14,14 @@ use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
 
+use super::audio;
 use super::database;
 use super::diarization::DiarizationManager;
-use super::transcription::TranscriptionManager;
+use super::transcription::{
+    mean_probability, JobKind, Transcript, TranscriptSegment, TranscriptWord, TranscriptionManager,
+};
 use crate::audio_toolkit::audio::save_wav_file;
-use crate::audio_toolkit::{list_input_devices, AudioRecorder};
-use crate::helpers::clamshell;
+use crate::audio_toolkit::AudioRecorder;
 use crate::settings;
 
 // ── Types ──────────────────────────────────────────────────────────────────
@@ -93,6 +95,9 @@ pub struct MeetingSegment {
     pub text: String,
     pub confidence: Option<f64>,
     pub audio_source: String,
+    /// Word-level timings (absolute, in meeting milliseconds) when the engine provides them.
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -166,29 +171,9 @@ impl MeetingManager {
     /// Resolve the microphone device from settings (respects clamshell mode).
     fn get_effective_mic_device(&self) -> Option<cpal::Device> {
         let app_settings = settings::get_settings(&self.app_handle);
-
-        let should_use_clamshell = if app_settings.clamshell_microphone.is_some() {
-            clamshell::is_clamshell().unwrap_or(false)
-        } else {
-            false
-        };
-
-        let device_name = if should_use_clamshell {
-            app_settings.clamshell_microphone.as_deref()
-        } else {
-            app_settings.selected_microphone.as_deref()
-        }?;
-
-        match list_input_devices() {
-            Ok(devices) => devices
-                .into_iter()
-                .find(|d| d.name == device_name)
-                .map(|d| d.device),
-            Err(e) => {
-                debug!("Failed to list devices, using default: {}", e);
-                None
-            }
-        }
+        audio::preferred_input_devices(&app_settings)
+            .into_iter()
+            .next()
     }
 
     /// Start a new meeting recording.
@@ -198,13 +183,22 @@ impl MeetingManager {
             anyhow::bail!("A meeting is already in progress");
         }
 
-        // Open mic recorder (no VAD — meetings should capture all audio)
+        // Open mic recorder (no VAD — meetings should capture all audio).
+        // If the mic drops out mid-meeting it carries on with the next one.
         let mut recorder = AudioRecorder::new()
-            .map_err(|e| anyhow::anyhow!("Failed to create meeting audio recorder: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("Failed to create meeting audio recorder: {}", e))?
+            .with_device_chooser({
+                let app_handle = self.app_handle.clone();
+                move || audio::preferred_input_devices(&settings::get_settings(&app_handle))
+            })
+            .with_device_callback({
+                let app_handle = self.app_handle.clone();
+                move |name| audio::emit_microphone_changed(&app_handle, name)
+            });
 
         let selected_device = self.get_effective_mic_device();
         recorder
-            .open(selected_device)
+            .open(audio::input_source(selected_device))
             .map_err(|e| anyhow::anyhow!("Failed to open microphone for meeting: {}", e))?;
 
         recorder
@@ -382,30 +376,53 @@ impl MeetingManager {
         // Ensure the transcription model is loaded before we start chunking
         transcription_manager.initiate_model_load();
 
+        let overlap_ms = (overlap_size as i64 * 1000) / 16_000;
         let mut offset_ms: i64 = 0;
         let mut pos = 0;
+        // Segments starting before this point were already committed by the previous chunk
+        let mut committed_until_ms: i64 = 0;
 
         while pos < samples.len() {
             let end = (pos + chunk_size).min(samples.len());
             let chunk = &samples[pos..end];
+            let chunk_end_ms = offset_ms + ((end - pos) as i64 * 1000) / 16_000;
 
-            match transcription_manager.transcribe(chunk.to_vec()) {
-                Ok(text) if !text.trim().is_empty() => {
-                    let chunk_duration_ms = ((end - pos) as i64 * 1000) / 16_000;
-                    let segment = MeetingSegment {
-                        id: 0,
-                        meeting_id,
-                        speaker_label: speaker_label.to_string(),
-                        start_ms: offset_ms,
-                        end_ms: offset_ms + chunk_duration_ms,
-                        text: text.trim().to_string(),
-                        confidence: None,
-                        audio_source: source.as_str().to_string(),
-                    };
-                    if let Err(e) = self.insert_segment(&segment) {
-                        error!("Failed to insert meeting segment: {}", e);
+            // Split the overlap in half: this chunk owns segments starting before the
+            // midpoint, the next chunk owns the rest.
+            let window_end_ms = if end < samples.len() {
+                chunk_end_ms - overlap_ms / 2
+            } else {
+                i64::MAX
+            };
+
+            match transcription_manager.transcribe(chunk.to_vec(), JobKind::Meeting) {
+                Ok(transcript) if !transcript.is_empty() => {
+                    let transcript = transcript.offset_by(offset_ms);
+                    let segments = if transcript.segments.is_empty() {
+                        // Engine gave no timings; fall back to the chunk's own span
+                        vec![TranscriptSegment {
+                            text: transcript.text,
+                            start_ms: committed_until_ms,
+                            end_ms: chunk_end_ms,
+                            probability: None,
+                            words: Vec::new(),
+                        }]
                     } else {
-                        let _ = self.app_handle.emit("meeting-segment-added", &segment);
+                        segments_in_window(transcript.segments, committed_until_ms, window_end_ms)
+                    };
+
+                    for transcript_segment in segments {
+                        let segment = meeting_segment_from_transcript(
+                            meeting_id,
+                            speaker_label,
+                            &source,
+                            transcript_segment,
+                        );
+                        if let Err(e) = self.insert_segment(&segment) {
+                            error!("Failed to insert meeting segment: {}", e);
+                        } else {
+                            let _ = self.app_handle.emit("meeting-segment-added", &segment);
+                        }
                     }
                 }
                 Ok(_) => {
@@ -415,6 +432,7 @@ impl MeetingManager {
                     error!("Failed to transcribe meeting chunk at {}ms: {}", offset_ms, e);
                 }
             }
+            committed_until_ms = window_end_ms;
 
             let step = if end < samples.len() {
                 chunk_size.saturating_sub(overlap_size)
@@ -487,18 +505,17 @@ impl MeetingManager {
 
             let chunk = &samples[start_sample..end_sample];
 
-            match transcription_manager.transcribe(chunk.to_vec()) {
-                Ok(text) if !text.trim().is_empty() => {
-                    let segment = MeetingSegment {
-                        id: 0,
+            match transcription_manager.transcribe(chunk.to_vec(), JobKind::Meeting) {
+                Ok(transcript) if !transcript.is_empty() => {
+                    let mut segment = meeting_segment_from_transcript(
                         meeting_id,
-                        speaker_label: format!("Speaker {}", seg.speaker_id),
-                        start_ms: seg.start_ms,
-                        end_ms: seg.end_ms,
-                        text: text.trim().to_string(),
-                        confidence: None,
-                        audio_source: source.as_str().to_string(),
-                    };
+                        &format!("Speaker {}", seg.speaker_id),
+                        &source,
+                        merge_transcript(transcript.offset_by(seg.start_ms)),
+                    );
+                    // Keep the diarizer's boundaries for the speaker turn
+                    segment.start_ms = seg.start_ms;
+                    segment.end_ms = seg.end_ms;
                     if let Err(e) = self.insert_segment(&segment) {
                         error!("Failed to insert diarized segment: {}", e);
                     } else {
@@ -529,8 +546,13 @@ impl MeetingManager {
 
     fn insert_segment(&self, segment: &MeetingSegment) -> Result<()> {
         let conn = self.get_connection()?;
+        let words_json = if segment.words.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&segment.words)?)
+        };
         conn.execute(
-            "INSERT INTO meeting_segments (meeting_id, speaker_label, start_ms, end_ms, text, confidence, audio_source) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO meeting_segments (meeting_id, speaker_label, start_ms, end_ms, text, confidence, audio_source, words_json) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 segment.meeting_id,
                 segment.speaker_label,
@@ -539,6 +561,7 @@ impl MeetingManager {
                 segment.text,
                 segment.confidence,
                 segment.audio_source,
+                words_json,
             ],
         )?;
         Ok(())
@@ -584,10 +607,11 @@ impl MeetingManager {
     pub fn get_meeting_segments(&self, meeting_id: i64) -> Result<Vec<MeetingSegment>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, meeting_id, speaker_label, start_ms, end_ms, text, confidence, audio_source FROM meeting_segments WHERE meeting_id = ?1 ORDER BY start_ms ASC",
+            "SELECT id, meeting_id, speaker_label, start_ms, end_ms, text, confidence, audio_source, words_json FROM meeting_segments WHERE meeting_id = ?1 ORDER BY start_ms ASC",
         )?;
         let segments = stmt
             .query_map(params![meeting_id], |row| {
+                let words_json: Option<String> = row.get(8)?;
                 Ok(MeetingSegment {
                     id: row.get(0)?,
                     meeting_id: row.get(1)?,
@@ -597,6 +621,9 @@ impl MeetingManager {
                     text: row.get(5)?,
                     confidence: row.get(6)?,
                     audio_source: row.get(7)?,
+                    words: words_json
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
                 })
             })?
             .collect::<Result<Vec<_>, _>>()
@@ -876,6 +903,59 @@ impl MeetingManager {
 
 // ── Helpers ────────────────────────────────────────────────────────────────
 
+/// Keep the transcript segments that start inside `[from_ms, until_ms)`.
+///
+/// Used to drop the duplicates produced by overlapping transcription chunks.
+fn segments_in_window(
+    segments: Vec<TranscriptSegment>,
+    from_ms: i64,
+    until_ms: i64,
+) -> Vec<TranscriptSegment> {
+    segments
+        .into_iter()
+        .filter(|s| s.start_ms >= from_ms && s.start_ms < until_ms)
+        .filter(|s| !s.text.trim().is_empty())
+        .collect()
+}
+
+/// Collapse a whole transcript into one segment spanning all of its timed parts.
+fn merge_transcript(transcript: Transcript) -> TranscriptSegment {
+    let start_ms = transcript.segments.first().map(|s| s.start_ms).unwrap_or(0);
+    let end_ms = transcript.segments.last().map(|s| s.end_ms).unwrap_or(0);
+    let probability = mean_probability(transcript.segments.iter().filter_map(|s| s.probability));
+
+    TranscriptSegment {
+        text: transcript.text,
+        start_ms,
+        end_ms,
+        probability,
+        words: transcript
+            .segments
+            .into_iter()
+            .flat_map(|s| s.words)
+            .collect(),
+    }
+}
+
+fn meeting_segment_from_transcript(
+    meeting_id: i64,
+    speaker_label: &str,
+    source: &AudioSource,
+    segment: TranscriptSegment,
+) -> MeetingSegment {
+    MeetingSegment {
+        id: 0,
+        meeting_id,
+        speaker_label: speaker_label.to_string(),
+        start_ms: segment.start_ms,
+        end_ms: segment.end_ms,
+        text: segment.text.trim().to_string(),
+        confidence: segment.probability.map(f64::from),
+        audio_source: source.as_str().to_string(),
+        words: segment.words,
+    }
+}
+
 pub fn format_ms_to_hms(ms: i64) -> String {
     let total_secs = ms / 1000;
     let h = total_secs / 3600;
@@ -1318,6 +1398,12 @@ mod tests {
             text: "Hello world".to_string(),
             confidence: Some(0.95),
             audio_source: "mic".to_string(),
+            words: vec![TranscriptWord {
+                text: "Hello".to_string(),
+                start_ms: 0,
+                end_ms: 400,
+                probability: Some(0.9),
+            }],
         };
 
         let json = serde_json::to_string(&seg).unwrap();
@@ -1326,5 +1412,74 @@ mod tests {
         assert_eq!(deserialized.speaker_label, "Alice");
         assert_eq!(deserialized.text, "Hello world");
         assert_eq!(deserialized.confidence, Some(0.95));
+        assert_eq!(deserialized.words.len(), 1);
+        assert_eq!(deserialized.words[0].end_ms, 400);
+    }
+
+    #[test]
+    fn meeting_segment_deserializes_without_words() {
+        let json = r#"{"id":1,"meeting_id":1,"speaker_label":"A","start_ms":0,"end_ms":1,"text":"x","confidence":null,"audio_source":"mic"}"#;
+        let seg: MeetingSegment = serde_json::from_str(json).unwrap();
+        assert!(seg.words.is_empty());
+    }
+
+    fn transcript_segment(text: &str, start_ms: i64, end_ms: i64) -> TranscriptSegment {
+        TranscriptSegment {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            probability: None,
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn overlapping_chunks_commit_each_segment_once() {
+        // 30s chunks with 5s overlap: chunk 1 covers 0-30s, chunk 2 covers 25-55s.
+        // The cutoff between them is the overlap midpoint (27.5s).
+        let chunk1 = vec![
+            transcript_segment("one", 0, 10_000),
+            transcript_segment("two", 26_000, 29_000),
+            transcript_segment("three", 28_000, 30_000),
+        ];
+        let chunk2 = vec![
+            transcript_segment("two", 26_100, 29_000),
+            transcript_segment("three", 28_000, 30_000),
+            transcript_segment("four", 40_000, 45_000),
+        ];
+
+        let first = segments_in_window(chunk1, 0, 27_500);
+        let second = segments_in_window(chunk2, 27_500, i64::MAX);
+
+        let texts: Vec<_> = first
+            .iter()
+            .chain(second.iter())
+            .map(|s| s.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["one", "two", "three", "four"]);
+    }
+
+    #[test]
+    fn merge_transcript_spans_all_segments() {
+        let mut a = transcript_segment("Hello.", 1_000, 2_000);
+        a.probability = Some(0.8);
+        a.words.push(TranscriptWord {
+            text: "Hello.".to_string(),
+            start_ms: 1_000,
+            end_ms: 2_000,
+            probability: Some(0.8),
+        });
+        let mut b = transcript_segment("Bye.", 3_000, 4_000);
+        b.probability = Some(0.6);
+
+        let merged = merge_transcript(Transcript {
+            text: "Hello. Bye.".to_string(),
+            segments: vec![a, b],
+            ..Transcript::default()
+        });
+
+        assert_eq!((merged.start_ms, merged.end_ms), (1_000, 4_000));
+        assert_eq!(merged.words.len(), 1);
+        assert!((merged.probability.unwrap() - 0.7).abs() < 1e-6);
     }
 }
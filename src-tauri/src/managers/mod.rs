@@ -1,6 +1,9 @@
 pub mod audio;
+pub mod benchmark;
+pub mod catalog;
 pub mod database;
 pub mod diarization;
+pub mod download;
 pub mod export;
 pub mod history;
 pub mod input_tracker;
@@ -8,3 +11,4 @@ pub mod meeting;
 pub mod model;
 pub mod transcription;
 pub mod tts;
+pub mod wake_word;
@@ -1,23 +1,50 @@
+use super::catalog::{self, CatalogEntry};
+use super::download::{self, DownloadQueue};
 use crate::settings;
 use anyhow::Result;
 use bzip2::read::BzDecoder;
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
 use tar::Archive;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// How many times a download is fetched before a corrupt result is given up on.
+const MAX_DOWNLOAD_ATTEMPTS: usize = 2;
+
+/// How often a queued download checks for a free slot.
+const QUEUE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Models the user imported, stored in the models directory.
+const USER_CATALOG_FILE: &str = "custom_models.json";
+
+/// Last catalog fetched from a remote source, used until the next fetch succeeds.
+const CATALOG_CACHE_FILE: &str = "catalog_cache.json";
+
+/// First four bytes of a whisper.cpp GGML file ("ggml" as a little-endian u32).
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EngineType {
     Whisper,
     Parakeet,
     Diarization,
+    KeywordSpotting,
+}
+
+impl EngineType {
+    /// Whether models of this type turn speech into text, as opposed to the
+    /// helper models used by diarization and wake words.
+    pub fn is_transcription(&self) -> bool {
+        matches!(self, EngineType::Whisper | EngineType::Parakeet)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,208 +53,141 @@ pub struct ModelInfo {
     pub name: String,
     pub description: String,
     pub filename: String,
-    pub url: Option<String>,
+    /// Download URLs in the order they are tried, before any configured mirrors.
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// Expected SHA-256 of the downloaded file (or archive), as lowercase hex.
+    #[serde(default)]
+    pub sha256: Option<String>,
     pub size_mb: u64,
     pub is_downloaded: bool,
     pub is_downloading: bool,
     pub partial_size: u64,
     pub is_directory: bool,
+    /// Imported by the user rather than part of the built-in catalog.
+    #[serde(default)]
+    pub is_custom: bool,
     pub engine_type: EngineType,
+    /// Speech backend that runs this model, `None` for models that are not transcription models.
+    #[serde(default)]
+    pub backend: Option<String>,
     pub accuracy_score: f32, // 0.0 to 1.0, higher is more accurate
     pub speed_score: f32,    // 0.0 to 1.0, higher is faster
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVerification {
+    pub model_id: String,
+    /// "ok", "corrupt", or "unverified" when there is no checksum to compare against.
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDiskUsage {
+    pub model_id: String,
+    pub name: String,
+    pub bytes: u64,
+    /// Size of an unfinished download that can still be resumed.
+    pub partial_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedFile {
+    pub path: String,
+    pub bytes: u64,
+    /// "partial", "extracting" or "importing".
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageReport {
+    pub models_dir: String,
+    /// Everything in the models directory, including files no model owns.
+    pub total_bytes: u64,
+    pub models: Vec<ModelDiskUsage>,
+    pub orphans: Vec<OrphanedFile>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadProgress {
     pub model_id: String,
     pub downloaded: u64,
     pub total: u64,
     pub percentage: f64,
+    /// Bytes per second, smoothed.
+    pub speed: f64,
+    /// Seconds left at the current speed, when the size is known.
+    pub eta_seconds: Option<u64>,
+}
+
+impl DownloadProgress {
+    fn new(model_id: &str, downloaded: u64, total: u64, speed: f64) -> Self {
+        Self {
+            model_id: model_id.to_string(),
+            downloaded,
+            total,
+            percentage: if total > 0 {
+                (downloaded as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            },
+            speed,
+            eta_seconds: download::eta_seconds(downloaded, total, speed),
+        }
+    }
 }
 
 pub struct ModelManager {
     app_handle: AppHandle,
-    models_dir: PathBuf,
+    models_dir: RwLock<PathBuf>,
     available_models: Mutex<HashMap<String, ModelInfo>>,
+    downloads: Arc<DownloadQueue>,
+    /// Staging names of the imports being copied right now.
+    importing: Mutex<HashSet<String>>,
 }
 
 impl ModelManager {
     pub fn new(app_handle: &AppHandle) -> Result<Self> {
-        // Create models directory in app data
-        let models_dir = app_handle
+        // Create models directory in app data, unless it was moved elsewhere
+        let default_dir = app_handle
             .path()
             .app_data_dir()
             .map_err(|e| anyhow::anyhow!("Failed to get app data dir: {}", e))?
             .join("models");
+        let models_dir = match settings::get_settings(app_handle).models_directory {
+            Some(dir) if Path::new(&dir).is_dir() => PathBuf::from(dir),
+            Some(dir) => {
+                log::warn!(
+                    "Models directory {} is not available, using {:?}",
+                    dir,
+                    default_dir
+                );
+                default_dir
+            }
+            None => default_dir,
+        };
 
         if !models_dir.exists() {
             fs::create_dir_all(&models_dir)?;
         }
 
-        let mut available_models = HashMap::new();
-
-        // TODO this should be read from a JSON file or something..
-        available_models.insert(
-            "small".to_string(),
-            ModelInfo {
-                id: "small".to_string(),
-                name: "Whisper Small".to_string(),
-                description: "Fast and fairly accurate.".to_string(),
-                filename: "ggml-small.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-small.bin".to_string()),
-                size_mb: 487,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.60,
-                speed_score: 0.85,
-            },
-        );
-
-        // Add downloadable models
-        available_models.insert(
-            "medium".to_string(),
-            ModelInfo {
-                id: "medium".to_string(),
-                name: "Whisper Medium".to_string(),
-                description: "Good accuracy, medium speed".to_string(),
-                filename: "whisper-medium-q4_1.bin".to_string(),
-                url: Some("https://blob.handy.computer/whisper-medium-q4_1.bin".to_string()),
-                size_mb: 492, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.75,
-                speed_score: 0.60,
-            },
-        );
-
-        available_models.insert(
-            "turbo".to_string(),
-            ModelInfo {
-                id: "turbo".to_string(),
-                name: "Whisper Turbo".to_string(),
-                description: "Balanced accuracy and speed.".to_string(),
-                filename: "ggml-large-v3-turbo.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-turbo.bin".to_string()),
-                size_mb: 1600, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.80,
-                speed_score: 0.40,
-            },
-        );
-
-        available_models.insert(
-            "large".to_string(),
-            ModelInfo {
-                id: "large".to_string(),
-                name: "Whisper Large".to_string(),
-                description: "Good accuracy, but slow.".to_string(),
-                filename: "ggml-large-v3-q5_0.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-q5_0.bin".to_string()),
-                size_mb: 1100, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.30,
-            },
-        );
-
-        // Add NVIDIA Parakeet models (directory-based)
-        available_models.insert(
-            "parakeet-tdt-0.6b-v2".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v2".to_string(),
-                name: "Parakeet V2".to_string(),
-                description: "English only. The best model for English speakers.".to_string(),
-                filename: "parakeet-tdt-0.6b-v2-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v2-int8.tar.gz".to_string()),
-                size_mb: 473, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.85,
-                speed_score: 0.85,
-            },
-        );
-
-        available_models.insert(
-            "parakeet-tdt-0.6b-v3".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v3".to_string(),
-                name: "Parakeet V3".to_string(),
-                description: "Fast and accurate".to_string(),
-                filename: "parakeet-tdt-0.6b-v3-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v3-int8.tar.gz".to_string()),
-                size_mb: 478, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.80,
-                speed_score: 0.85,
-            },
-        );
-
-        // Diarization models (speaker segmentation + embedding)
-        available_models.insert(
-            "diarization-segmentation".to_string(),
-            ModelInfo {
-                id: "diarization-segmentation".to_string(),
-                name: "Speaker Segmentation".to_string(),
-                description: "Identifies when different speakers are talking".to_string(),
-                filename: "sherpa-onnx-pyannote-segmentation-3-0".to_string(),
-                url: Some("https://github.com/k2-fsa/sherpa-onnx/releases/download/speaker-segmentation-models/sherpa-onnx-pyannote-segmentation-3-0.tar.bz2".to_string()),
-                size_mb: 6,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Diarization,
-                accuracy_score: 0.0,
-                speed_score: 0.0,
-            },
-        );
-
-        available_models.insert(
-            "diarization-embedding".to_string(),
-            ModelInfo {
-                id: "diarization-embedding".to_string(),
-                name: "Speaker Embedding".to_string(),
-                description: "Creates voice signatures to distinguish speakers".to_string(),
-                filename: "3dspeaker_speech_eres2net_base_sv_zh-cn_3dspeaker_16k.onnx".to_string(),
-                url: Some("https://github.com/k2-fsa/sherpa-onnx/releases/download/speaker-recongition-models/3dspeaker_speech_eres2net_base_sv_zh-cn_3dspeaker_16k.onnx".to_string()),
-                size_mb: 70,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Diarization,
-                accuracy_score: 0.0,
-                speed_score: 0.0,
-            },
-        );
+        let available_models = Self::initial_catalog(app_handle, &models_dir)
+            .into_iter()
+            .map(|entry| (entry.id.clone(), entry.into_model_info()))
+            .collect();
 
         let manager = Self {
             app_handle: app_handle.clone(),
-            models_dir,
+            models_dir: RwLock::new(models_dir),
             available_models: Mutex::new(available_models),
+            downloads: Arc::new(DownloadQueue::default()),
+            importing: Mutex::new(HashSet::new()),
         };
 
+        // Add the models the user imported
+        manager.load_user_catalog();
+
         // Migrate any bundled models to user directory
         manager.migrate_bundled_models()?;
 
@@ -240,6 +200,70 @@ impl ModelManager {
         Ok(manager)
     }
 
+    /// The catalog to start with: the configured manifest if it can be read
+    /// without the network, the cached copy of a remote one, or the built-in one.
+    fn initial_catalog(app_handle: &AppHandle, models_dir: &Path) -> Vec<CatalogEntry> {
+        let source = settings::get_settings(app_handle).model_catalog_source;
+        let loaded = match source.as_deref() {
+            None => return catalog::default_entries(),
+            Some(source) if catalog::is_remote(source) => {
+                let cache = models_dir.join(CATALOG_CACHE_FILE);
+                if !cache.exists() {
+                    return catalog::default_entries();
+                }
+                fs::read_to_string(&cache)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| catalog::parse_manifest(&json))
+            }
+            Some(source) => catalog::read_manifest(source),
+        };
+
+        loaded.unwrap_or_else(|e| {
+            log::warn!("Using the built-in model catalog: {}", e);
+            catalog::default_entries()
+        })
+    }
+
+    /// Reload the catalog from the configured manifest, keeping imported models.
+    ///
+    /// A remote manifest is cached in the models directory for the next start.
+    pub async fn refresh_catalog(&self) -> Result<()> {
+        let source = settings::get_settings(&self.app_handle).model_catalog_source;
+        let entries = match source.as_deref() {
+            None => catalog::default_entries(),
+            Some(source) => {
+                let entries = catalog::fetch_manifest(source).await?;
+                if catalog::is_remote(source) {
+                    let manifest = catalog::CatalogManifest {
+                        version: 1,
+                        models: entries.clone(),
+                    };
+                    fs::write(
+                        self.models_dir().join(CATALOG_CACHE_FILE),
+                        serde_json::to_string_pretty(&manifest)?,
+                    )?;
+                }
+                entries
+            }
+        };
+
+        let count = entries.len();
+        {
+            let mut models = self.available_models.lock().unwrap();
+            let previous = std::mem::take(&mut *models);
+            *models = merge_catalog(previous, entries, |id| self.downloads.is_pending(id));
+        }
+        self.update_download_status()?;
+
+        log::info!(
+            "Loaded {} models from {}",
+            count,
+            source.as_deref().unwrap_or("the built-in catalog")
+        );
+        let _ = self.app_handle.emit("model-catalog-updated", ());
+        Ok(())
+    }
+
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
         let models = self.available_models.lock().unwrap();
         models.values().cloned().collect()
@@ -249,7 +273,7 @@ impl ModelManager {
         let models = self.available_models.lock().unwrap();
         models
             .values()
-            .filter(|m| !matches!(m.engine_type, EngineType::Diarization))
+            .filter(|m| m.engine_type.is_transcription())
             .cloned()
             .collect()
     }
@@ -259,6 +283,336 @@ impl ModelManager {
         models.get(model_id).cloned()
     }
 
+    fn load_user_catalog(&self) {
+        let path = self.models_dir().join(USER_CATALOG_FILE);
+        if !path.exists() {
+            return;
+        }
+
+        let entries = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<Vec<ModelInfo>>(&json).map_err(|e| e.to_string())
+            });
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!("Failed to read custom model catalog {:?}: {}", path, e);
+                return;
+            }
+        };
+
+        let mut models = self.available_models.lock().unwrap();
+        for mut model in entries {
+            if models.contains_key(&model.id) {
+                log::warn!(
+                    "Custom model {} shadows a built-in model, skipping",
+                    model.id
+                );
+                continue;
+            }
+            model.is_custom = true;
+            models.insert(model.id.clone(), model);
+        }
+    }
+
+    fn save_user_catalog(&self) -> Result<()> {
+        let mut entries: Vec<ModelInfo> = {
+            let models = self.available_models.lock().unwrap();
+            models.values().filter(|m| m.is_custom).cloned().collect()
+        };
+        entries.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let json = serde_json::to_string_pretty(&entries)?;
+        fs::write(self.models_dir().join(USER_CATALOG_FILE), json)?;
+        Ok(())
+    }
+
+    /// Add a local model file or directory to the user catalog.
+    ///
+    /// The engine is detected from the model's layout unless `engine_type` is
+    /// given. With `link`, the model is symlinked into the models directory
+    /// instead of copied, so it keeps following the original.
+    pub fn import_model(
+        &self,
+        source: &Path,
+        name: &str,
+        engine_type: Option<EngineType>,
+        link: bool,
+    ) -> Result<ModelInfo> {
+        if !source.exists() {
+            return Err(anyhow::anyhow!("{:?} does not exist", source));
+        }
+
+        let engine_type = engine_type
+            .or_else(|| detect_engine_type(source))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Could not tell what kind of model {:?} is, please choose the engine",
+                    source
+                )
+            })?;
+        let is_directory = source.is_dir();
+        let backend = match engine_type {
+            EngineType::Whisper if !is_directory => "whisper",
+            EngineType::Parakeet if is_directory => "parakeet",
+            EngineType::Whisper => {
+                return Err(anyhow::anyhow!("Whisper models must be a single GGML file"))
+            }
+            EngineType::Parakeet => {
+                return Err(anyhow::anyhow!(
+                    "Parakeet models must be a directory of ONNX files"
+                ))
+            }
+            EngineType::Diarization | EngineType::KeywordSpotting => {
+                return Err(anyhow::anyhow!("Only transcription models can be imported"))
+            }
+        };
+
+        let name = match name.trim() {
+            "" => source
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "Custom model".to_string()),
+            name => name.to_string(),
+        };
+        let id = self.unique_model_id(&name);
+        let filename = if is_directory {
+            id.clone()
+        } else {
+            format!("{}.bin", id)
+        };
+        let target = self.models_dir().join(&filename);
+
+        if link {
+            link_path(source, &target)?;
+        } else {
+            // Copy under a temporary name so an interrupted import never looks complete
+            let staging_name = format!("{}.importing", &filename);
+            let staging = self.models_dir().join(&staging_name);
+            self.importing.lock().unwrap().insert(staging_name.clone());
+            let _ = fs::remove_dir_all(&staging);
+            let _ = fs::remove_file(&staging);
+            let copied = copy_path(source, &staging)
+                .map_err(|e| anyhow::anyhow!("Failed to copy model: {}", e))
+                .and_then(|()| fs::rename(&staging, &target).map_err(anyhow::Error::from));
+            if copied.is_err() {
+                let _ = fs::remove_dir_all(&staging);
+                let _ = fs::remove_file(&staging);
+            }
+            self.importing.lock().unwrap().remove(&staging_name);
+            copied?;
+        }
+
+        let model = ModelInfo {
+            id: id.clone(),
+            name,
+            description: format!("Imported from {}", source.display()),
+            filename,
+            urls: Vec::new(),
+            sha256: None,
+            size_mb: path_size(source) / (1024 * 1024),
+            is_downloaded: true,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory,
+            is_custom: true,
+            engine_type,
+            backend: Some(backend.to_string()),
+            accuracy_score: 0.0,
+            speed_score: 0.0,
+        };
+
+        self.available_models
+            .lock()
+            .unwrap()
+            .insert(id.clone(), model.clone());
+        self.save_user_catalog()?;
+        self.update_download_status()?;
+
+        log::info!(
+            "Imported {} model {} from {:?} ({})",
+            backend,
+            id,
+            source,
+            if link { "linked" } else { "copied" }
+        );
+        Ok(model)
+    }
+
+    /// A model id derived from `name` that is not taken yet.
+    fn unique_model_id(&self, name: &str) -> String {
+        let base = format!("custom-{}", slugify(name));
+        let models = self.available_models.lock().unwrap();
+        let taken = |id: &str| {
+            models.contains_key(id)
+                || self.models_dir().join(id).exists()
+                || self.models_dir().join(format!("{}.bin", id)).exists()
+        };
+
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while taken(&id) {
+            id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+        id
+    }
+
+    /// Where models are stored right now.
+    pub fn models_dir(&self) -> PathBuf {
+        self.models_dir.read().unwrap().clone()
+    }
+
+    /// Disk space taken by each model, and by files no download will finish.
+    pub fn storage_report(&self) -> Result<StorageReport> {
+        let models_dir = self.models_dir();
+        let models: Vec<ModelInfo> = {
+            let models = self.available_models.lock().unwrap();
+            models.values().cloned().collect()
+        };
+
+        let importing = self.importing.lock().unwrap().clone();
+        let mut usage = Vec::new();
+        let mut owned = HashSet::new();
+        for model in &models {
+            let path = models_dir.join(&model.filename);
+            let partial_name = format!("{}.partial", &model.filename);
+            owned.insert(model.filename.clone());
+            owned.insert(partial_name.clone());
+
+            // A linked model's data lives elsewhere; count only the link
+            let bytes = match fs::symlink_metadata(&path) {
+                Ok(meta) if meta.file_type().is_symlink() => 0,
+                Ok(_) => path_size(&path),
+                Err(_) => 0,
+            };
+            let partial_bytes = path_size(&models_dir.join(&partial_name));
+            if bytes > 0 || partial_bytes > 0 {
+                usage.push(ModelDiskUsage {
+                    model_id: model.id.clone(),
+                    name: model.name.clone(),
+                    bytes,
+                    partial_bytes,
+                });
+            }
+        }
+        usage.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+
+        // Leftovers of interrupted extractions and imports, and downloads of
+        // models that are no longer in the catalog
+        let mut orphans = Vec::new();
+        for entry in fs::read_dir(&models_dir)?.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let kind = if name.ends_with(".extracting") {
+                let filename = name.trim_end_matches(".extracting");
+                let active = models
+                    .iter()
+                    .any(|m| m.filename == filename && self.downloads.is_active(&m.id));
+                (!active).then_some("extracting")
+            } else if name.ends_with(".importing") {
+                (!importing.contains(&name)).then_some("importing")
+            } else if name.ends_with(".partial") && !owned.contains(&name) {
+                Some("partial")
+            } else {
+                None
+            };
+
+            if let Some(kind) = kind {
+                orphans.push(OrphanedFile {
+                    path: entry.path().to_string_lossy().into_owned(),
+                    bytes: path_size(&entry.path()),
+                    kind: kind.to_string(),
+                });
+            }
+        }
+
+        let total_bytes = path_size(&models_dir);
+        Ok(StorageReport {
+            models_dir: models_dir.to_string_lossy().into_owned(),
+            total_bytes,
+            models: usage,
+            orphans,
+        })
+    }
+
+    /// Delete the orphaned files found by `storage_report`, returning the bytes freed.
+    pub fn cleanup_orphans(&self) -> Result<u64> {
+        let mut freed = 0;
+        for orphan in self.storage_report()?.orphans {
+            let path = Path::new(&orphan.path);
+            let removed = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            };
+            match removed {
+                Ok(()) => {
+                    log::info!("Removed orphaned {} file {:?}", orphan.kind, path);
+                    freed += orphan.bytes;
+                }
+                Err(e) => log::warn!("Failed to remove {:?}: {}", path, e),
+            }
+        }
+        self.update_download_status()?;
+        Ok(freed)
+    }
+
+    /// Move the models directory to `target`, which must not exist or be empty.
+    ///
+    /// See `move_dir` for how a failure leaves everything where it was.
+    pub fn relocate_models_dir(&self, target: &Path) -> Result<PathBuf> {
+        let source = self.models_dir();
+        let target = std::path::absolute(target)?;
+        if target == source {
+            return Err(anyhow::anyhow!("Models are already stored in {:?}", target));
+        }
+        if target.starts_with(&source) {
+            return Err(anyhow::anyhow!(
+                "Cannot move the models directory into itself"
+            ));
+        }
+        if target.exists() {
+            let empty = target.is_dir() && fs::read_dir(&target)?.next().is_none();
+            if !empty {
+                return Err(anyhow::anyhow!(
+                    "{:?} already exists and is not empty",
+                    target
+                ));
+            }
+        }
+        {
+            let models = self.available_models.lock().unwrap();
+            if models.keys().any(|id| self.downloads.is_active(id))
+                || !self.importing.lock().unwrap().is_empty()
+            {
+                return Err(anyhow::anyhow!(
+                    "Wait for model downloads to finish before moving the models"
+                ));
+            }
+        }
+
+        // Hold the lock so nothing resolves a model path halfway through
+        let mut models_dir = self.models_dir.write().unwrap();
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        move_dir(&source, &target)
+            .map_err(|e| anyhow::anyhow!("Failed to move models to {:?}: {}", target, e))?;
+
+        *models_dir = target.clone();
+        drop(models_dir);
+
+        let dir = target.to_string_lossy().into_owned();
+        settings::update_settings(&self.app_handle, |s| {
+            s.models_directory = Some(dir);
+        });
+        self.update_download_status()?;
+
+        log::info!("Moved models directory from {:?} to {:?}", source, target);
+        Ok(target)
+    }
+
     fn migrate_bundled_models(&self) -> Result<()> {
         // Check for bundled models and copy them to user directory
         let bundled_models = ["ggml-small.bin"]; // Add other bundled models here if any
@@ -271,7 +625,7 @@ impl ModelManager {
 
             if let Ok(bundled_path) = bundled_path {
                 if bundled_path.exists() {
-                    let user_path = self.models_dir.join(filename);
+                    let user_path = self.models_dir().join(filename);
 
                     // Only copy if user doesn't already have the model
                     if !user_path.exists() {
@@ -287,25 +641,25 @@ impl ModelManager {
     }
 
     fn update_download_status(&self) -> Result<()> {
+        let models_dir = self.models_dir();
         let mut models = self.available_models.lock().unwrap();
 
         for model in models.values_mut() {
             if model.is_directory {
                 // For directory-based models, check if the directory exists
-                let model_path = self.models_dir.join(&model.filename);
-                let partial_path = self.models_dir.join(format!("{}.partial", &model.filename));
-                let extracting_path = self
-                    .models_dir
-                    .join(format!("{}.extracting", &model.filename));
+                let model_path = models_dir.join(&model.filename);
+                let partial_path = models_dir.join(format!("{}.partial", &model.filename));
+                let extracting_path = models_dir.join(format!("{}.extracting", &model.filename));
 
                 // Clean up any leftover .extracting directories from interrupted extractions
-                if extracting_path.exists() {
+                if extracting_path.exists() && !self.downloads.is_active(&model.id) {
                     log::warn!("Cleaning up interrupted extraction for model: {}", model.id);
                     let _ = fs::remove_dir_all(&extracting_path);
                 }
 
                 model.is_downloaded = model_path.exists() && model_path.is_dir();
-                model.is_downloading = partial_path.exists();
+                model.is_downloading =
+                    partial_path.exists() || self.downloads.is_pending(&model.id);
 
                 // Get partial file size if it exists (for the .tar.gz being downloaded)
                 if partial_path.exists() {
@@ -315,11 +669,12 @@ impl ModelManager {
                 }
             } else {
                 // For file-based models (existing logic)
-                let model_path = self.models_dir.join(&model.filename);
-                let partial_path = self.models_dir.join(format!("{}.partial", &model.filename));
+                let model_path = models_dir.join(&model.filename);
+                let partial_path = models_dir.join(format!("{}.partial", &model.filename));
 
                 model.is_downloaded = model_path.exists();
-                model.is_downloading = partial_path.exists();
+                model.is_downloading =
+                    partial_path.exists() || self.downloads.is_pending(&model.id);
 
                 // Get partial file size if it exists
                 if partial_path.exists() {
@@ -343,7 +698,7 @@ impl ModelManager {
             let models = self.available_models.lock().unwrap();
             if let Some(available_model) = models
                 .values()
-                .find(|model| model.is_downloaded && !matches!(model.engine_type, EngineType::Diarization))
+                .find(|model| model.is_downloaded && model.engine_type.is_transcription())
             {
                 log::info!(
                     "Auto-selecting model: {} ({})",
@@ -373,12 +728,14 @@ impl ModelManager {
         let model_info =
             model_info.ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
 
-        let url = model_info
-            .url
-            .ok_or_else(|| anyhow::anyhow!("No download URL for model"))?;
-        let model_path = self.models_dir.join(&model_info.filename);
+        let mirrors = settings::get_settings(&self.app_handle).model_mirrors;
+        let urls = catalog::candidate_urls(&model_info.urls, &mirrors);
+        if urls.is_empty() {
+            return Err(anyhow::anyhow!("No download URL for model"));
+        }
+        let model_path = self.models_dir().join(&model_info.filename);
         let partial_path = self
-            .models_dir
+            .models_dir()
             .join(format!("{}.partial", &model_info.filename));
 
         // Don't download if complete version already exists
@@ -391,15 +748,7 @@ impl ModelManager {
             return Ok(());
         }
 
-        // Check if we have a partial download to resume
-        let resume_from = if partial_path.exists() {
-            let size = partial_path.metadata()?.len();
-            log::info!("Resuming download of model {} from byte {}", model_id, size);
-            size
-        } else {
-            log::info!("Starting fresh download of model {} from {}", model_id, url);
-            0
-        };
+        self.downloads.enqueue(model_id)?;
 
         // Mark as downloading
         {
@@ -409,103 +758,104 @@ impl ModelManager {
             }
         }
 
-        // Create HTTP client with range request for resuming
-        let client = reqwest::Client::new();
-        let mut request = client.get(&url);
+        // Wait for a free slot; the slot is released when this function returns
+        let mut queued = false;
+        let _slot = loop {
+            let limit = settings::get_settings(&self.app_handle).max_concurrent_downloads;
+            match self.downloads.try_start(model_id, limit) {
+                Ok(Some(slot)) => break slot,
+                Ok(None) => {
+                    if !queued {
+                        log::info!("Download of model {} is queued", model_id);
+                        let _ = self.app_handle.emit("model-download-queued", model_id);
+                        queued = true;
+                    }
+                    tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+                }
+                Err(e) => {
+                    log::info!("Queued download of model {} was cancelled", model_id);
+                    self.set_downloading(model_id, false);
+                    return Err(e);
+                }
+            }
+        };
 
-        if resume_from > 0 {
-            request = request.header("Range", format!("bytes={}-", resume_from));
+        // Try each source in order. A corrupt download is fetched again from the
+        // same source first, since a bad resume is the usual cause.
+        let mut url = None;
+        let mut last_error = None;
+        'sources: for source in &urls {
+            for attempt in 1..=MAX_DOWNLOAD_ATTEMPTS {
+                let fetched = self
+                    .fetch_with_retries(model_id, source, &partial_path)
+                    .await;
+                let expected_size = match fetched {
+                    Ok(expected_size) => expected_size,
+                    Err(e) if e.is::<download::Cancelled>() => {
+                        log::info!("Download of model {} was cancelled", model_id);
+                        self.set_downloading(model_id, false);
+                        return Err(e);
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Download of model {} from {} failed: {}",
+                            model_id,
+                            source,
+                            e
+                        );
+                        last_error = Some((e.to_string(), false));
+                        continue 'sources;
+                    }
+                };
+
+                let path = partial_path.clone();
+                let sha256 = model_info.sha256.clone();
+                let verified = tauri::async_runtime::spawn_blocking(move || {
+                    verify_file(&path, sha256.as_deref(), expected_size)
+                })
+                .await?;
+
+                match verified {
+                    Ok(()) => {
+                        url = Some(source.clone());
+                        break 'sources;
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Download of model {} from {} is corrupt (attempt {}/{}): {}",
+                            model_id,
+                            source,
+                            attempt,
+                            MAX_DOWNLOAD_ATTEMPTS,
+                            e
+                        );
+                        let _ = fs::remove_file(&partial_path);
+                        let error_msg = format!("Downloaded file failed verification: {}", e);
+                        last_error = Some((error_msg, true));
+                    }
+                }
+            }
         }
 
-        let response = request.send().await?;
-
-        // Check for success or partial content status
-        if !response.status().is_success()
-            && response.status() != reqwest::StatusCode::PARTIAL_CONTENT
-        {
-            // Mark as not downloading on error
+        let Some(url) = url else {
             {
                 let mut models = self.available_models.lock().unwrap();
                 if let Some(model) = models.get_mut(model_id) {
                     model.is_downloading = false;
                 }
             }
-            return Err(anyhow::anyhow!(
-                "Failed to download model: HTTP {}",
-                response.status()
-            ));
-        }
-
-        let total_size = if resume_from > 0 {
-            // For resumed downloads, add the resume point to content length
-            resume_from + response.content_length().unwrap_or(0)
-        } else {
-            response.content_length().unwrap_or(0)
-        };
-
-        let mut downloaded = resume_from;
-        let mut stream = response.bytes_stream();
-
-        // Open file for appending if resuming, or create new if starting fresh
-        let mut file = if resume_from > 0 {
-            std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&partial_path)?
-        } else {
-            std::fs::File::create(&partial_path)?
-        };
-
-        // Emit initial progress
-        let initial_progress = DownloadProgress {
-            model_id: model_id.to_string(),
-            downloaded,
-            total: total_size,
-            percentage: if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                0.0
-            },
+            let (error_msg, corrupt) = last_error.unwrap_or_default();
+            if corrupt {
+                let _ = self.app_handle.emit(
+                    "model-verification-failed",
+                    &serde_json::json!({
+                        "model_id": model_id,
+                        "error": error_msg
+                    }),
+                );
+            }
+            return Err(anyhow::anyhow!(error_msg));
         };
-        let _ = self
-            .app_handle
-            .emit("model-download-progress", &initial_progress);
-
-        // Download with progress
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| {
-                // Mark as not downloading on error
-                {
-                    let mut models = self.available_models.lock().unwrap();
-                    if let Some(model) = models.get_mut(model_id) {
-                        model.is_downloading = false;
-                    }
-                }
-                e
-            })?;
-
-            file.write_all(&chunk)?;
-            downloaded += chunk.len() as u64;
-
-            let percentage = if total_size > 0 {
-                (downloaded as f64 / total_size as f64) * 100.0
-            } else {
-                0.0
-            };
-
-            // Emit progress event
-            let progress = DownloadProgress {
-                model_id: model_id.to_string(),
-                downloaded,
-                total: total_size,
-                percentage,
-            };
-
-            let _ = self.app_handle.emit("model-download-progress", &progress);
-        }
-
-        file.flush()?;
-        drop(file); // Ensure file is closed before moving
 
         // Handle directory-based models (extract tar.gz) vs file-based models
         if model_info.is_directory {
@@ -515,9 +865,9 @@ impl ModelManager {
 
             // Use a temporary extraction directory to ensure atomic operations
             let temp_extract_dir = self
-                .models_dir
+                .models_dir()
                 .join(format!("{}.extracting", &model_info.filename));
-            let final_model_dir = self.models_dir.join(&model_info.filename);
+            let final_model_dir = self.models_dir().join(&model_info.filename);
 
             // Clean up any previous incomplete extraction
             if temp_extract_dir.exists() {
@@ -619,6 +969,79 @@ impl ModelManager {
         Ok(())
     }
 
+    fn set_downloading(&self, model_id: &str, downloading: bool) {
+        let mut models = self.available_models.lock().unwrap();
+        if let Some(model) = models.get_mut(model_id) {
+            model.is_downloading = downloading;
+        }
+    }
+
+    /// `fetch_partial`, resuming after dropped connections and server errors
+    /// with exponential backoff.
+    async fn fetch_with_retries(
+        &self,
+        model_id: &str,
+        url: &str,
+        partial_path: &Path,
+    ) -> Result<u64> {
+        // A local mirror fails the same way every time
+        let max_retries = if url.starts_with("file://") {
+            0
+        } else {
+            download::MAX_FETCH_RETRIES
+        };
+        retry_fetch(
+            model_id,
+            &self.downloads,
+            max_retries,
+            || self.fetch_partial(model_id, url, partial_path),
+            |retry, delay, error| {
+                let _ = self.app_handle.emit(
+                    "model-download-retrying",
+                    &serde_json::json!({
+                        "model_id": model_id,
+                        "retry": retry,
+                        "max_retries": max_retries,
+                        "delay_ms": delay.as_millis() as u64,
+                        "error": error.to_string()
+                    }),
+                );
+            },
+        )
+        .await
+    }
+
+    /// Stream the model's download into `partial_path`, resuming from whatever
+    /// is already there. Returns the expected size of the finished file, or 0
+    /// if the server did not say.
+    async fn fetch_partial(&self, model_id: &str, url: &str, partial_path: &Path) -> Result<u64> {
+        if url.starts_with("file://") {
+            let source = catalog::local_path(url)
+                .ok_or_else(|| anyhow::anyhow!("Invalid file URL {}", url))?;
+            let app_handle = self.app_handle.clone();
+            let downloads = self.downloads.clone();
+            let model_id = model_id.to_string();
+            let partial_path = partial_path.to_path_buf();
+            return tauri::async_runtime::spawn_blocking(move || {
+                copy_partial(&app_handle, &downloads, &model_id, &source, &partial_path)
+            })
+            .await?;
+        }
+
+        let bandwidth_limit = settings::get_settings(&self.app_handle).download_bandwidth_limit;
+        fetch_http(
+            model_id,
+            url,
+            partial_path,
+            &self.downloads,
+            bandwidth_limit,
+            |progress| {
+                let _ = self.app_handle.emit("model-download-progress", progress);
+            },
+        )
+        .await
+    }
+
     pub fn delete_model(&self, model_id: &str) -> Result<()> {
         log::info!("ModelManager: delete_model called for: {}", model_id);
 
@@ -632,9 +1055,9 @@ impl ModelManager {
 
         log::debug!("ModelManager: Found model info: {:?}", model_info);
 
-        let model_path = self.models_dir.join(&model_info.filename);
+        let model_path = self.models_dir().join(&model_info.filename);
         let partial_path = self
-            .models_dir
+            .models_dir()
             .join(format!("{}.partial", &model_info.filename));
         log::debug!("ModelManager: Model path: {:?}", model_path);
         log::debug!("ModelManager: Partial path: {:?}", partial_path);
@@ -670,6 +1093,17 @@ impl ModelManager {
             deleted_something = true;
         }
 
+        // Imported models leave the catalog along with their files
+        if model_info.is_custom {
+            self.available_models.lock().unwrap().remove(model_id);
+            self.save_user_catalog()?;
+            log::info!(
+                "ModelManager: Removed custom model {} from catalog",
+                model_id
+            );
+            deleted_something = true;
+        }
+
         if !deleted_something {
             return Err(anyhow::anyhow!("No model files found to delete"));
         }
@@ -698,9 +1132,9 @@ impl ModelManager {
             ));
         }
 
-        let model_path = self.models_dir.join(&model_info.filename);
+        let model_path = self.models_dir().join(&model_info.filename);
         let partial_path = self
-            .models_dir
+            .models_dir()
             .join(format!("{}.partial", &model_info.filename));
 
         if model_info.is_directory {
@@ -726,6 +1160,57 @@ impl ModelManager {
         }
     }
 
+    /// Re-hash every downloaded model that has a known checksum.
+    ///
+    /// Archives are deleted after extraction, so directory-based models can only
+    /// be checked while they download and are reported as unverified here.
+    pub fn verify_installed_models(&self) -> Vec<ModelVerification> {
+        let models: Vec<ModelInfo> = {
+            let models = self.available_models.lock().unwrap();
+            models
+                .values()
+                .filter(|m| m.is_downloaded)
+                .cloned()
+                .collect()
+        };
+
+        models
+            .into_iter()
+            .map(|model| {
+                let expected = match &model.sha256 {
+                    Some(expected) if !model.is_directory => expected,
+                    _ => {
+                        return ModelVerification {
+                            model_id: model.id,
+                            status: "unverified".to_string(),
+                            error: None,
+                        }
+                    }
+                };
+
+                let path = self.models_dir().join(&model.filename);
+                match verify_file(&path, Some(expected), 0) {
+                    Ok(()) => {
+                        log::info!("Model {} passed verification", model.id);
+                        ModelVerification {
+                            model_id: model.id,
+                            status: "ok".to_string(),
+                            error: None,
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("Model {} failed verification: {}", model.id, e);
+                        ModelVerification {
+                            model_id: model.id,
+                            status: "corrupt".to_string(),
+                            error: Some(e.to_string()),
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
     pub fn cancel_download(&self, model_id: &str) -> Result<()> {
         log::info!("ModelManager: cancel_download called for: {}", model_id);
 
@@ -745,9 +1230,9 @@ impl ModelManager {
             }
         }
 
-        // Note: The actual download cancellation would need to be handled
-        // by the download task itself. This just updates the state.
-        // The partial file is kept so the download can be resumed later.
+        // The download task stops at its next chunk, or leaves the queue if it
+        // has not started. The partial file is kept so the download can be resumed later.
+        self.downloads.cancel(model_id);
 
         // Update download status to reflect current state
         self.update_download_status()?;
@@ -756,3 +1241,646 @@ impl ModelManager {
         Ok(())
     }
 }
+
+/// Check a downloaded file against its expected size (skipped when 0) and SHA-256.
+fn verify_file(path: &Path, expected_sha256: Option<&str>, expected_size: u64) -> Result<()> {
+    let size = path.metadata()?.len();
+    if expected_size > 0 && size != expected_size {
+        return Err(anyhow::anyhow!(
+            "expected {} bytes but found {}",
+            expected_size,
+            size
+        ));
+    }
+
+    if let Some(expected) = expected_sha256 {
+        let actual = sha256_file(path)?;
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err(anyhow::anyhow!(
+                "SHA-256 mismatch: expected {}, got {}",
+                expected,
+                actual
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copy a model from a local mirror into `partial_path`, resuming like an HTTP
+/// download and emitting the same progress events.
+fn copy_partial(
+    app_handle: &AppHandle,
+    downloads: &DownloadQueue,
+    model_id: &str,
+    source: &Path,
+    partial_path: &Path,
+) -> Result<u64> {
+    let total = source.metadata()?.len();
+    let mut copied = partial_path.metadata().map(|m| m.len()).unwrap_or(0);
+    if copied > total {
+        copied = 0;
+    }
+
+    let mut input = File::open(source)?;
+    input.seek(SeekFrom::Start(copied))?;
+    let mut output = if copied > 0 {
+        fs::OpenOptions::new().append(true).open(partial_path)?
+    } else {
+        File::create(partial_path)?
+    };
+
+    let mut speed = download::SpeedEstimator::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        if downloads.is_cancelled(model_id) {
+            return Err(download::Cancelled.into());
+        }
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        output.write_all(&buffer[..read])?;
+        copied += read as u64;
+
+        let progress = DownloadProgress::new(model_id, copied, total, speed.record(read as u64));
+        let _ = app_handle.emit("model-download-progress", &progress);
+    }
+
+    output.flush()?;
+    Ok(total)
+}
+
+/// Guess the engine of a model from its layout: whisper.cpp models are single
+/// GGML files, Parakeet exports are directories of ONNX files with a vocabulary.
+fn detect_engine_type(path: &Path) -> Option<EngineType> {
+    if path.is_dir() {
+        let names: Vec<String> = fs::read_dir(path)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        let has_onnx = names.iter().any(|name| name.ends_with(".onnx"));
+        let has_vocab = names.iter().any(|name| name == "vocab.txt");
+        (has_onnx && has_vocab).then_some(EngineType::Parakeet)
+    } else {
+        let mut magic = [0u8; 4];
+        File::open(path).ok()?.read_exact(&mut magic).ok()?;
+        (u32::from_le_bytes(magic) == GGML_MAGIC).then_some(EngineType::Whisper)
+    }
+}
+
+/// Lowercase ASCII letters and digits, with runs of anything else turned into "-".
+fn slugify(name: &str) -> String {
+    let slug = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "model".to_string()
+    } else {
+        slug
+    }
+}
+
+fn copy_path(source: &Path, target: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, target).map(|_| ())
+    }
+}
+
+/// Copy a directory tree, recreating symlinks rather than following them.
+/// Stream `url` into `partial_path`, resuming from whatever is already there.
+/// Returns the expected size of the finished file, or 0 if the server did not
+/// say.
+async fn fetch_http(
+    model_id: &str,
+    url: &str,
+    partial_path: &Path,
+    downloads: &DownloadQueue,
+    bandwidth_limit: Option<u64>,
+    on_progress: impl Fn(&DownloadProgress),
+) -> Result<u64> {
+    // Check if we have a partial download to resume
+    let resume_from = if partial_path.exists() {
+        let size = partial_path.metadata()?.len();
+        log::info!("Resuming download of model {} from byte {}", model_id, size);
+        size
+    } else {
+        log::info!("Starting fresh download of model {} from {}", model_id, url);
+        0
+    };
+
+    // Create HTTP client with range request for resuming
+    let client = reqwest::Client::new();
+    let mut request = client.get(url);
+
+    if resume_from > 0 {
+        request = request.header("Range", format!("bytes={}-", resume_from));
+    }
+
+    let response = request.send().await?;
+
+    // Check for success or partial content status
+    let status = response.status();
+    if !status.is_success() && status != reqwest::StatusCode::PARTIAL_CONTENT {
+        return Err(download::HttpStatus(status).into());
+    }
+
+    // A server that ignores the Range header sends the whole file again
+    let range_ignored = status != reqwest::StatusCode::PARTIAL_CONTENT;
+    let resume_from = if resume_from > 0 && range_ignored {
+        log::warn!(
+            "Server ignored the resume request for model {}, restarting download",
+            model_id
+        );
+        0
+    } else {
+        resume_from
+    };
+
+    let total_size = if resume_from > 0 {
+        // For resumed downloads, add the resume point to content length
+        resume_from + response.content_length().unwrap_or(0)
+    } else {
+        response.content_length().unwrap_or(0)
+    };
+
+    let mut downloaded = resume_from;
+    let mut stream = response.bytes_stream();
+
+    // Open file for appending if resuming, or create new if starting fresh
+    let mut file = if resume_from > 0 {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(partial_path)?
+    } else {
+        std::fs::File::create(partial_path)?
+    };
+
+    // Emit initial progress
+    let initial_progress = DownloadProgress::new(model_id, downloaded, total_size, 0.0);
+    on_progress(&initial_progress);
+
+    let mut speed = download::SpeedEstimator::new();
+
+    // Download with progress
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if downloads.is_cancelled(model_id) {
+            return Err(download::Cancelled.into());
+        }
+
+        file.write_all(&chunk)?;
+        downloaded += chunk.len() as u64;
+
+        // Reading slower makes the server send slower
+        if let Some(limit) = bandwidth_limit {
+            let wait = downloads.throttle(chunk.len(), limit);
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        // Emit progress event
+        let progress = DownloadProgress::new(
+            model_id,
+            downloaded,
+            total_size,
+            speed.record(chunk.len() as u64),
+        );
+
+        on_progress(&progress);
+    }
+
+    file.flush()?;
+
+    Ok(total_size)
+}
+
+/// Call `fetch` until it succeeds, resuming after dropped connections and
+/// server errors with exponential backoff, at most `max_retries` times.
+/// `on_retry` hears of each retry before its delay.
+async fn retry_fetch<F, Fut>(
+    model_id: &str,
+    downloads: &DownloadQueue,
+    max_retries: u32,
+    mut fetch: F,
+    on_retry: impl Fn(u32, std::time::Duration, &anyhow::Error),
+) -> Result<u64>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<u64>>,
+{
+    let mut retry = 0;
+    loop {
+        let error = match fetch().await {
+            Ok(expected_size) => return Ok(expected_size),
+            Err(e) => e,
+        };
+        if retry >= max_retries || !download::is_retryable(&error) {
+            return Err(error);
+        }
+
+        retry += 1;
+        let delay = download::backoff_delay(retry);
+        log::warn!(
+            "Download of model {} interrupted ({}), resuming in {:?} (retry {}/{})",
+            model_id,
+            error,
+            delay,
+            retry,
+            max_retries
+        );
+        on_retry(retry, delay, &error);
+        tokio::time::sleep(delay).await;
+
+        if downloads.is_cancelled(model_id) {
+            return Err(download::Cancelled.into());
+        }
+    }
+}
+
+/// The models of a freshly loaded catalog, keeping the imported models and
+/// what is known about each model's download. A model the new catalog drops
+/// stays while `pending(id)` says it is still downloading.
+fn merge_catalog(
+    mut previous: HashMap<String, ModelInfo>,
+    entries: Vec<CatalogEntry>,
+    pending: impl Fn(&str) -> bool,
+) -> HashMap<String, ModelInfo> {
+    let mut models: HashMap<String, ModelInfo> = HashMap::new();
+    for entry in entries {
+        let mut model = entry.into_model_info();
+        if let Some(old) = previous.remove(&model.id) {
+            model.is_downloaded = old.is_downloaded;
+            model.is_downloading = old.is_downloading;
+            model.partial_size = old.partial_size;
+        }
+        models.insert(model.id.clone(), model);
+    }
+    for (id, model) in previous {
+        if model.is_custom || pending(&id) {
+            models.entry(id).or_insert(model);
+        }
+    }
+    models
+}
+
+/// Move the directory `source` to `target`, which must not exist or be empty.
+///
+/// `source` is first moved to a staging directory next to `target`, by a
+/// rename or, across file systems, a copy. Only then is the empty `target`
+/// swapped for it, so a failure at any step leaves both where they were.
+/// A copied `source` is removed last.
+fn move_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "models".to_string());
+    let staging = target.with_file_name(format!(".{}.moving", name));
+    let _ = fs::remove_dir_all(&staging);
+
+    let copied = match fs::rename(source, &staging) {
+        Ok(()) => false,
+        Err(e) => {
+            log::info!(
+                "Cannot rename {:?} to {:?} ({}), copying instead",
+                source,
+                staging,
+                e
+            );
+            if let Err(e) = copy_tree(source, &staging) {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+            true
+        }
+    };
+
+    let target_existed = target.is_dir();
+    let placed = if target_existed {
+        fs::remove_dir(target)
+    } else {
+        Ok(())
+    }
+    .and_then(|()| fs::rename(&staging, target));
+    if let Err(e) = placed {
+        if copied {
+            let _ = fs::remove_dir_all(&staging);
+        } else {
+            let _ = fs::rename(&staging, source);
+        }
+        if target_existed {
+            let _ = fs::create_dir(target);
+        }
+        return Err(e);
+    }
+
+    if copied {
+        if let Err(e) = fs::remove_dir_all(source) {
+            log::warn!("Moved models but could not remove {:?}: {}", source, e);
+        }
+    }
+    Ok(())
+}
+
+fn copy_tree(source: &Path, target: &Path) -> std::io::Result<()> {
+    let file_type = fs::symlink_metadata(source)?.file_type();
+    if file_type.is_symlink() {
+        link_path(&fs::read_link(source)?, target)
+    } else if file_type.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, target).map(|_| ())
+    }
+}
+
+#[cfg(unix)]
+fn link_path(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn link_path(source: &Path, target: &Path) -> std::io::Result<()> {
+    if source.is_dir() {
+        std::os::windows::fs::symlink_dir(source, target)
+    } else {
+        std::os::windows::fs::symlink_file(source, target)
+    }
+}
+
+/// Total size in bytes of a file, or of every file under a directory.
+fn path_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| path_size(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or(0)
+    } else {
+        path.metadata().map(|m| m.len()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SHA-256 of "hello world"
+    const HELLO_SHA256: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    #[test]
+    fn verify_file_checks_size_and_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.bin");
+        fs::write(&path, b"hello world").unwrap();
+
+        assert_eq!(sha256_file(&path).unwrap(), HELLO_SHA256);
+        assert!(verify_file(&path, Some(HELLO_SHA256), 11).is_ok());
+        assert!(verify_file(&path, Some(&HELLO_SHA256.to_uppercase()), 0).is_ok());
+        assert!(verify_file(&path, None, 0).is_ok());
+    }
+
+    #[test]
+    fn verify_file_rejects_truncated_or_corrupt_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.bin");
+        fs::write(&path, b"hello wor").unwrap();
+
+        // A truncated resume is caught by size alone
+        assert!(verify_file(&path, None, 11).is_err());
+        assert!(verify_file(&path, Some(HELLO_SHA256), 0).is_err());
+    }
+
+    #[test]
+    fn detect_engine_type_from_layout() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let ggml = dir.path().join("fine-tuned.bin");
+        let mut bytes = GGML_MAGIC.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0; 16]);
+        fs::write(&ggml, bytes).unwrap();
+        assert!(matches!(
+            detect_engine_type(&ggml),
+            Some(EngineType::Whisper)
+        ));
+
+        let parakeet = dir.path().join("parakeet");
+        fs::create_dir(&parakeet).unwrap();
+        fs::write(parakeet.join("encoder-model.onnx"), b"").unwrap();
+        fs::write(parakeet.join("vocab.txt"), b"").unwrap();
+        assert!(matches!(
+            detect_engine_type(&parakeet),
+            Some(EngineType::Parakeet)
+        ));
+
+        let unknown = dir.path().join("notes.txt");
+        fs::write(&unknown, b"not a model").unwrap();
+        assert!(detect_engine_type(&unknown).is_none());
+    }
+
+    #[test]
+    fn copy_path_copies_directories_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("a.onnx"), b"abc").unwrap();
+        fs::write(source.join("nested").join("b.txt"), b"de").unwrap();
+
+        let target = dir.path().join("target");
+        copy_path(&source, &target).unwrap();
+        assert_eq!(
+            fs::read(target.join("nested").join("b.txt")).unwrap(),
+            b"de"
+        );
+        assert_eq!(path_size(&target), 5);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_tree_keeps_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let external = dir.path().join("external.bin");
+        fs::write(&external, b"weights").unwrap();
+
+        let source = dir.path().join("models");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("ggml-small.bin"), b"small").unwrap();
+        link_path(&external, &source.join("custom.bin")).unwrap();
+
+        let target = dir.path().join("moved");
+        copy_tree(&source, &target).unwrap();
+        assert_eq!(fs::read(target.join("ggml-small.bin")).unwrap(), b"small");
+        assert!(fs::symlink_metadata(target.join("custom.bin"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_link(target.join("custom.bin")).unwrap(), external);
+    }
+
+    #[test]
+    fn move_dir_replaces_an_empty_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("models");
+        fs::create_dir(&source).unwrap();
+        fs::write(source.join("ggml-small.bin"), b"small").unwrap();
+        let target = dir.path().join("elsewhere");
+        fs::create_dir(&target).unwrap();
+
+        move_dir(&source, &target).unwrap();
+        assert!(!source.exists());
+        assert_eq!(fs::read(target.join("ggml-small.bin")).unwrap(), b"small");
+        assert!(!dir.path().join(".elsewhere.moving").exists());
+    }
+
+    #[test]
+    fn failed_move_dir_keeps_the_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("elsewhere");
+        fs::create_dir(&target).unwrap();
+
+        assert!(move_dir(&dir.path().join("missing"), &target).is_err());
+        assert!(target.is_dir());
+        assert!(!dir.path().join(".elsewhere.moving").exists());
+    }
+
+    #[test]
+    fn merge_catalog_keeps_download_state() {
+        let entry = |id: &str| CatalogEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            engine: catalog::CatalogEngine::Whisper,
+            filename: format!("{}.bin", id),
+            urls: vec![format!("https://example.com/{}.bin", id)],
+            size_mb: 100,
+            sha256: None,
+            is_directory: false,
+            accuracy_score: 0.0,
+            speed_score: 0.0,
+        };
+        let downloading = |id: &str| ModelInfo {
+            is_downloading: true,
+            partial_size: 4096,
+            ..entry(id).into_model_info()
+        };
+        let custom = ModelInfo {
+            is_custom: true,
+            is_downloaded: true,
+            ..entry("custom-notes").into_model_info()
+        };
+        let previous = HashMap::from([
+            ("small".to_string(), downloading("small")),
+            ("dropped".to_string(), downloading("dropped")),
+            ("stale".to_string(), entry("stale").into_model_info()),
+            ("custom-notes".to_string(), custom),
+        ]);
+
+        let models = merge_catalog(previous, vec![entry("small"), entry("turbo")], |id| {
+            id == "small" || id == "dropped"
+        });
+        let small = &models["small"];
+        assert!(small.is_downloading);
+        assert_eq!(small.partial_size, 4096);
+        assert!(!models["turbo"].is_downloading);
+        assert!(models["dropped"].is_downloading);
+        assert!(models["custom-notes"].is_custom);
+        assert!(!models.contains_key("stale"));
+    }
+
+    /// Accept one connection, read the request head and answer with `response`.
+    fn serve_once(listener: &std::net::TcpListener, response: &[u8]) -> String {
+        use std::io::{BufRead, BufReader};
+
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = String::new();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        // The head ends at the first empty line
+        while reader.read_line(&mut request).unwrap() > 2 {}
+        stream.write_all(response).unwrap();
+        // Give the client the bytes before the connection drops
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        request
+    }
+
+    #[test]
+    fn dropped_download_resumes_where_it_stopped() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            // The connection drops after 4 of the 10 promised bytes
+            serve_once(
+                &listener,
+                b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\nConnection: close\r\n\r\nhell",
+            );
+            serve_once(
+                &listener,
+                b"HTTP/1.1 206 Partial Content\r\nContent-Length: 6\r\nContent-Range: bytes 4-9/10\r\nConnection: close\r\n\r\no worl",
+            )
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let partial = dir.path().join("model.bin.partial");
+        let queue = DownloadQueue::default();
+        let retries = std::sync::atomic::AtomicU32::new(0);
+        let total = tauri::async_runtime::block_on(retry_fetch(
+            "stub",
+            &queue,
+            2,
+            || fetch_http("stub", &url, &partial, &queue, None, |_| {}),
+            |_, _, _| {
+                retries.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            },
+        ))
+        .unwrap();
+
+        let resumed_request = server.join().unwrap().to_ascii_lowercase();
+        assert!(resumed_request.contains("range: bytes=4-"));
+        assert_eq!(total, 10);
+        assert_eq!(retries.into_inner(), 1);
+        assert_eq!(fs::read(&partial).unwrap(), b"hello worl");
+    }
+
+    #[test]
+    fn slugify_model_names() {
+        assert_eq!(
+            slugify("Whisper Medium (legal) v2"),
+            "whisper-medium-legal-v2"
+        );
+        assert_eq!(slugify("  ***  "), "model");
+    }
+}
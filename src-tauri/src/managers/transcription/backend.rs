@@ -0,0 +1,104 @@
+//! Speech backend abstraction.
+//!
+//! Every transcription engine implements [`SpeechBackend`]. The model catalog
+//! names the backend a model needs (`ModelInfo::backend`), and
+//! [`create_backend`] turns that name into an engine instance, so adding an
+//! engine only means writing a backend and registering it in [`BACKENDS`].
+
+use super::parakeet::ParakeetBackend;
+use super::types::Transcript;
+use super::whisper::WhisperBackend;
+use crate::settings::WhisperDecodingParams;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// What a backend can do, used to adapt requests and to inform the UI.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BackendCapabilities {
+    /// Decodes audio incrementally as it arrives, instead of re-running on a window.
+    pub streaming: bool,
+    pub segment_timestamps: bool,
+    pub word_timestamps: bool,
+    /// Can translate speech to English.
+    pub translation: bool,
+    /// Accepts an explicit source language rather than only detecting it.
+    pub language_selection: bool,
+    /// Favours custom words while decoding; other backends only get them
+    /// through the fuzzy correction of the finished transcript.
+    pub vocabulary_biasing: bool,
+    /// Language codes the backend can transcribe; empty when not restricted.
+    pub languages: Vec<String>,
+}
+
+/// Per-call transcription options, already resolved from the user settings.
+#[derive(Debug, Clone, Default)]
+pub struct TranscribeOptions {
+    /// ISO language code, or `None` to let the engine detect it.
+    pub language: Option<String>,
+    /// Report how sure the engine is of the language it detected, for
+    /// engines where that costs extra work.
+    pub language_confidence: bool,
+    pub translate: bool,
+    /// Vocabulary the engine should favour.
+    pub custom_words: Vec<String>,
+    /// Decoding parameters of the loaded model, used by the whisper backend.
+    pub whisper: WhisperDecodingParams,
+    /// Set while running to ask the backend to stop early; backends that
+    /// can't interrupt their engine simply finish the job.
+    pub interrupt: Arc<AtomicBool>,
+}
+
+pub trait SpeechBackend: Send {
+    /// Identifier matching `ModelInfo::backend`.
+    fn id(&self) -> &'static str;
+
+    fn capabilities(&self) -> BackendCapabilities;
+
+    fn load(&mut self, model_path: &Path) -> Result<()>;
+
+    fn unload(&mut self);
+
+    fn transcribe(&mut self, audio: &[f32], options: &TranscribeOptions) -> Result<Transcript>;
+}
+
+type BackendFactory = fn() -> Box<dyn SpeechBackend>;
+
+/// Registered backends, keyed by the id used in the model catalog.
+const BACKENDS: &[(&str, BackendFactory)] = &[("whisper", new_whisper), ("parakeet", new_parakeet)];
+
+fn new_whisper() -> Box<dyn SpeechBackend> {
+    Box::new(WhisperBackend::new())
+}
+
+fn new_parakeet() -> Box<dyn SpeechBackend> {
+    Box::new(ParakeetBackend::new())
+}
+
+/// Create an unloaded backend for the given catalog id.
+pub fn create_backend(id: &str) -> Result<Box<dyn SpeechBackend>> {
+    BACKENDS
+        .iter()
+        .find(|(backend_id, _)| *backend_id == id)
+        .map(|(_, factory)| factory())
+        .ok_or_else(|| anyhow::anyhow!("Unknown transcription backend: {}", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_backends_report_their_id() {
+        for (id, factory) in BACKENDS {
+            assert_eq!(factory().id(), *id);
+        }
+    }
+
+    #[test]
+    fn unknown_backend_is_an_error() {
+        assert!(create_backend("does-not-exist").is_err());
+    }
+}
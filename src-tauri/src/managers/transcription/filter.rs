@@ -0,0 +1,321 @@
+//! Filter for text whisper invents when there is little or no speech.
+//!
+//! Given silence or breathing, whisper tends to emit subtitle credits
+//! ("Thanks for watching."), non-speech markers like `[BLANK_AUDIO]`, or one
+//! sentence repeated until the window ends. Every change made here is logged
+//! at info level so it can be audited afterwards.
+
+use super::types::Transcript;
+use crate::settings::AppSettings;
+use log::info;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Longest phrase, in words, that is checked for repetition loops.
+const MAX_LOOP_WORDS: usize = 12;
+/// A phrase must repeat at least this many times in a row to count as a loop...
+const MIN_LOOP_REPEATS: usize = 3;
+/// ...and the loop must span at least this many words, so "no, no, no" survives.
+const MIN_LOOP_SPAN: usize = 6;
+
+/// Sound descriptions whisper puts in brackets or parentheses.
+const NON_SPEECH_TAGS: &str = r"music|applause|laugh\w*|silence|inaudible|blank_audio|no_speech|sigh\w*|cough\w*|breath\w*|noise|static";
+
+/// Non-speech markers in brackets or parentheses, and music notes. Other
+/// bracketed text, such as "[sic]", is left alone.
+static NON_SPEECH_TOKEN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)\[\s*[a-z _-]*(?:{NON_SPEECH_TAGS})\s*\]|[♪♫]+|\(\s*[a-z _-]*(?:{NON_SPEECH_TAGS})\s*\)"
+    ))
+    .unwrap()
+});
+
+pub(super) struct HallucinationFilter<'a> {
+    phrases: &'a [String],
+    min_speech_ms: i64,
+}
+
+impl<'a> HallucinationFilter<'a> {
+    /// The filter configured in `settings`, or `None` if it is disabled.
+    pub fn from_settings(settings: &'a AppSettings) -> Option<Self> {
+        settings.hallucination_filter_enabled.then(|| Self {
+            phrases: &settings.hallucination_phrases,
+            min_speech_ms: settings.hallucination_min_speech_ms as i64,
+        })
+    }
+
+    /// Clean up `transcript`.
+    ///
+    /// `speech_ms` is how much speech the VAD measured in the recording, when
+    /// known. Below the configured minimum, the whole result is dropped.
+    pub fn apply(&self, mut transcript: Transcript, speech_ms: Option<i64>) -> Transcript {
+        if transcript.is_empty() {
+            return transcript;
+        }
+
+        if let Some(speech_ms) = speech_ms.filter(|ms| *ms < self.min_speech_ms) {
+            info!(
+                "Hallucination filter: dropped {:?}, VAD measured only {}ms of speech (minimum {}ms)",
+                transcript.text, speech_ms, self.min_speech_ms
+            );
+            return Transcript {
+                language: transcript.language,
+                language_probability: transcript.language_probability,
+                ..Transcript::default()
+            };
+        }
+
+        transcript = self.run("non-speech tokens", transcript, strip_non_speech_tokens);
+
+        let looped_text = transcript.text.clone();
+        transcript = self.run("repetition loop", transcript, collapse_repetitions);
+        if transcript.text != looped_text {
+            // A loop that spans segments leaves one copy of the segment each
+            let count = transcript.segments.len();
+            transcript
+                .segments
+                .dedup_by(|b, a| normalize(&a.text) == normalize(&b.text));
+            if transcript.segments.len() < count {
+                info!(
+                    "Hallucination filter (repetition loop): removed {} repeated segments",
+                    count - transcript.segments.len()
+                );
+            }
+        }
+
+        transcript = self.run("known phrase", transcript, |text| {
+            drop_phrases(text, self.phrases)
+        });
+
+        transcript
+            .segments
+            .retain(|segment| !segment.text.trim().is_empty());
+        transcript
+    }
+
+    /// Apply one text filter to the transcript, logging what it changed.
+    fn run<F>(&self, name: &str, transcript: Transcript, filter: F) -> Transcript
+    where
+        F: Fn(&str) -> String,
+    {
+        let filtered = filter(&transcript.text);
+        if filtered == transcript.text {
+            return transcript;
+        }
+        info!(
+            "Hallucination filter ({}): {:?} -> {:?}",
+            name, transcript.text, filtered
+        );
+        transcript.map_text(filter)
+    }
+}
+
+/// Remove bracketed non-speech markers such as `[BLANK_AUDIO]` or `(music)`.
+fn strip_non_speech_tokens(text: &str) -> String {
+    let stripped = NON_SPEECH_TOKEN.replace_all(text, " ");
+    if stripped == text {
+        return text.to_string();
+    }
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Keep a single copy of any phrase repeated back to back.
+fn collapse_repetitions(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = words.iter().map(|w| normalize(w)).collect();
+
+    let mut kept = Vec::with_capacity(words.len());
+    let mut collapsed = false;
+    let mut i = 0;
+    while i < words.len() {
+        let max_len = MAX_LOOP_WORDS.min((words.len() - i) / MIN_LOOP_REPEATS);
+        let found = (1..=max_len).find_map(|n| {
+            let mut repeats = 1;
+            while i + (repeats + 1) * n <= words.len()
+                && keys[i..i + n] == keys[i + repeats * n..i + (repeats + 1) * n]
+            {
+                repeats += 1;
+            }
+            (repeats >= MIN_LOOP_REPEATS && repeats * n >= MIN_LOOP_SPAN).then_some((n, repeats))
+        });
+
+        match found {
+            Some((n, repeats)) => {
+                kept.extend_from_slice(&words[i..i + n]);
+                i += n * repeats;
+                collapsed = true;
+            }
+            None => {
+                kept.push(words[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if collapsed {
+        kept.join(" ")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Remove every sentence that is one of `phrases`, ignoring case and punctuation.
+fn drop_phrases(text: &str, phrases: &[String]) -> String {
+    let phrases: Vec<String> = phrases
+        .iter()
+        .map(|p| normalize(p))
+        .filter(|p| !p.is_empty())
+        .collect();
+    if phrases.is_empty() {
+        return text.to_string();
+    }
+
+    let sentences = split_sentences(text);
+    let kept: Vec<&str> = sentences
+        .iter()
+        .copied()
+        .filter(|sentence| !phrases.contains(&normalize(sentence)))
+        .collect();
+    if kept.len() == sentences.len() {
+        text.to_string()
+    } else {
+        kept.join(" ")
+    }
+}
+
+/// Split after sentence-ending punctuation, keeping the punctuation.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let at_boundary = chars.peek().map_or(true, |(_, next)| next.is_whitespace());
+        if matches!(c, '.' | '!' | '?' | '…' | '。' | '！' | '？') && at_boundary {
+            let end = i + c.len_utf8();
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+/// Lowercase with punctuation removed and whitespace collapsed.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::transcription::TranscriptSegment;
+
+    fn segment(text: &str, start_ms: i64) -> TranscriptSegment {
+        TranscriptSegment {
+            text: text.to_string(),
+            start_ms,
+            end_ms: start_ms + 1_000,
+            probability: None,
+            words: Vec::new(),
+        }
+    }
+
+    fn transcript(segments: &[&str]) -> Transcript {
+        Transcript {
+            text: segments.join(" "),
+            segments: segments
+                .iter()
+                .enumerate()
+                .map(|(i, text)| segment(text, i as i64 * 1_000))
+                .collect(),
+            ..Transcript::default()
+        }
+    }
+
+    fn filter(phrases: &[String]) -> HallucinationFilter<'_> {
+        HallucinationFilter {
+            phrases,
+            min_speech_ms: 300,
+        }
+    }
+
+    #[test]
+    fn non_speech_tokens_are_removed() {
+        assert_eq!(
+            strip_non_speech_tokens("[BLANK_AUDIO] Hello (upbeat music) there ♪"),
+            "Hello there"
+        );
+        assert_eq!(
+            strip_non_speech_tokens("[ Silence ] Call me (maybe) later"),
+            "Call me (maybe) later"
+        );
+        assert_eq!(
+            strip_non_speech_tokens("He wrote \"their [sic] car\" [1]"),
+            "He wrote \"their [sic] car\" [1]"
+        );
+    }
+
+    #[test]
+    fn repetition_loops_collapse_to_one_copy() {
+        assert_eq!(
+            collapse_repetitions("I think so. I think so. I think so. I think so."),
+            "I think so."
+        );
+        assert_eq!(
+            collapse_repetitions("Okay, the the the the the the end"),
+            "Okay, the end"
+        );
+        assert_eq!(collapse_repetitions("no, no, no"), "no, no, no");
+    }
+
+    #[test]
+    fn known_phrases_are_dropped_as_whole_sentences() {
+        let phrases = vec!["Thanks for watching".to_string()];
+        assert_eq!(
+            drop_phrases("See you tomorrow. Thanks for watching!", &phrases),
+            "See you tomorrow."
+        );
+        assert_eq!(
+            drop_phrases("Thanks for watching the kids.", &phrases),
+            "Thanks for watching the kids."
+        );
+    }
+
+    #[test]
+    fn apply_filters_text_and_segments() {
+        let phrases = vec!["Thank you for watching.".to_string()];
+        let result = filter(&phrases).apply(
+            transcript(&[
+                "[BLANK_AUDIO]",
+                "See you next time.",
+                "See you next time.",
+                "See you next time.",
+                "Thank you for watching.",
+            ]),
+            None,
+        );
+        assert_eq!(result.text, "See you next time.");
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].text, "See you next time.");
+        assert_eq!(result.segments[0].start_ms, 1_000);
+    }
+
+    #[test]
+    fn apply_drops_everything_without_speech() {
+        let mut input = transcript(&["Bye."]);
+        input.language = Some("en".to_string());
+        let result = filter(&[]).apply(input.clone(), Some(90));
+        assert!(result.is_empty());
+        assert!(result.segments.is_empty());
+        assert_eq!(result.language.as_deref(), Some("en"));
+
+        assert_eq!(filter(&[]).apply(input.clone(), Some(900)), input);
+    }
+}
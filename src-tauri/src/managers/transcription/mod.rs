@@ -0,0 +1,1061 @@
+mod backend;
+mod filter;
+mod parakeet;
+mod remote;
+mod scheduler;
+mod streaming;
+mod types;
+mod whisper;
+
+pub use backend::{BackendCapabilities, SpeechBackend, TranscribeOptions};
+pub use remote::{RemoteBackend, RemoteConfig};
+pub use scheduler::{CancelToken, JobInfo, JobKind, JobState};
+pub use streaming::StreamingProgress;
+pub use types::{mean_probability, Transcript, TranscriptSegment, TranscriptWord};
+
+use crate::audio_toolkit::apply_custom_word_spans;
+use crate::managers::model::ModelManager;
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout, TranscriptionProfile};
+use anyhow::Result;
+use filter::HallucinationFilter;
+use log::{debug, error, info, warn};
+use scheduler::{JobOutput, JobQueue};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use streaming::StreamingState;
+use tauri::{AppHandle, Emitter};
+
+/// Window length before streaming slides past committed text, until the
+/// adaptive limit lowers it.
+const DEFAULT_STREAMING_WINDOW_SAMPLES: usize = 16000 * 20;
+
+/// whisper is unreliable on very short input, so final tails are padded to
+/// the same length the recorder pads short recordings to.
+const MIN_FINAL_TAIL_SAMPLES: usize = 16000 * 5 / 4;
+
+fn samples_to_ms(samples: usize) -> i64 {
+    (samples / 16) as i64
+}
+
+/// Timings of one [`TranscriptionManager::benchmark_model`] run.
+pub struct BenchmarkRun {
+    pub load_ms: i64,
+    pub transcribe_ms: i64,
+    pub transcript: Transcript,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ModelStateEvent {
+    pub event_type: String,
+    pub model_id: Option<String>,
+    pub model_name: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct TranscriptionManager {
+    engine: Arc<Mutex<Option<Box<dyn SpeechBackend>>>>,
+    model_manager: Arc<ModelManager>,
+    app_handle: AppHandle,
+    current_model_id: Arc<Mutex<Option<String>>>,
+    last_activity: Arc<AtomicU64>,
+    shutdown_signal: Arc<AtomicBool>,
+    watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+    is_loading: Arc<Mutex<bool>>,
+    loading_condvar: Arc<Condvar>,
+    /// Model asked for while another one was loading, loaded next.
+    pending_model: Arc<Mutex<Option<String>>>,
+    streaming_buffer: Arc<Mutex<Vec<f32>>>,
+    /// Committed and tentative words of the current streaming session.
+    streaming_state: Arc<Mutex<StreamingState>>,
+    last_partial_update: Arc<Mutex<std::time::Instant>>,
+    streaming_in_progress: Arc<AtomicBool>,
+    /// Adaptive streaming window length - dynamically adjusted based on transcription performance
+    /// Starts at None (DEFAULT_STREAMING_WINDOW_SAMPLES), then caps when transcription exceeds 800ms
+    adaptive_max_samples: Arc<Mutex<Option<usize>>>,
+    /// Generation counter for the current streaming session, used to discard
+    /// stale streaming chunks that belong to a previous recording.
+    active_generation: Arc<AtomicU64>,
+    /// Pending and running transcription jobs, served by a single worker.
+    job_queue: Arc<Mutex<JobQueue>>,
+    job_condvar: Arc<Condvar>,
+    /// Raised by [`shutdown`](Self::shutdown) to stop the job worker.
+    jobs_closed: Arc<AtomicBool>,
+    worker_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+    /// Profile of the binding that started the current dictation, applied to
+    /// dictation and preview jobs.
+    dictation_profile: Arc<Mutex<Option<TranscriptionProfile>>>,
+}
+
+impl TranscriptionManager {
+    pub fn new(app_handle: &AppHandle, model_manager: Arc<ModelManager>) -> Result<Self> {
+        let manager = Self {
+            engine: Arc::new(Mutex::new(None)),
+            model_manager,
+            app_handle: app_handle.clone(),
+            current_model_id: Arc::new(Mutex::new(None)),
+            last_activity: Arc::new(AtomicU64::new(
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64,
+            )),
+            shutdown_signal: Arc::new(AtomicBool::new(false)),
+            watcher_handle: Arc::new(Mutex::new(None)),
+            is_loading: Arc::new(Mutex::new(false)),
+            loading_condvar: Arc::new(Condvar::new()),
+            pending_model: Arc::new(Mutex::new(None)),
+            streaming_buffer: Arc::new(Mutex::new(Vec::new())),
+            streaming_state: Arc::new(Mutex::new(StreamingState::new())),
+            last_partial_update: Arc::new(Mutex::new(std::time::Instant::now())),
+            streaming_in_progress: Arc::new(AtomicBool::new(false)),
+            adaptive_max_samples: Arc::new(Mutex::new(None)),
+            active_generation: Arc::new(AtomicU64::new(0)),
+            job_queue: Arc::new(Mutex::new(JobQueue::new())),
+            job_condvar: Arc::new(Condvar::new()),
+            jobs_closed: Arc::new(AtomicBool::new(false)),
+            worker_handle: Arc::new(Mutex::new(None)),
+            dictation_profile: Arc::new(Mutex::new(None)),
+        };
+
+        // Start the job worker. It owns the engine until the app exits, so it
+        // stops on `shutdown` rather than on the idle watcher's signal.
+        {
+            let manager_cloned = manager.clone();
+            let handle = thread::spawn(move || manager_cloned.run_jobs());
+            *manager.worker_handle.lock().unwrap() = Some(handle);
+        }
+
+        // Start the idle watcher
+        {
+            let app_handle_cloned = app_handle.clone();
+            let manager_cloned = manager.clone();
+            let shutdown_signal = manager.shutdown_signal.clone();
+            let handle = thread::spawn(move || {
+                while !shutdown_signal.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_secs(10)); // Check every 10 seconds
+
+                    // Check shutdown signal again after sleep
+                    if shutdown_signal.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    let settings = get_settings(&app_handle_cloned);
+                    let timeout_seconds = settings.model_unload_timeout.to_seconds();
+
+                    if let Some(limit_seconds) = timeout_seconds {
+                        // Skip polling-based unloading for immediate timeout since it's handled directly in transcribe()
+                        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
+                            continue;
+                        }
+
+                        let last = manager_cloned.last_activity.load(Ordering::Relaxed);
+                        let now_ms = SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap()
+                            .as_millis() as u64;
+
+                        if now_ms.saturating_sub(last) > limit_seconds * 1000 {
+                            // idle -> unload
+                            if manager_cloned.is_model_loaded() {
+                                let unload_start = std::time::Instant::now();
+                                debug!("Starting to unload model due to inactivity");
+
+                                if let Ok(()) = manager_cloned.unload_model() {
+                                    let _ = app_handle_cloned.emit(
+                                        "model-state-changed",
+                                        ModelStateEvent {
+                                            event_type: "unloaded".to_string(),
+                                            model_id: None,
+                                            model_name: None,
+                                            error: None,
+                                        },
+                                    );
+                                    let unload_duration = unload_start.elapsed();
+                                    debug!(
+                                        "Model unloaded due to inactivity (took {}ms)",
+                                        unload_duration.as_millis()
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+                debug!("Idle watcher thread shutting down gracefully");
+            });
+            *manager.watcher_handle.lock().unwrap() = Some(handle);
+        }
+
+        Ok(manager)
+    }
+
+    pub fn is_model_loaded(&self) -> bool {
+        let engine = self.engine.lock().unwrap();
+        engine.is_some()
+    }
+
+    pub fn unload_model(&self) -> Result<()> {
+        let unload_start = std::time::Instant::now();
+        debug!("Starting to unload model");
+
+        {
+            let mut engine = self.engine.lock().unwrap();
+            if let Some(ref mut loaded_engine) = *engine {
+                loaded_engine.unload();
+            }
+            *engine = None; // Drop the engine to free memory
+        }
+        {
+            let mut current_model = self.current_model_id.lock().unwrap();
+            *current_model = None;
+        }
+
+        // Emit unloaded event
+        let _ = self.app_handle.emit(
+            "model-state-changed",
+            ModelStateEvent {
+                event_type: "unloaded".to_string(),
+                model_id: None,
+                model_name: None,
+                error: None,
+            },
+        );
+
+        let unload_duration = unload_start.elapsed();
+        debug!(
+            "Model unloaded manually (took {}ms)",
+            unload_duration.as_millis()
+        );
+        Ok(())
+    }
+
+    pub fn load_model(&self, model_id: &str) -> Result<()> {
+        let load_start = std::time::Instant::now();
+        debug!("Starting to load model: {}", model_id);
+
+        // Emit loading started event
+        let _ = self.app_handle.emit(
+            "model-state-changed",
+            ModelStateEvent {
+                event_type: "loading_started".to_string(),
+                model_id: Some(model_id.to_string()),
+                model_name: None,
+                error: None,
+            },
+        );
+
+        let model_info = self
+            .model_manager
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+
+        if !model_info.is_downloaded {
+            let error_msg = "Model not downloaded";
+            let _ = self.app_handle.emit(
+                "model-state-changed",
+                ModelStateEvent {
+                    event_type: "loading_failed".to_string(),
+                    model_id: Some(model_id.to_string()),
+                    model_name: Some(model_info.name.clone()),
+                    error: Some(error_msg.to_string()),
+                },
+            );
+            return Err(anyhow::anyhow!(error_msg));
+        }
+
+        let model_path = self.model_manager.get_model_path(model_id)?;
+
+        // Create the backend the catalog declares for this model
+        let backend_id = model_info.backend.clone().ok_or_else(|| {
+            anyhow::anyhow!("Model {} cannot be used for transcription", model_id)
+        })?;
+        let mut loaded_engine = backend::create_backend(&backend_id)?;
+        loaded_engine.load(&model_path).map_err(|e| {
+            let error_msg = format!("Failed to load {} model {}: {}", backend_id, model_id, e);
+            let _ = self.app_handle.emit(
+                "model-state-changed",
+                ModelStateEvent {
+                    event_type: "loading_failed".to_string(),
+                    model_id: Some(model_id.to_string()),
+                    model_name: Some(model_info.name.clone()),
+                    error: Some(error_msg.clone()),
+                },
+            );
+            anyhow::anyhow!(error_msg)
+        })?;
+
+        // Update the current engine and model ID
+        {
+            let mut engine = self.engine.lock().unwrap();
+            *engine = Some(loaded_engine);
+        }
+        {
+            let mut current_model = self.current_model_id.lock().unwrap();
+            *current_model = Some(model_id.to_string());
+        }
+
+        // Emit loading completed event
+        let _ = self.app_handle.emit(
+            "model-state-changed",
+            ModelStateEvent {
+                event_type: "loading_completed".to_string(),
+                model_id: Some(model_id.to_string()),
+                model_name: Some(model_info.name.clone()),
+                error: None,
+            },
+        );
+
+        let load_duration = load_start.elapsed();
+        debug!(
+            "Successfully loaded transcription model: {} (took {}ms)",
+            model_id,
+            load_duration.as_millis()
+        );
+        Ok(())
+    }
+
+    /// Kicks off the model loading in a background thread if it's not already loaded
+    pub fn initiate_model_load(&self) {
+        self.initiate_load(None);
+    }
+
+    /// Like [`initiate_model_load`](Self::initiate_model_load), but replaces
+    /// the loaded model if it isn't `model_id`. A model asked for while
+    /// another one is loading is loaded right after it.
+    fn initiate_load(&self, model_id: Option<String>) {
+        let mut is_loading = self.is_loading.lock().unwrap();
+        if *is_loading {
+            if model_id.is_some() {
+                *self.pending_model.lock().unwrap() = model_id;
+            }
+            return;
+        }
+        let loaded = match &model_id {
+            Some(id) => self.get_current_model().as_ref() == Some(id),
+            None => self.is_model_loaded(),
+        };
+        if loaded {
+            return;
+        }
+
+        *is_loading = true;
+        let self_clone = self.clone();
+        thread::spawn(move || {
+            let mut model_id =
+                model_id.unwrap_or_else(|| get_settings(&self_clone.app_handle).selected_model);
+            loop {
+                if self_clone.get_current_model().as_ref() != Some(&model_id) {
+                    if let Err(e) = self_clone.load_model(&model_id) {
+                        error!("Failed to load model: {}", e);
+                    }
+                }
+                // Checked under the loading lock, so a request made meanwhile isn't lost
+                let mut is_loading = self_clone.is_loading.lock().unwrap();
+                if let Some(next) = self_clone.pending_model.lock().unwrap().take() {
+                    model_id = next;
+                    continue;
+                }
+                *is_loading = false;
+                self_clone.loading_condvar.notify_all();
+                break;
+            }
+        });
+    }
+
+    /// Wait for a load in progress, then make sure `model_id` is the loaded
+    /// model, swapping it in if another one is.
+    fn ensure_model(&self, model_id: &str) -> Result<()> {
+        let mut is_loading = self.is_loading.lock().unwrap();
+        while *is_loading {
+            is_loading = self.loading_condvar.wait(is_loading).unwrap();
+        }
+        if self.is_model_loaded() && self.get_current_model().as_deref() == Some(model_id) {
+            return Ok(());
+        }
+        *is_loading = true;
+        drop(is_loading);
+
+        debug!("Loading model {} for the next job", model_id);
+        let result = self.load_model(model_id);
+
+        *self.is_loading.lock().unwrap() = false;
+        self.loading_condvar.notify_all();
+        if let Some(next) = self.pending_model.lock().unwrap().take() {
+            self.initiate_load(Some(next));
+        }
+        result
+    }
+
+    /// Use `profile` for the dictation about to start, and load its model.
+    pub fn set_dictation_profile(&self, profile: Option<TranscriptionProfile>) {
+        let mut settings = get_settings(&self.app_handle);
+        if let Some(profile) = &profile {
+            debug!("Dictating with profile '{}'", profile.name);
+            profile.apply(&mut settings);
+        }
+        *self.dictation_profile.lock().unwrap() = profile;
+        self.initiate_load(Some(settings.selected_model));
+    }
+
+    /// Current settings, with the dictation profile applied for jobs it covers.
+    fn settings_for(&self, kind: JobKind) -> AppSettings {
+        let mut settings = get_settings(&self.app_handle);
+        if matches!(kind, JobKind::Dictation | JobKind::Preview) {
+            if let Some(profile) = &*self.dictation_profile.lock().unwrap() {
+                profile.apply(&mut settings);
+            }
+        }
+        if kind == JobKind::Benchmark {
+            // The engine's own output, in the spoken language
+            settings.translate_to_english = false;
+        }
+        settings
+    }
+
+    pub fn get_current_model(&self) -> Option<String> {
+        let current_model = self.current_model_id.lock().unwrap();
+        current_model.clone()
+    }
+
+    /// Load a model afresh and transcribe `audio` with it, timing both steps.
+    ///
+    /// Runs as a queued job on the shared engine, which is left holding the
+    /// benchmarked model. Translation and post-transcription filters are
+    /// skipped, so the result is the engine's own output in the spoken language.
+    pub fn benchmark_model(
+        &self,
+        model_id: &str,
+        audio: Vec<f32>,
+        language: Option<&str>,
+    ) -> Result<BenchmarkRun> {
+        let output = self.submit(
+            audio,
+            JobKind::Benchmark,
+            model_id.to_string(),
+            Some(language.unwrap_or("auto").to_string()),
+            &CancelToken::new(),
+        )?;
+        Ok(BenchmarkRun {
+            load_ms: output.load_ms,
+            transcribe_ms: output.transcribe_ms,
+            transcript: output.transcript,
+        })
+    }
+
+    /// Load the selected model in the background, for after jobs that
+    /// swapped in another one.
+    pub fn restore_selected_model(&self) {
+        self.initiate_load(Some(get_settings(&self.app_handle).selected_model));
+    }
+
+    /// Capabilities of the loaded backend, if a model is loaded.
+    pub fn get_capabilities(&self) -> Option<BackendCapabilities> {
+        let engine = self.engine.lock().unwrap();
+        engine.as_ref().map(|e| e.capabilities())
+    }
+
+    /// Queue a transcription and wait for its finished result.
+    ///
+    /// The audio didn't come through the recorder, so the VAD measures its
+    /// speech here for the hallucination filter.
+    pub fn transcribe(&self, audio: Vec<f32>, kind: JobKind) -> Result<Transcript> {
+        self.transcribe_cancellable(audio, kind, &CancelToken::new())
+    }
+
+    /// Like [`transcribe`](Self::transcribe), but cancelled along with `cancel`.
+    pub fn transcribe_cancellable(
+        &self,
+        audio: Vec<f32>,
+        kind: JobKind,
+        cancel: &CancelToken,
+    ) -> Result<Transcript> {
+        let settings = self.settings_for(kind);
+        let speech_ms = if settings.hallucination_filter_enabled {
+            crate::managers::audio::measure_speech_ms(&self.app_handle, &audio)
+        } else {
+            None
+        };
+        let transcript = self.run_job(audio, kind, cancel)?;
+        Ok(finish_transcript(transcript, &settings, speech_ms))
+    }
+
+    /// Queue a job and wait for the engine's own output.
+    fn run_job(&self, audio: Vec<f32>, kind: JobKind, cancel: &CancelToken) -> Result<Transcript> {
+        // Pinned now, so a later change of model or profile doesn't affect it
+        let model_id = self.settings_for(kind).selected_model;
+        self.submit(audio, kind, model_id, None, cancel)
+            .map(|output| output.transcript)
+    }
+
+    /// Queue a job on `model_id` and wait for it. `language` overrides the
+    /// spoken language setting for this job only.
+    fn submit(
+        &self,
+        audio: Vec<f32>,
+        kind: JobKind,
+        model_id: String,
+        language: Option<String>,
+        cancel: &CancelToken,
+    ) -> Result<JobOutput> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        let id = {
+            // Checked under the queue lock, so `shutdown` answers every job
+            // that got in
+            let mut queue = self.job_queue.lock().unwrap();
+            if self.jobs_closed.load(Ordering::SeqCst) {
+                return Err(anyhow::anyhow!("Transcription worker stopped"));
+            }
+            queue.push(kind, audio, model_id, language, cancel.clone(), reply_tx)
+        };
+        debug!("Queued {:?} transcription job {}", kind, id);
+        self.job_condvar.notify_all();
+        self.emit_job_queue();
+
+        reply_rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("Transcription worker stopped"))?
+    }
+
+    /// Queued and running jobs, running job first.
+    pub fn get_job_queue(&self) -> Vec<JobInfo> {
+        self.job_queue.lock().unwrap().snapshot()
+    }
+
+    /// Cancel a queued or running job. Returns false if the id is unknown.
+    pub fn cancel_job(&self, id: u64) -> bool {
+        let cancelled = self.job_queue.lock().unwrap().cancel(id);
+        if cancelled {
+            info!("Cancelled transcription job {}", id);
+            self.emit_job_queue();
+        }
+        cancelled
+    }
+
+    /// Cancel every queued or running job of the given kinds.
+    pub fn cancel_jobs(&self, kinds: &[JobKind]) {
+        self.job_queue.lock().unwrap().cancel_kinds(kinds);
+        self.emit_job_queue();
+    }
+
+    /// Cancel `token`, stopping its running job now rather than letting it finish.
+    pub fn cancel_token(&self, token: &CancelToken) {
+        token.cancel();
+        self.job_queue.lock().unwrap().drop_cancelled();
+        self.emit_job_queue();
+    }
+
+    /// Cancel every job and stop the worker, for when the app exits.
+    pub fn shutdown(&self) {
+        let mut queue = self.job_queue.lock().unwrap();
+        self.jobs_closed.store(true, Ordering::SeqCst);
+        queue.cancel_kinds(&[
+            JobKind::Dictation,
+            JobKind::Preview,
+            JobKind::Retranscription,
+            JobKind::Meeting,
+            JobKind::File,
+            JobKind::Benchmark,
+        ]);
+        // Released before joining, as the worker needs it to notice
+        drop(queue);
+        self.job_condvar.notify_all();
+
+        if let Some(handle) = self.worker_handle.lock().unwrap().take() {
+            if let Err(e) = handle.join() {
+                warn!("Failed to join transcription worker: {:?}", e);
+            }
+        }
+    }
+
+    fn emit_job_queue(&self) {
+        let _ = self
+            .app_handle
+            .emit("transcription-queue-changed", self.get_job_queue());
+    }
+
+    /// Worker loop: run queued jobs one at a time, highest priority first,
+    /// until [`shutdown`](Self::shutdown).
+    fn run_jobs(&self) {
+        loop {
+            let job = {
+                let mut queue = self.job_queue.lock().unwrap();
+                loop {
+                    if self.jobs_closed.load(Ordering::SeqCst) {
+                        debug!("Transcription worker shutting down");
+                        return;
+                    }
+                    if let Some(job) = queue.pop() {
+                        break job;
+                    }
+                    queue = self.job_condvar.wait(queue).unwrap();
+                }
+            };
+            self.emit_job_queue();
+
+            debug!("Running {:?} transcription job {}", job.kind, job.id);
+            let result = self.transcribe_with(
+                &job.audio,
+                job.kind,
+                &job.model_id,
+                job.language.as_deref(),
+                &job.interrupt,
+            );
+
+            self.job_queue.lock().unwrap().complete(job, result);
+            self.emit_job_queue();
+        }
+    }
+
+    fn transcribe_with(
+        &self,
+        audio: &[f32],
+        kind: JobKind,
+        model_id: &str,
+        language: Option<&str>,
+        interrupt: &Arc<AtomicBool>,
+    ) -> Result<JobOutput> {
+        // Update last activity timestamp
+        self.last_activity.store(
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+            Ordering::Relaxed,
+        );
+
+        let st = std::time::Instant::now();
+
+        debug!("Audio vector length: {}", audio.len());
+
+        if audio.len() == 0 {
+            debug!("Empty audio vector");
+            return Ok(JobOutput::default());
+        }
+
+        // Get current settings for configuration
+        let mut settings = self.settings_for(kind);
+        if let Some(language) = language {
+            settings.selected_language = language.to_string();
+        }
+
+        if kind == JobKind::Benchmark && self.is_model_loaded() {
+            // Benchmarks time the load too
+            self.unload_model()?;
+        }
+
+        let output = if kind.allows_remote() && settings.remote_transcription_enabled {
+            match self.transcribe_remote(audio, &settings, interrupt) {
+                Ok(transcript) => JobOutput {
+                    transcript,
+                    load_ms: 0,
+                    transcribe_ms: st.elapsed().as_millis() as i64,
+                },
+                Err(e) => {
+                    warn!(
+                        "Remote transcription failed, falling back to the local model: {}",
+                        e
+                    );
+                    let _ = self
+                        .app_handle
+                        .emit("remote-transcription-failed", e.to_string());
+                    self.transcribe_local(audio, &settings, kind, model_id, interrupt)?
+                }
+            }
+        } else {
+            self.transcribe_local(audio, &settings, kind, model_id, interrupt)?
+        };
+
+        let et = std::time::Instant::now();
+        let translation_note = if settings.translate_to_english {
+            " (translated)"
+        } else {
+            ""
+        };
+        info!(
+            "Transcription completed in {}ms{}",
+            (et - st).as_millis(),
+            translation_note
+        );
+
+        // Check if we should immediately unload the model after transcription
+        if settings.model_unload_timeout == ModelUnloadTimeout::Immediately {
+            info!("Immediately unloading model after transcription");
+            if let Err(e) = self.unload_model() {
+                error!("Failed to immediately unload model: {}", e);
+            }
+        }
+
+        Ok(JobOutput {
+            transcript: output.transcript.trimmed(),
+            ..output
+        })
+    }
+
+    fn transcribe_remote(
+        &self,
+        audio: &[f32],
+        settings: &AppSettings,
+        interrupt: &Arc<AtomicBool>,
+    ) -> Result<Transcript> {
+        let mut backend = RemoteBackend::new(RemoteConfig::from_settings(settings)?);
+        let options = transcribe_options(settings, &backend, None, interrupt);
+        backend.transcribe(audio, &options)
+    }
+
+    fn transcribe_local(
+        &self,
+        audio: &[f32],
+        settings: &AppSettings,
+        kind: JobKind,
+        model_id: &str,
+        interrupt: &Arc<AtomicBool>,
+    ) -> Result<JobOutput> {
+        // Only this worker transcribes, so the model can't change until it's done
+        let load_start = std::time::Instant::now();
+        self.ensure_model(model_id)
+            .map_err(|e| anyhow::anyhow!("Model is not loaded for transcription: {}", e))?;
+        let load_ms = load_start.elapsed().as_millis() as i64;
+
+        // Perform transcription with the appropriate engine
+        let mut engine_guard = self.engine.lock().unwrap();
+        let engine = engine_guard.as_mut().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model failed to load after auto-load attempt. Please check your model settings."
+            )
+        })?;
+
+        let backend_id = engine.id();
+        let mut options = transcribe_options(settings, engine.as_ref(), Some(model_id), interrupt);
+        // Previews are shown and thrown away, so they don't need it
+        options.language_confidence = kind != JobKind::Preview;
+        let transcribe_start = std::time::Instant::now();
+        let transcript = engine
+            .transcribe(audio, &options)
+            .map_err(|e| anyhow::anyhow!("{} transcription failed: {}", backend_id, e))?;
+        Ok(JobOutput {
+            transcript,
+            load_ms,
+            transcribe_ms: transcribe_start.elapsed().as_millis() as i64,
+        })
+    }
+
+    /// Final transcription of a recording that was previewed while streaming.
+    ///
+    /// Words committed during streaming are kept as they are, and only the
+    /// audio after them is transcribed again. Falls back to a full pass when
+    /// the remote API is used or the recording doesn't match what was streamed.
+    /// `speech_ms` is how much speech the VAD detected while recording, if known.
+    pub fn transcribe_streamed(
+        &self,
+        audio: Vec<f32>,
+        speech_ms: Option<i64>,
+    ) -> Result<Transcript> {
+        let state = std::mem::take(&mut *self.streaming_state.lock().unwrap());
+        let streamed = std::mem::take(&mut *self.streaming_buffer.lock().unwrap());
+        let settings = self.settings_for(JobKind::Dictation);
+
+        let cut_ms = state
+            .committed_words()
+            .last()
+            .map(|w| w.end_ms)
+            .filter(|_| !settings.remote_transcription_enabled)
+            .filter(|_| streamed.len() <= audio.len() && audio[..streamed.len()] == streamed[..]);
+        let Some(cut_ms) = cut_ms else {
+            return self.transcribe_dictation(audio, speech_ms);
+        };
+
+        let cut = (cut_ms.max(0) as usize * 16).min(audio.len());
+        let mut tail = audio[cut..].to_vec();
+        debug!(
+            "Reusing {} committed words, transcribing the last {:.1}s",
+            state.committed_words().len(),
+            tail.len() as f32 / 16000.0
+        );
+        if tail.len() < MIN_FINAL_TAIL_SAMPLES {
+            tail.resize(MIN_FINAL_TAIL_SAMPLES, 0.0);
+        }
+
+        let tail_transcript = self.run_job(tail, JobKind::Dictation, &CancelToken::new())?;
+        let transcript = state.finish(&tail_transcript, cut_ms);
+        Ok(finish_transcript(transcript, &settings, speech_ms))
+    }
+
+    /// Transcription of a dictation that wasn't previewed, such as a hands-free
+    /// utterance. `speech_ms` is how much speech the VAD detected, if known.
+    pub fn transcribe_dictation(
+        &self,
+        audio: Vec<f32>,
+        speech_ms: Option<i64>,
+    ) -> Result<Transcript> {
+        let settings = self.settings_for(JobKind::Dictation);
+        let transcript = self.run_job(audio, JobKind::Dictation, &CancelToken::new())?;
+        Ok(finish_transcript(transcript, &settings, speech_ms))
+    }
+
+    pub fn start_streaming(&self, generation: u64) {
+        debug!("start_streaming called - clearing buffer and resetting adaptive limit");
+        self.active_generation.store(generation, Ordering::SeqCst);
+        self.streaming_buffer.lock().unwrap().clear();
+        *self.streaming_state.lock().unwrap() = StreamingState::new();
+        *self.last_partial_update.lock().unwrap() = std::time::Instant::now();
+        // Reset adaptive limit for new recording session
+        *self.adaptive_max_samples.lock().unwrap() = None;
+    }
+
+    pub fn handle_streaming_chunk(&self, chunk: Vec<f32>, generation: u64) {
+        // Discard chunk if it belongs to a stale recording session
+        if self.active_generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        // Append chunk to buffer
+        let current_len = {
+            let mut buf = self.streaming_buffer.lock().unwrap();
+            buf.extend_from_slice(&chunk);
+            buf.len()
+        };
+
+        // Throttle updates to ~500ms
+        let now = std::time::Instant::now();
+        let mut last = self.last_partial_update.lock().unwrap();
+        let elapsed_ms = now.duration_since(*last).as_millis();
+
+        if elapsed_ms > 500 {
+            *last = now;
+            drop(last);
+
+            // Only the uncommitted window is transcribed; words before it
+            // are already committed in the streaming state
+            let (window_start_ms, window_start) = {
+                let state = self.streaming_state.lock().unwrap();
+                let start_ms = state.window_start_ms();
+                (start_ms, (start_ms.max(0) as usize * 16).min(current_len))
+            };
+
+            // Avoid transcribing extremely short windows (need at least 1 second)
+            if current_len - window_start < 16000 {
+                return;
+            }
+
+            // Skip if a streaming transcription is already in progress
+            // This prevents parallel transcriptions and resource waste
+            if self.streaming_in_progress.swap(true, Ordering::SeqCst) {
+                debug!("Skipping streaming transcription - previous one still in progress");
+                return;
+            }
+
+            // Adaptive streaming: adjust max samples based on transcription performance
+            // Target: keep transcription time under 800ms for responsive UI
+            // Minimum: 5 seconds of audio for context (16000 * 5 = 80000 samples)
+            const MIN_STREAMING_SAMPLES: usize = 16000 * 5;
+            const TARGET_TRANSCRIPTION_MS: u128 = 800;
+
+            let buf_to_transcribe = self.streaming_buffer.lock().unwrap()[window_start..].to_vec();
+
+            // Calculate audio duration for logging
+            let audio_duration_secs = buf_to_transcribe.len() as f32 / 16000.0;
+            let samples_count = buf_to_transcribe.len();
+
+            let this = self.clone();
+
+            thread::spawn(move || {
+                // Check generation before starting transcription work
+                if this.active_generation.load(Ordering::SeqCst) != generation {
+                    this.streaming_in_progress.store(false, Ordering::SeqCst);
+                    return;
+                }
+
+                let transcription_start = std::time::Instant::now();
+                if let Ok(transcript) =
+                    this.run_job(buf_to_transcribe, JobKind::Preview, &CancelToken::new())
+                {
+                    let transcription_ms = transcription_start.elapsed().as_millis();
+
+                    // Adaptive algorithm: if transcription exceeded target time, reduce the limit
+                    if transcription_ms > TARGET_TRANSCRIPTION_MS {
+                        let mut adaptive = this.adaptive_max_samples.lock().unwrap();
+
+                        match *adaptive {
+                            None => {
+                                // First time exceeding: set limit to current sample count
+                                let new_limit = samples_count.max(MIN_STREAMING_SAMPLES);
+                                info!(
+                                    "Adaptive limit set: {:.1}s ({}ms exceeded {}ms target)",
+                                    new_limit as f32 / 16000.0,
+                                    transcription_ms,
+                                    TARGET_TRANSCRIPTION_MS
+                                );
+                                *adaptive = Some(new_limit);
+                            }
+                            Some(current) => {
+                                // Already have a limit but still exceeding: reduce by 10%
+                                let reduced = (current as f32 * 0.9) as usize;
+                                let new_limit = reduced.max(MIN_STREAMING_SAMPLES);
+
+                                if new_limit < current {
+                                    info!(
+                                        "Adaptive limit reduced: {:.1}s -> {:.1}s ({}ms still exceeded {}ms)",
+                                        current as f32 / 16000.0,
+                                        new_limit as f32 / 16000.0,
+                                        transcription_ms,
+                                        TARGET_TRANSCRIPTION_MS
+                                    );
+                                    *adaptive = Some(new_limit);
+                                }
+                            }
+                        }
+                    }
+
+                    // Only emit if this generation is still current
+                    if this.active_generation.load(Ordering::SeqCst) == generation {
+                        let max_samples = this
+                            .adaptive_max_samples
+                            .lock()
+                            .unwrap()
+                            .unwrap_or(DEFAULT_STREAMING_WINDOW_SAMPLES);
+                        let progress = {
+                            let mut state = this.streaming_state.lock().unwrap();
+                            // The final pass may have taken the state in the meantime
+                            if state.window_start_ms() == window_start_ms {
+                                Some(state.insert(
+                                    &transcript,
+                                    samples_to_ms(samples_count),
+                                    samples_to_ms(max_samples),
+                                ))
+                            } else {
+                                None
+                            }
+                        };
+
+                        if let Some(progress) = progress {
+                            info!(
+                                "Partial transcription ({:.1}s window, {}ms): '{}' + '{}'",
+                                audio_duration_secs,
+                                transcription_ms,
+                                progress.committed,
+                                progress.tentative
+                            );
+                            crate::overlay::emit_transcription_progress(
+                                &this.app_handle,
+                                &progress,
+                            );
+                        }
+                    }
+                }
+                // Mark streaming transcription as complete
+                this.streaming_in_progress.store(false, Ordering::SeqCst);
+            });
+        }
+    }
+}
+
+/// Clean up the complete transcript of a recording, once: custom words
+/// first, so the filter sees the text as it will be pasted.
+fn finish_transcript(
+    transcript: Transcript,
+    settings: &AppSettings,
+    speech_ms: Option<i64>,
+) -> Transcript {
+    let transcript = correct_custom_words(transcript, settings);
+    filter_hallucinations(transcript, settings, speech_ms).trimmed()
+}
+
+/// Fuzzy correction of custom words in the finished transcript.
+///
+/// Backends with `vocabulary_biasing` also see custom words while decoding;
+/// for the others, such as Parakeet, this is the only place they apply.
+fn correct_custom_words(transcript: Transcript, settings: &AppSettings) -> Transcript {
+    if settings.custom_words_fuzzy_correction && !settings.custom_words.is_empty() {
+        transcript.map_text(|text| {
+            apply_custom_word_spans(
+                text,
+                &settings.custom_words,
+                settings.word_correction_threshold,
+            )
+        })
+    } else {
+        transcript
+    }
+}
+
+/// Drop silence artifacts, if the filter is enabled.
+fn filter_hallucinations(
+    transcript: Transcript,
+    settings: &AppSettings,
+    speech_ms: Option<i64>,
+) -> Transcript {
+    match HallucinationFilter::from_settings(settings) {
+        Some(filter) => filter.apply(transcript, speech_ms),
+        None => transcript,
+    }
+}
+
+/// Resolve the user settings into options the given backend can honour.
+///
+/// `model_id` selects the decoding parameters of a local model.
+fn transcribe_options(
+    settings: &AppSettings,
+    backend: &dyn SpeechBackend,
+    model_id: Option<&str>,
+    interrupt: &Arc<AtomicBool>,
+) -> TranscribeOptions {
+    let backend_id = backend.id();
+    let capabilities = backend.capabilities();
+    let language = if settings.selected_language == "auto" {
+        None
+    } else if matches!(settings.selected_language.as_str(), "zh-Hans" | "zh-Hant") {
+        Some("zh".to_string())
+    } else {
+        Some(settings.selected_language.clone())
+    };
+    if language.is_some() && !capabilities.language_selection {
+        debug!(
+            "{} backend detects the language itself, ignoring selection",
+            backend_id
+        );
+    }
+    if settings.translate_to_english && !capabilities.translation {
+        warn!("{} backend does not support translation", backend_id);
+    }
+    if !settings.custom_words.is_empty() && !capabilities.vocabulary_biasing {
+        debug!(
+            "{} backend can't be biased toward custom words, relying on fuzzy correction",
+            backend_id
+        );
+    }
+
+    TranscribeOptions {
+        language: language.filter(|_| capabilities.language_selection),
+        translate: settings.translate_to_english && capabilities.translation,
+        custom_words: if capabilities.vocabulary_biasing {
+            settings.custom_words.clone()
+        } else {
+            Vec::new()
+        },
+        whisper: model_id
+            .map(|id| settings.decoding_params_for(id))
+            .unwrap_or_default(),
+        interrupt: interrupt.clone(),
+    }
+}
+
+impl Drop for TranscriptionManager {
+    fn drop(&mut self) {
+        debug!("Shutting down TranscriptionManager");
+
+        // Signal the watcher thread to shutdown
+        self.shutdown_signal.store(true, Ordering::Relaxed);
+
+        // Wait for the thread to finish gracefully
+        if let Some(handle) = self.watcher_handle.lock().unwrap().take() {
+            if let Err(e) = handle.join() {
+                warn!("Failed to join idle watcher thread: {:?}", e);
+            } else {
+                debug!("Idle watcher thread joined successfully");
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! NVIDIA Parakeet backend via `transcribe_rs`.
+
+use super::backend::{BackendCapabilities, SpeechBackend, TranscribeOptions};
+use super::types::{segments_from_words, Transcript, TranscriptWord};
+use crate::audio_toolkit::detect_text_language;
+use anyhow::Result;
+use std::path::Path;
+use transcribe_rs::{
+    engines::parakeet::{
+        ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
+    },
+    TranscriptionEngine,
+};
+
+pub struct ParakeetBackend {
+    engine: Option<ParakeetEngine>,
+}
+
+impl ParakeetBackend {
+    pub fn new() -> Self {
+        Self { engine: None }
+    }
+}
+
+impl SpeechBackend for ParakeetBackend {
+    fn id(&self) -> &'static str {
+        "parakeet"
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            streaming: false,
+            segment_timestamps: true,
+            word_timestamps: true,
+            translation: false,
+            language_selection: false,
+            // transcribe-rs exposes no hook into the TDT decoder to boost
+            // hotword tokens, so custom words are left to the fuzzy correction
+            vocabulary_biasing: false,
+            languages: Vec::new(),
+        }
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()> {
+        let mut engine = ParakeetEngine::new();
+        engine
+            .load_model_with_params(model_path, ParakeetModelParams::int8())
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        self.engine = Some(engine);
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        if let Some(mut engine) = self.engine.take() {
+            engine.unload_model();
+        }
+    }
+
+    fn transcribe(&mut self, audio: &[f32], _options: &TranscribeOptions) -> Result<Transcript> {
+        let engine = self
+            .engine
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Parakeet model is not loaded"))?;
+
+        let params = ParakeetInferenceParams {
+            timestamp_granularity: TimestampGranularity::Word,
+            ..Default::default()
+        };
+
+        let result = engine
+            .transcribe_samples(audio.to_vec(), Some(params))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        // With word granularity each reported segment is a single word
+        let words = result
+            .segments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|w| TranscriptWord {
+                text: w.text,
+                start_ms: (w.start * 1000.0).round() as i64,
+                end_ms: (w.end * 1000.0).round() as i64,
+                probability: None,
+            })
+            .collect();
+
+        // Parakeet doesn't report the language it recognised, so guess it
+        // from the text
+        Ok(Transcript {
+            language: detect_text_language(&result.text).map(str::to_string),
+            text: result.text,
+            segments: segments_from_words(words),
+            language_probability: None,
+        })
+    }
+}
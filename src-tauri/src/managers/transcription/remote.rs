@@ -0,0 +1,443 @@
+//! Remote transcription through an OpenAI-compatible HTTP API.
+//!
+//! Works with the hosted OpenAI endpoint as well as self-hosted servers that
+//! mirror `/audio/transcriptions` (faster-whisper-server, whisper.cpp server,
+//! LocalAI...). Recordings are uploaded as 16 kHz mono WAV.
+
+use super::backend::{BackendCapabilities, SpeechBackend, TranscribeOptions};
+use super::types::{
+    mean_probability, segments_from_words, Transcript, TranscriptSegment, TranscriptWord,
+};
+use super::whisper::{language_code, vocabulary_prompt};
+use crate::audio_toolkit::encode_wav;
+use crate::settings::AppSettings;
+use anyhow::Result;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Where and how to reach the transcription server.
+#[derive(Debug, Clone)]
+pub struct RemoteConfig {
+    /// API root including the version, e.g. `https://api.openai.com/v1`.
+    pub base_url: String,
+    pub api_key: String,
+    pub model: String,
+}
+
+impl RemoteConfig {
+    /// Resolve the endpoint from the provider list shared with post-processing.
+    pub fn from_settings(settings: &AppSettings) -> Result<Self> {
+        let provider_id = &settings.remote_transcription_provider_id;
+        let provider = settings
+            .post_process_provider(provider_id)
+            .ok_or_else(|| anyhow::anyhow!("Provider '{}' not found", provider_id))?;
+
+        Ok(Self {
+            base_url: provider.base_url.trim_end_matches('/').to_string(),
+            api_key: settings
+                .post_process_api_keys
+                .get(provider_id)
+                .cloned()
+                .unwrap_or_default(),
+            model: settings.remote_transcription_model.clone(),
+        })
+    }
+}
+
+pub struct RemoteBackend {
+    config: RemoteConfig,
+}
+
+impl RemoteBackend {
+    pub fn new(config: RemoteConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl SpeechBackend for RemoteBackend {
+    fn id(&self) -> &'static str {
+        "remote"
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            streaming: false,
+            segment_timestamps: true,
+            word_timestamps: true,
+            translation: true,
+            language_selection: true,
+            vocabulary_biasing: true,
+            languages: Vec::new(),
+        }
+    }
+
+    fn load(&mut self, _model_path: &Path) -> Result<()> {
+        // The model lives on the server
+        Ok(())
+    }
+
+    fn unload(&mut self) {}
+
+    fn transcribe(&mut self, audio: &[f32], options: &TranscribeOptions) -> Result<Transcript> {
+        let wav = encode_wav(audio)?;
+        let config = self.config.clone();
+        let options = options.clone();
+
+        // Callers are plain threads, some of which may already be inside the
+        // async runtime, so block on the request from a dedicated thread
+        thread::spawn(move || {
+            tauri::async_runtime::block_on(request_transcription(&config, wav, &options))
+        })
+        .join()
+        .map_err(|_| anyhow::anyhow!("Remote transcription thread panicked"))?
+    }
+}
+
+async fn request_transcription(
+    config: &RemoteConfig,
+    wav: Vec<u8>,
+    options: &TranscribeOptions,
+) -> Result<Transcript> {
+    // Translation has its own endpoint, which takes neither a language nor
+    // timestamp granularities
+    let endpoint = if options.translate {
+        "translations"
+    } else {
+        "transcriptions"
+    };
+    let url = format!("{}/audio/{}", config.base_url, endpoint);
+
+    let file = Part::bytes(wav)
+        .file_name("audio.wav")
+        .mime_str("audio/wav")?;
+    let mut form = Form::new()
+        .part("file", file)
+        .text("model", config.model.clone())
+        .text("response_format", "verbose_json");
+    if !options.translate {
+        form = form
+            .text("timestamp_granularities[]", "segment")
+            .text("timestamp_granularities[]", "word");
+        if let Some(language) = &options.language {
+            form = form.text("language", language.clone());
+        }
+    }
+    if let Some(prompt) = vocabulary_prompt(&options.custom_words) {
+        form = form.text("prompt", prompt);
+    }
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()?;
+    let mut request = client.post(&url).multipart(form);
+    if !config.api_key.is_empty() {
+        request = request.bearer_auth(&config.api_key);
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(anyhow::anyhow!(
+            "{} returned {}: {}",
+            url,
+            status,
+            body.trim()
+        ));
+    }
+
+    let mut transcript = parse_response(&body)?;
+    // Translations report the output language, not the spoken one
+    if options.translate || transcript.language.is_none() {
+        transcript.language = options.language.clone();
+    }
+    Ok(transcript)
+}
+
+/// `verbose_json` response; every field but `text` is optional so that plain
+/// `json` responses and partial implementations still parse.
+#[derive(Deserialize)]
+struct VerboseResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Option<Vec<RemoteSegment>>,
+    #[serde(default)]
+    words: Option<Vec<RemoteWord>>,
+}
+
+#[derive(Deserialize)]
+struct RemoteSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    /// Some servers nest words in their segment instead of listing them at the top level.
+    #[serde(default)]
+    words: Option<Vec<RemoteWord>>,
+}
+
+#[derive(Deserialize)]
+struct RemoteWord {
+    word: String,
+    start: f64,
+    end: f64,
+    #[serde(default)]
+    probability: Option<f32>,
+}
+
+fn seconds_to_ms(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+impl From<RemoteWord> for TranscriptWord {
+    fn from(word: RemoteWord) -> Self {
+        TranscriptWord {
+            text: word.word.trim().to_string(),
+            start_ms: seconds_to_ms(word.start),
+            end_ms: seconds_to_ms(word.end),
+            probability: word.probability,
+        }
+    }
+}
+
+fn parse_response(body: &str) -> Result<Transcript> {
+    let response: VerboseResponse = serde_json::from_str(body)
+        .map_err(|e| anyhow::anyhow!("Unexpected transcription response: {}", e))?;
+
+    let mut words: Vec<TranscriptWord> = response
+        .words
+        .unwrap_or_default()
+        .into_iter()
+        .map(TranscriptWord::from)
+        .collect();
+
+    let segments = match response.segments {
+        Some(remote_segments) if !remote_segments.is_empty() => {
+            let count = remote_segments.len();
+            let mut segments = Vec::with_capacity(count);
+            for (i, segment) in remote_segments.into_iter().enumerate() {
+                let start_ms = seconds_to_ms(segment.start);
+                let end_ms = seconds_to_ms(segment.end);
+
+                // Hand top-level words to the segment they start in; the last
+                // segment takes whatever is left
+                let segment_words: Vec<TranscriptWord> = match segment.words {
+                    Some(nested) => nested.into_iter().map(TranscriptWord::from).collect(),
+                    None => {
+                        let is_last = i + 1 == count;
+                        let (inside, rest): (Vec<_>, Vec<_>) = words
+                            .drain(..)
+                            .partition(|w| is_last || w.start_ms < end_ms);
+                        words = rest;
+                        inside
+                    }
+                };
+
+                let probability = segment
+                    .avg_logprob
+                    .map(|logprob| logprob.exp() as f32)
+                    .or_else(|| {
+                        mean_probability(segment_words.iter().filter_map(|w| w.probability))
+                    });
+
+                segments.push(TranscriptSegment {
+                    text: segment.text.trim().to_string(),
+                    start_ms,
+                    end_ms,
+                    probability,
+                    words: segment_words,
+                });
+            }
+            segments
+        }
+        _ => segments_from_words(words),
+    };
+
+    Ok(Transcript {
+        text: response.text.trim().to_string(),
+        segments,
+        language: response.language.as_deref().and_then(language_code),
+        language_probability: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    const VERBOSE_RESPONSE: &str = r#"{
+        "task": "transcribe",
+        "language": "english",
+        "duration": 3.2,
+        "text": " Hello there. General Kenobi.",
+        "segments": [
+            {"id": 0, "start": 0.0, "end": 1.5, "text": " Hello there.", "avg_logprob": -0.1},
+            {"id": 1, "start": 1.5, "end": 3.2, "text": " General Kenobi.", "avg_logprob": -0.3}
+        ],
+        "words": [
+            {"word": "Hello", "start": 0.1, "end": 0.5},
+            {"word": "there.", "start": 0.6, "end": 1.2},
+            {"word": "General", "start": 1.6, "end": 2.2},
+            {"word": "Kenobi.", "start": 2.3, "end": 3.1}
+        ]
+    }"#;
+
+    /// Serve a single canned response and return the raw request it received.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request = read_request(&mut stream);
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 8192];
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+
+            let Some(header_end) = request.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&request[..header_end]).to_lowercase();
+            let content_length = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .and_then(|value| value.trim().parse::<usize>().ok());
+            let complete = match content_length {
+                Some(len) => request.len() >= header_end + 4 + len,
+                None => request.ends_with(b"0\r\n\r\n"),
+            };
+            if complete {
+                break;
+            }
+        }
+        String::from_utf8_lossy(&request).into_owned()
+    }
+
+    fn backend(base_url: String) -> RemoteBackend {
+        RemoteBackend::new(RemoteConfig {
+            base_url,
+            api_key: "test-key".to_string(),
+            model: "whisper-1".to_string(),
+        })
+    }
+
+    #[test]
+    fn uploads_wav_and_parses_verbose_json() {
+        let (base_url, server) = serve_once("200 OK", VERBOSE_RESPONSE);
+        let options = TranscribeOptions {
+            language: Some("en".to_string()),
+            custom_words: vec!["Kenobi".to_string()],
+            ..Default::default()
+        };
+
+        let transcript = backend(base_url)
+            .transcribe(&[0.0; 16000], &options)
+            .unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("POST /v1/audio/transcriptions "));
+        assert!(request
+            .to_lowercase()
+            .contains("authorization: bearer test-key"));
+        assert!(request.contains("filename=\"audio.wav\""));
+        assert!(request.contains("RIFF"));
+        assert!(request.contains("name=\"model\"\r\n\r\nwhisper-1"));
+        assert!(request.contains("name=\"response_format\"\r\n\r\nverbose_json"));
+        assert!(request.contains("name=\"language\"\r\n\r\nen"));
+        assert!(request.contains("name=\"prompt\"\r\n\r\nKenobi."));
+
+        assert_eq!(transcript.text, "Hello there. General Kenobi.");
+        assert_eq!(transcript.language.as_deref(), Some("en"));
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[0].start_ms, 0);
+        assert_eq!(transcript.segments[0].end_ms, 1500);
+        assert_eq!(transcript.segments[0].words.len(), 2);
+        assert_eq!(transcript.segments[1].words[1].text, "Kenobi.");
+        assert_eq!(transcript.segments[1].words[1].start_ms, 2300);
+        let probability = transcript.segments[0].probability.unwrap();
+        assert!((probability - (-0.1f64).exp() as f32).abs() < 1e-6);
+    }
+
+    #[test]
+    fn translation_uses_translations_endpoint() {
+        let (base_url, server) = serve_once("200 OK", r#"{"text": "Hello"}"#);
+        let options = TranscribeOptions {
+            language: Some("de".to_string()),
+            translate: true,
+            ..Default::default()
+        };
+
+        let transcript = backend(base_url)
+            .transcribe(&[0.0; 1600], &options)
+            .unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("POST /v1/audio/translations "));
+        assert!(!request.contains("name=\"language\""));
+        assert_eq!(transcript.text, "Hello");
+        assert_eq!(transcript.language.as_deref(), Some("de"));
+        assert!(transcript.segments.is_empty());
+    }
+
+    #[test]
+    fn error_status_is_reported() {
+        let (base_url, server) = serve_once(
+            "401 Unauthorized",
+            r#"{"error": {"message": "Invalid API key"}}"#,
+        );
+
+        let result = backend(base_url).transcribe(&[0.0; 1600], &TranscribeOptions::default());
+        server.join().unwrap();
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("401"));
+        assert!(error.contains("Invalid API key"));
+    }
+
+    #[test]
+    fn words_without_segments_are_grouped() {
+        let transcript = parse_response(
+            r#"{"text": "One. Two.", "words": [
+                {"word": " One.", "start": 0.0, "end": 0.4, "probability": 0.9},
+                {"word": " Two.", "start": 0.5, "end": 0.9, "probability": 0.7}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[0].words[0].text, "One.");
+        assert_eq!(transcript.segments[1].words[0].probability, Some(0.7));
+    }
+}
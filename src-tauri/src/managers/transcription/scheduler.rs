@@ -0,0 +1,415 @@
+//! Priority queue for transcription jobs.
+//!
+//! Every caller of the engine submits a job instead of locking it directly.
+//! One worker runs the highest-priority job; jobs of equal priority run in
+//! submission order. Dictation also interrupts a running lower-priority job,
+//! which is put back at the head of its priority and run again afterwards.
+
+use super::types::Transcript;
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Final pass of a push-to-talk recording; the user is waiting to paste.
+    Dictation,
+    /// Streaming preview while recording.
+    Preview,
+    /// Re-running a history entry from the UI.
+    Retranscription,
+    /// A chunk of a meeting recording.
+    Meeting,
+    /// A chunk of an imported audio or video file.
+    File,
+    /// Timing a model on a reference recording.
+    Benchmark,
+}
+
+impl JobKind {
+    fn priority(self) -> u8 {
+        match self {
+            JobKind::Dictation => 3,
+            JobKind::Preview => 2,
+            JobKind::Retranscription => 1,
+            JobKind::Meeting | JobKind::File | JobKind::Benchmark => 0,
+        }
+    }
+
+    /// Whether this job interrupts a running job of lower priority.
+    fn preempts(self) -> bool {
+        self == JobKind::Dictation
+    }
+
+    /// Previews run every 500ms, so they never go to the remote API, and
+    /// benchmarks measure a local model.
+    pub fn allows_remote(self) -> bool {
+        !matches!(self, JobKind::Preview | JobKind::Benchmark)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+}
+
+/// Queue entry as reported to the frontend.
+#[derive(Clone, Debug, Serialize)]
+pub struct JobInfo {
+    pub id: u64,
+    pub kind: JobKind,
+    pub state: JobState,
+    pub audio_ms: i64,
+}
+
+/// Cancels every job submitted with it, including jobs not queued yet.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// What a job produced, and how long the engine took.
+#[derive(Default)]
+pub(super) struct JobOutput {
+    pub transcript: Transcript,
+    /// Time spent loading the job's model; zero if it was loaded already.
+    pub load_ms: i64,
+    pub transcribe_ms: i64,
+}
+
+pub(super) struct Job {
+    pub id: u64,
+    pub kind: JobKind,
+    pub audio: Vec<f32>,
+    /// Local model to run on, chosen when the job was submitted.
+    pub model_id: String,
+    /// Spoken language overriding the setting, for benchmarks of a known clip.
+    pub language: Option<String>,
+    pub cancel: CancelToken,
+    /// Raised to stop the engine mid-run, on cancellation or preemption.
+    pub interrupt: Arc<AtomicBool>,
+    seq: u64,
+    reply: mpsc::Sender<Result<JobOutput>>,
+}
+
+impl Job {
+    fn info(&self, state: JobState) -> JobInfo {
+        JobInfo {
+            id: self.id,
+            kind: self.kind,
+            state,
+            audio_ms: (self.audio.len() / 16) as i64,
+        }
+    }
+
+    fn reply(self, result: Result<JobOutput>) {
+        // The submitter may have given up waiting; nothing to do then
+        let _ = self.reply.send(result);
+    }
+}
+
+struct RunningJob {
+    info: JobInfo,
+    cancel: CancelToken,
+    interrupt: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+pub(super) struct JobQueue {
+    queued: Vec<Job>,
+    running: Option<RunningJob>,
+    next_id: u64,
+    next_seq: u64,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a job, interrupting the running one if the new job preempts it.
+    pub fn push(
+        &mut self,
+        kind: JobKind,
+        audio: Vec<f32>,
+        model_id: String,
+        language: Option<String>,
+        cancel: CancelToken,
+        reply: mpsc::Sender<Result<JobOutput>>,
+    ) -> u64 {
+        self.next_id += 1;
+        self.next_seq += 1;
+        let id = self.next_id;
+
+        if let Some(running) = &self.running {
+            if kind.preempts() && kind.priority() > running.info.kind.priority() {
+                running.interrupt.store(true, Ordering::SeqCst);
+            }
+        }
+
+        self.queued.push(Job {
+            id,
+            kind,
+            audio,
+            model_id,
+            language,
+            cancel,
+            interrupt: Arc::new(AtomicBool::new(false)),
+            seq: self.next_seq,
+            reply,
+        });
+        id
+    }
+
+    /// Take the next job to run and mark it as running.
+    ///
+    /// Jobs whose token was cancelled while queued are answered here.
+    pub fn pop(&mut self) -> Option<Job> {
+        self.drop_cancelled();
+
+        let index = self
+            .queued
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, job)| (job.kind.priority(), std::cmp::Reverse(job.seq)))
+            .map(|(index, _)| index)?;
+        let job = self.queued.remove(index);
+        self.running = Some(RunningJob {
+            info: job.info(JobState::Running),
+            cancel: job.cancel.clone(),
+            interrupt: job.interrupt.clone(),
+        });
+        Some(job)
+    }
+
+    /// Settle the running job: answer it, or requeue it if it was preempted.
+    pub fn complete(&mut self, job: Job, result: Result<JobOutput>) {
+        self.running = None;
+
+        if job.cancel.is_cancelled() {
+            job.reply(Err(cancelled_error()));
+        } else if job.interrupt.swap(false, Ordering::SeqCst) && result.is_err() {
+            // Keeps its original sequence number, so it runs before jobs
+            // of the same priority that were queued after it
+            self.queued.push(job);
+        } else {
+            job.reply(result);
+        }
+    }
+
+    /// Answer the queued jobs whose token was cancelled, and interrupt the
+    /// running one if its token was.
+    pub fn drop_cancelled(&mut self) {
+        if let Some(running) = self.running.as_ref().filter(|r| r.cancel.is_cancelled()) {
+            running.interrupt.store(true, Ordering::SeqCst);
+        }
+        let (cancelled, queued): (Vec<Job>, Vec<Job>) = std::mem::take(&mut self.queued)
+            .into_iter()
+            .partition(|job| job.cancel.is_cancelled());
+        self.queued = queued;
+        for job in cancelled {
+            job.reply(Err(cancelled_error()));
+        }
+    }
+
+    /// Cancel a queued or running job. Returns false if the id is unknown.
+    pub fn cancel(&mut self, id: u64) -> bool {
+        if let Some(running) = self.running.as_ref().filter(|r| r.info.id == id) {
+            running.cancel.cancel();
+            running.interrupt.store(true, Ordering::SeqCst);
+            return true;
+        }
+        match self.queued.iter().position(|job| job.id == id) {
+            Some(index) => {
+                let job = self.queued.remove(index);
+                job.cancel.cancel();
+                job.reply(Err(cancelled_error()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel every queued or running job of the given kinds.
+    pub fn cancel_kinds(&mut self, kinds: &[JobKind]) {
+        let ids: Vec<u64> = self
+            .snapshot()
+            .into_iter()
+            .filter(|info| kinds.contains(&info.kind))
+            .map(|info| info.id)
+            .collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+
+    /// Running job first, then queued jobs in the order they will run.
+    pub fn snapshot(&self) -> Vec<JobInfo> {
+        let mut queued: Vec<&Job> = self.queued.iter().collect();
+        queued.sort_by_key(|job| (std::cmp::Reverse(job.kind.priority()), job.seq));
+
+        self.running
+            .iter()
+            .map(|running| running.info.clone())
+            .chain(queued.into_iter().map(|job| job.info(JobState::Queued)))
+            .collect()
+    }
+}
+
+fn cancelled_error() -> anyhow::Error {
+    anyhow::anyhow!("Transcription cancelled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(queue: &mut JobQueue, kind: JobKind) -> (u64, mpsc::Receiver<Result<JobOutput>>) {
+        let (tx, rx) = mpsc::channel();
+        let id = queue.push(
+            kind,
+            vec![0.0; 16000],
+            String::new(),
+            None,
+            CancelToken::new(),
+            tx,
+        );
+        (id, rx)
+    }
+
+    #[test]
+    fn higher_priority_jumps_ahead() {
+        let mut queue = JobQueue::new();
+        let (meeting, _rx1) = push(&mut queue, JobKind::Meeting);
+        let (file, _rx2) = push(&mut queue, JobKind::File);
+        let (dictation, _rx3) = push(&mut queue, JobKind::Dictation);
+
+        let order: Vec<u64> = queue.snapshot().iter().map(|info| info.id).collect();
+        assert_eq!(order, vec![dictation, meeting, file]);
+        assert_eq!(queue.pop().unwrap().id, dictation);
+        assert_eq!(queue.snapshot()[0].state, JobState::Running);
+    }
+
+    #[test]
+    fn dictation_preempts_background_job() {
+        let mut queue = JobQueue::new();
+        let (meeting, rx) = push(&mut queue, JobKind::Meeting);
+        let job = queue.pop().unwrap();
+
+        push(&mut queue, JobKind::Preview);
+        assert!(!job.interrupt.load(Ordering::SeqCst));
+        push(&mut queue, JobKind::Dictation);
+        assert!(job.interrupt.load(Ordering::SeqCst));
+
+        // The engine aborted, so the job goes back into the queue
+        queue.complete(job, Err(anyhow::anyhow!("aborted")));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(queue.pop().unwrap().kind, JobKind::Dictation);
+        assert_eq!(queue.pop().unwrap().kind, JobKind::Preview);
+        assert_eq!(queue.pop().unwrap().id, meeting);
+    }
+
+    #[test]
+    fn preempted_job_that_finished_keeps_its_result() {
+        let mut queue = JobQueue::new();
+        let (_, rx) = push(&mut queue, JobKind::File);
+        let job = queue.pop().unwrap();
+        push(&mut queue, JobKind::Dictation);
+
+        queue.complete(job, Ok(JobOutput::default()));
+        assert!(rx.try_recv().unwrap().is_ok());
+    }
+
+    #[test]
+    fn cancelling_queued_job_replies_immediately() {
+        let mut queue = JobQueue::new();
+        let (id, rx) = push(&mut queue, JobKind::File);
+
+        assert!(queue.cancel(id));
+        assert!(rx.try_recv().unwrap().is_err());
+        assert!(queue.pop().is_none());
+        assert!(!queue.cancel(id));
+    }
+
+    #[test]
+    fn cancelling_running_job_interrupts_it() {
+        let mut queue = JobQueue::new();
+        let (id, rx) = push(&mut queue, JobKind::Retranscription);
+        let job = queue.pop().unwrap();
+
+        assert!(queue.cancel(id));
+        assert!(job.interrupt.load(Ordering::SeqCst));
+        queue.complete(job, Ok(JobOutput::default()));
+        assert!(rx.try_recv().unwrap().is_err());
+        assert!(queue.snapshot().is_empty());
+    }
+
+    #[test]
+    fn shared_token_cancels_pending_jobs() {
+        let mut queue = JobQueue::new();
+        let token = CancelToken::new();
+        let (tx, rx) = mpsc::channel();
+        queue.push(
+            JobKind::File,
+            Vec::new(),
+            String::new(),
+            None,
+            token.clone(),
+            tx.clone(),
+        );
+        queue.push(
+            JobKind::File,
+            Vec::new(),
+            String::new(),
+            None,
+            token.clone(),
+            tx,
+        );
+
+        token.cancel();
+        assert!(queue.pop().is_none());
+        assert_eq!(rx.try_iter().filter(|r| r.is_err()).count(), 2);
+    }
+
+    #[test]
+    fn cancelled_token_interrupts_its_running_job() {
+        let mut queue = JobQueue::new();
+        let token = CancelToken::new();
+        let (tx, rx) = mpsc::channel();
+        queue.push(
+            JobKind::File,
+            Vec::new(),
+            String::new(),
+            None,
+            token.clone(),
+            tx,
+        );
+        let job = queue.pop().unwrap();
+        let (_, other) = push(&mut queue, JobKind::Meeting);
+
+        token.cancel();
+        queue.drop_cancelled();
+        assert!(job.interrupt.load(Ordering::SeqCst));
+        assert!(other.try_recv().is_err());
+        queue.complete(job, Err(anyhow::anyhow!("aborted")));
+        assert!(rx.try_recv().unwrap().is_err());
+        assert_eq!(queue.snapshot().len(), 1);
+    }
+}
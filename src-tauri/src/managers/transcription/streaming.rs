@@ -0,0 +1,362 @@
+//! Incremental streaming with the LocalAgreement-2 policy.
+//!
+//! Each pass re-transcribes the uncommitted window at the end of the
+//! recording. A word is committed once two consecutive passes agree on it
+//! (the longest common prefix of the last two hypotheses), so committed text
+//! never changes while the rest is shown as tentative. When the window grows
+//! too long it slides past the committed words, which keeps passes short
+//! without dropping earlier text from the preview.
+
+use super::types::{Transcript, TranscriptWord};
+use serde::Serialize;
+
+/// How many trailing committed words are checked against the start of a new
+/// hypothesis, since whisper tends to repeat the words just before the window.
+const MAX_REPEATED_NGRAM: usize = 5;
+
+/// When a slide has nothing to commit (silence, or no agreement), keep this
+/// much audio so a word being spoken at the window end isn't cut in half.
+const SILENT_SLIDE_KEEP_MS: i64 = 1_000;
+
+/// Text emitted to the overlay after every streaming pass.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct StreamingProgress {
+    /// Text that will not change any more.
+    pub committed: String,
+    /// Latest guess for the rest of the window; may still be revised.
+    pub tentative: String,
+}
+
+#[derive(Debug, Default)]
+pub struct StreamingState {
+    committed: Vec<TranscriptWord>,
+    /// Uncommitted words of the previous hypothesis, in recording time.
+    previous: Vec<TranscriptWord>,
+    /// Start of the uncommitted window, in ms from the start of the recording.
+    window_start_ms: i64,
+}
+
+impl StreamingState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn window_start_ms(&self) -> i64 {
+        self.window_start_ms
+    }
+
+    pub fn committed_words(&self) -> &[TranscriptWord] {
+        &self.committed
+    }
+
+    /// Record the hypothesis for the current window and commit what the last
+    /// two passes agree on.
+    ///
+    /// `transcript` timings are relative to the window start. Once the window
+    /// is longer than `max_window_ms` it slides to the end of the committed
+    /// text.
+    pub fn insert(
+        &mut self,
+        transcript: &Transcript,
+        window_len_ms: i64,
+        max_window_ms: i64,
+    ) -> StreamingProgress {
+        let mut hypothesis = hypothesis_words(transcript, self.window_start_ms);
+        self.drop_committed(&mut hypothesis);
+
+        let agreed = self
+            .previous
+            .iter()
+            .zip(hypothesis.iter())
+            .take_while(|(a, b)| normalize(&a.text) == normalize(&b.text))
+            .count();
+        self.previous = hypothesis.split_off(agreed);
+        self.committed.extend(hypothesis);
+
+        if window_len_ms > max_window_ms {
+            self.slide(self.window_start_ms + window_len_ms);
+        }
+
+        self.progress()
+    }
+
+    /// Combine the committed words with a final pass over the audio after
+    /// them, whose timings are relative to `tail_start_ms`.
+    ///
+    /// The language is taken from the final pass.
+    pub fn finish(mut self, tail: &Transcript, tail_start_ms: i64) -> Transcript {
+        let mut words = hypothesis_words(tail, tail_start_ms);
+        self.drop_committed(&mut words);
+        self.committed.extend(words);
+        Transcript {
+            language: tail.language.clone(),
+            language_probability: tail.language_probability,
+            ..Transcript::from_words(self.committed)
+        }
+    }
+
+    pub fn progress(&self) -> StreamingProgress {
+        StreamingProgress {
+            committed: join_words(&self.committed),
+            tentative: join_words(&self.previous),
+        }
+    }
+
+    /// Move the window start past the committed words.
+    fn slide(&mut self, window_end_ms: i64) {
+        let committed_end = self.committed.last().map(|w| w.end_ms);
+        match committed_end {
+            Some(end) if end > self.window_start_ms => self.window_start_ms = end,
+            _ if !self.previous.is_empty() => {
+                // Nothing agreed within a full window: commit the latest
+                // guess rather than let the window grow without bound
+                self.committed.append(&mut self.previous);
+                if let Some(last) = self.committed.last() {
+                    self.window_start_ms = self.window_start_ms.max(last.end_ms);
+                }
+            }
+            _ => {
+                self.window_start_ms = self
+                    .window_start_ms
+                    .max(window_end_ms - SILENT_SLIDE_KEEP_MS);
+            }
+        }
+
+        let window_start_ms = self.window_start_ms;
+        self.previous.retain(|w| w.start_ms >= window_start_ms);
+    }
+
+    /// Remove the words of `hypothesis` that were already committed.
+    ///
+    /// Until the window slides it still covers the committed words, so those
+    /// are recognised by time: a word centred before the end of the committed
+    /// text is one of them. Whisper also tends to repeat the words just before
+    /// the window, which only their text gives away.
+    fn drop_committed(&self, hypothesis: &mut Vec<TranscriptWord>) {
+        if let Some(committed_end) = self.committed.last().map(|w| w.end_ms) {
+            hypothesis.retain(|w| (w.start_ms + w.end_ms) / 2 >= committed_end);
+        }
+        self.drop_repeated_prefix(hypothesis);
+    }
+
+    /// Remove words at the start of `hypothesis` that repeat the committed tail.
+    fn drop_repeated_prefix(&self, hypothesis: &mut Vec<TranscriptWord>) {
+        let max_n = MAX_REPEATED_NGRAM
+            .min(self.committed.len())
+            .min(hypothesis.len());
+        for n in (1..=max_n).rev() {
+            let tail = &self.committed[self.committed.len() - n..];
+            let repeated = tail
+                .iter()
+                .zip(hypothesis.iter())
+                .all(|(a, b)| normalize(&a.text) == normalize(&b.text));
+            if repeated {
+                hypothesis.drain(..n);
+                return;
+            }
+        }
+    }
+}
+
+/// Flatten a window transcript into words in recording time.
+///
+/// Segments without word timings count as a single word so that backends
+/// reporting only segments still stream, just more coarsely.
+fn hypothesis_words(transcript: &Transcript, offset_ms: i64) -> Vec<TranscriptWord> {
+    transcript
+        .segments
+        .iter()
+        .flat_map(|segment| {
+            if segment.words.is_empty() {
+                vec![TranscriptWord {
+                    text: segment.text.clone(),
+                    start_ms: segment.start_ms,
+                    end_ms: segment.end_ms,
+                    probability: segment.probability,
+                }]
+            } else {
+                segment.words.clone()
+            }
+        })
+        .filter(|w| !w.text.trim().is_empty())
+        .map(|mut w| {
+            w.start_ms += offset_ms;
+            w.end_ms += offset_ms;
+            w
+        })
+        .collect()
+}
+
+/// Compare words by their letters only, so "there," and "There." agree.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn join_words(words: &[TranscriptWord]) -> String {
+    words
+        .iter()
+        .map(|w| w.text.trim())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a window transcript from (text, start_ms, end_ms) words.
+    fn window(words: &[(&str, i64, i64)]) -> Transcript {
+        Transcript::from_words(
+            words
+                .iter()
+                .map(|(text, start_ms, end_ms)| TranscriptWord {
+                    text: text.to_string(),
+                    start_ms: *start_ms,
+                    end_ms: *end_ms,
+                    probability: None,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn commits_prefix_two_passes_agree_on() {
+        let mut state = StreamingState::new();
+
+        let first = state.insert(
+            &window(&[("hello", 0, 400), ("word", 500, 800)]),
+            1_000,
+            10_000,
+        );
+        assert_eq!(first.committed, "");
+        assert_eq!(first.tentative, "hello word");
+
+        let second = state.insert(
+            &window(&[("Hello", 0, 400), ("world,", 500, 800), ("how", 900, 1_100)]),
+            1_500,
+            10_000,
+        );
+        assert_eq!(second.committed, "Hello");
+        assert_eq!(second.tentative, "world, how");
+    }
+
+    #[test]
+    fn committed_text_survives_window_slide() {
+        let mut state = StreamingState::new();
+        state.insert(
+            &window(&[("one", 0, 300), ("two", 400, 700)]),
+            1_000,
+            10_000,
+        );
+        state.insert(
+            &window(&[("one", 0, 300), ("two", 400, 700), ("three", 800, 1_000)]),
+            1_200,
+            1_100,
+        );
+        assert_eq!(state.window_start_ms(), 700);
+
+        // The next window starts at 700ms, so only the new words come back
+        let progress = state.insert(
+            &window(&[("three", 100, 300), ("four", 400, 600)]),
+            600,
+            1_100,
+        );
+        assert_eq!(progress.committed, "one two three");
+        assert_eq!(progress.tentative, "four");
+        assert_eq!(state.committed_words()[2].start_ms, 800);
+    }
+
+    #[test]
+    fn repeated_committed_words_are_ignored() {
+        let mut state = StreamingState::new();
+        state.insert(
+            &window(&[("good", 0, 300), ("morning", 400, 700)]),
+            1_000,
+            10_000,
+        );
+        state.insert(
+            &window(&[("good", 0, 300), ("morning", 400, 700)]),
+            1_000,
+            900,
+        );
+        assert_eq!(state.window_start_ms(), 700);
+
+        // Whisper re-emits the last committed word at the start of the window
+        let progress = state.insert(
+            &window(&[("morning.", 0, 100), ("everyone", 200, 600)]),
+            600,
+            10_000,
+        );
+        assert_eq!(progress.committed, "good morning");
+        assert_eq!(progress.tentative, "everyone");
+    }
+
+    #[test]
+    fn long_commit_before_first_slide_is_not_repeated() {
+        let words: Vec<(String, i64, i64)> = (0..10)
+            .map(|i| (format!("w{i}"), i * 300, i * 300 + 200))
+            .collect();
+        let pass = |n: usize| {
+            let words: Vec<_> = words[..n]
+                .iter()
+                .map(|(text, start, end)| (text.as_str(), *start, *end))
+                .collect();
+            window(&words)
+        };
+
+        let mut state = StreamingState::new();
+        state.insert(&pass(6), 2_000, 10_000);
+        let progress = state.insert(&pass(7), 2_200, 10_000);
+        assert_eq!(progress.committed, "w0 w1 w2 w3 w4 w5");
+
+        // The window still starts at 0, so every pass repeats all six words
+        state.insert(&pass(8), 2_500, 10_000);
+        let progress = state.insert(&pass(9), 2_800, 10_000);
+        assert_eq!(progress.committed, "w0 w1 w2 w3 w4 w5 w6 w7");
+        assert_eq!(progress.tentative, "w8");
+    }
+
+    #[test]
+    fn window_without_agreement_is_force_committed() {
+        let mut state = StreamingState::new();
+        state.insert(&window(&[("alpha", 0, 300)]), 1_000, 10_000);
+        let progress = state.insert(
+            &window(&[("beta", 0, 300), ("gamma", 400, 800)]),
+            2_000,
+            1_500,
+        );
+        assert_eq!(progress.committed, "beta gamma");
+        assert_eq!(progress.tentative, "");
+        assert_eq!(state.window_start_ms(), 800);
+    }
+
+    #[test]
+    fn finish_appends_tail_after_committed_words() {
+        let mut state = StreamingState::new();
+        state.insert(
+            &window(&[("see", 0, 300), ("you", 400, 600)]),
+            1_000,
+            10_000,
+        );
+        state.insert(
+            &window(&[("see", 0, 300), ("you", 400, 600)]),
+            1_000,
+            10_000,
+        );
+
+        let transcript = state.finish(&window(&[("you", 0, 100), ("soon.", 150, 500)]), 600);
+        assert_eq!(transcript.text, "see you soon.");
+        assert_eq!(transcript.segments.len(), 1);
+        assert_eq!(transcript.segments[0].end_ms, 1_100);
+    }
+
+    #[test]
+    fn silent_window_slides_but_keeps_tail() {
+        let mut state = StreamingState::new();
+        state.insert(&Transcript::default(), 5_000, 4_000);
+        assert_eq!(state.window_start_ms(), 4_000);
+    }
+}
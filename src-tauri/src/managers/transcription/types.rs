@@ -0,0 +1,282 @@
+//! Structured transcription results shared by every engine.
+//!
+//! All timings are in milliseconds relative to the start of the audio that was
+//! passed to `TranscriptionManager::transcribe`. Callers that transcribe a slice
+//! of a longer recording (meetings, streaming windows) shift them with
+//! [`Transcript::offset_by`].
+
+use serde::{Deserialize, Serialize};
+
+/// Gap between two words that forces a new segment when an engine only reports words.
+const SEGMENT_SPLIT_GAP_MS: i64 = 1_000;
+
+/// A single recognised word.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// Engine confidence in `0.0..=1.0`, when the engine reports one.
+    #[serde(default)]
+    pub probability: Option<f32>,
+}
+
+/// A phrase or sentence with its timing and (optionally) its words.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    #[serde(default)]
+    pub probability: Option<f32>,
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Full result of one transcription call.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub text: String,
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    /// Spoken language as an ISO 639-1 code: detected by the engine, or the
+    /// language it was asked to recognise.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Probability of `language`, when the engine detected it and reports one.
+    #[serde(default)]
+    pub language_probability: Option<f32>,
+}
+
+impl Transcript {
+    /// Build a transcript from words alone, grouped with [`segments_from_words`].
+    pub fn from_words(words: Vec<TranscriptWord>) -> Self {
+        let text = words
+            .iter()
+            .map(|w| w.text.trim())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            text,
+            segments: segments_from_words(words),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// Append a transcript that follows this one on the same clock.
+    ///
+    /// The language of the first part that has one is kept.
+    pub fn append(&mut self, other: Transcript) {
+        if self.language.is_none() {
+            self.language = other.language;
+            self.language_probability = other.language_probability;
+        }
+        let other_text = other.text.trim();
+        if !other_text.is_empty() {
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            self.text.push_str(other_text);
+        }
+        self.segments.extend(other.segments);
+    }
+
+    /// Shift every timestamp by `offset_ms`.
+    pub fn offset_by(mut self, offset_ms: i64) -> Self {
+        for segment in &mut self.segments {
+            segment.start_ms += offset_ms;
+            segment.end_ms += offset_ms;
+            for word in &mut segment.words {
+                word.start_ms += offset_ms;
+                word.end_ms += offset_ms;
+            }
+        }
+        self
+    }
+
+    /// Apply a text transformation to the full text and to each segment.
+    ///
+    /// Word entries keep the raw engine output so their timings stay truthful.
+    pub fn map_text<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> String,
+    {
+        self.text = f(&self.text);
+        for segment in &mut self.segments {
+            segment.text = f(&segment.text);
+        }
+        self
+    }
+
+    /// Trim surrounding whitespace from the text and every segment.
+    pub fn trimmed(self) -> Self {
+        self.map_text(|s| s.trim().to_string())
+    }
+}
+
+/// Mean of the given probabilities, or `None` if there are none.
+pub fn mean_probability<I>(probabilities: I) -> Option<f32>
+where
+    I: IntoIterator<Item = f32>,
+{
+    let (sum, count) = probabilities
+        .into_iter()
+        .fold((0.0f32, 0usize), |(sum, count), p| (sum + p, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f32)
+    }
+}
+
+/// Group a flat list of words into sentence-like segments.
+///
+/// Used for engines that report word timings but no segment boundaries. A new
+/// segment starts after sentence-ending punctuation or a long pause.
+pub fn segments_from_words(words: Vec<TranscriptWord>) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+    let mut current: Vec<TranscriptWord> = Vec::new();
+
+    let flush = |current: &mut Vec<TranscriptWord>, segments: &mut Vec<TranscriptSegment>| {
+        if current.is_empty() {
+            return;
+        }
+        let words = std::mem::take(current);
+        segments.push(TranscriptSegment {
+            text: words
+                .iter()
+                .map(|w| w.text.trim())
+                .collect::<Vec<_>>()
+                .join(" "),
+            start_ms: words.first().map(|w| w.start_ms).unwrap_or(0),
+            end_ms: words.last().map(|w| w.end_ms).unwrap_or(0),
+            probability: mean_probability(words.iter().filter_map(|w| w.probability)),
+            words,
+        });
+    };
+
+    for word in words {
+        if word.text.trim().is_empty() {
+            continue;
+        }
+        if let Some(prev) = current.last() {
+            if word.start_ms - prev.end_ms > SEGMENT_SPLIT_GAP_MS {
+                flush(&mut current, &mut segments);
+            }
+        }
+        let ends_sentence = word.text.trim_end().ends_with(['.', '?', '!']);
+        current.push(word);
+        if ends_sentence {
+            flush(&mut current, &mut segments);
+        }
+    }
+    flush(&mut current, &mut segments);
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start_ms: i64, end_ms: i64) -> TranscriptWord {
+        TranscriptWord {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            probability: None,
+        }
+    }
+
+    #[test]
+    fn segments_split_on_sentence_punctuation() {
+        let segments = segments_from_words(vec![
+            word("Hello", 0, 300),
+            word("there.", 300, 600),
+            word("How", 700, 900),
+            word("are", 900, 1_000),
+            word("you?", 1_000, 1_300),
+        ]);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Hello there.");
+        assert_eq!((segments[0].start_ms, segments[0].end_ms), (0, 600));
+        assert_eq!(segments[1].text, "How are you?");
+        assert_eq!(segments[1].words.len(), 3);
+    }
+
+    #[test]
+    fn segments_split_on_long_pause() {
+        let segments =
+            segments_from_words(vec![word("first", 0, 400), word("second", 2_000, 2_400)]);
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[1].start_ms, 2_000);
+    }
+
+    #[test]
+    fn segment_probability_is_mean_of_words() {
+        let mut a = word("a", 0, 100);
+        a.probability = Some(0.5);
+        let mut b = word("b", 100, 200);
+        b.probability = Some(1.0);
+
+        let segments = segments_from_words(vec![a, b]);
+        assert_eq!(segments[0].probability, Some(0.75));
+    }
+
+    #[test]
+    fn offset_shifts_segments_and_words() {
+        let transcript = Transcript {
+            text: "Hi.".to_string(),
+            segments: segments_from_words(vec![word("Hi.", 100, 400)]),
+            ..Transcript::default()
+        }
+        .offset_by(30_000);
+
+        assert_eq!(transcript.segments[0].start_ms, 30_100);
+        assert_eq!(transcript.segments[0].words[0].end_ms, 30_400);
+    }
+
+    #[test]
+    fn from_words_joins_text_and_groups_segments() {
+        let transcript = Transcript::from_words(vec![word("One.", 0, 300), word(" two", 400, 600)]);
+
+        assert_eq!(transcript.text, "One. two");
+        assert_eq!(transcript.segments.len(), 2);
+    }
+
+    #[test]
+    fn append_joins_text_and_keeps_segments() {
+        let mut transcript = Transcript::default();
+        transcript.append(Transcript::from_words(vec![word("One.", 0, 300)]));
+        transcript.append(Transcript::default());
+        let mut second = Transcript::from_words(vec![word("Two.", 30_000, 30_300)]);
+        second.language = Some("en".to_string());
+        transcript.append(second);
+
+        assert_eq!(transcript.text, "One. Two.");
+        assert_eq!(transcript.language.as_deref(), Some("en"));
+        assert_eq!(transcript.segments.len(), 2);
+        assert_eq!(transcript.segments[1].start_ms, 30_000);
+    }
+
+    #[test]
+    fn map_text_leaves_word_timings_untouched() {
+        let transcript = Transcript {
+            text: " hi ".to_string(),
+            segments: segments_from_words(vec![word(" hi ", 0, 100)]),
+            ..Transcript::default()
+        }
+        .trimmed();
+
+        assert_eq!(transcript.text, "hi");
+        assert_eq!(transcript.segments[0].text, "hi");
+        assert_eq!(transcript.segments[0].words[0].text, " hi ");
+    }
+}
@@ -0,0 +1,384 @@
+//! Whisper engine built directly on `whisper-rs`.
+//!
+//! `transcribe_rs` only exposes segment text and times for whisper, so this
+//! wrapper drives whisper.cpp itself to get token timestamps and
+//! probabilities, which are folded into words.
+
+use super::backend::{BackendCapabilities, SpeechBackend, TranscribeOptions};
+use super::types::{mean_probability, Transcript, TranscriptSegment, TranscriptWord};
+use crate::settings::MAX_WHISPER_INITIAL_PROMPT_CHARS;
+use anyhow::Result;
+use log::{debug, warn};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+};
+
+/// whisper.cpp keeps at most half of its 448-token context for the prompt;
+/// stay well under that so long vocabularies don't get cut mid-word.
+const MAX_PROMPT_CHARS: usize = 600;
+
+/// Build an initial prompt that biases whisper toward the given custom words.
+///
+/// Whisper conditions on the prompt as if it were preceding speech, so the
+/// spelling used here is the spelling it tends to reproduce.
+pub(super) fn vocabulary_prompt(custom_words: &[String]) -> Option<String> {
+    vocabulary_prompt_within(custom_words, MAX_PROMPT_CHARS)
+}
+
+/// The user's initial prompt followed by the custom words, within the prompt budget.
+fn initial_prompt(prompt: Option<&str>, custom_words: &[String]) -> Option<String> {
+    // A prompt edited into the settings file can be longer than the settings allow
+    let prompt: Option<String> = prompt
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| p.chars().take(MAX_WHISPER_INITIAL_PROMPT_CHARS).collect());
+    let budget =
+        MAX_PROMPT_CHARS.saturating_sub(prompt.as_ref().map_or(0, |p| p.chars().count() + 1));
+    match (prompt, vocabulary_prompt_within(custom_words, budget)) {
+        (Some(prompt), Some(words)) => Some(format!("{} {}", prompt, words)),
+        (prompt, None) => prompt,
+        (None, words) => words,
+    }
+}
+
+fn vocabulary_prompt_within(custom_words: &[String], max_chars: usize) -> Option<String> {
+    let mut prompt = String::new();
+    let mut chars = 0;
+    for word in custom_words
+        .iter()
+        .map(|w| w.trim())
+        .filter(|w| !w.is_empty())
+    {
+        let separator = if prompt.is_empty() { "" } else { ", " };
+        let added = separator.len() + word.chars().count();
+        // Leave room for the closing full stop
+        if chars + added + 1 > max_chars {
+            break;
+        }
+        prompt.push_str(separator);
+        prompt.push_str(word);
+        chars += added;
+    }
+
+    if prompt.is_empty() {
+        None
+    } else {
+        Some(format!("{}.", prompt))
+    }
+}
+
+/// Normalise a language name or code ("english", "en") to whisper's code.
+pub(super) fn language_code(language: &str) -> Option<String> {
+    let id = whisper_rs::get_lang_id(&language.trim().to_lowercase())?;
+    whisper_rs::get_lang_str(id).map(str::to_string)
+}
+
+/// Decoder threads: the configured count, or whisper.cpp's default.
+fn decoding_threads(configured: u32) -> usize {
+    if configured > 0 {
+        return configured as usize;
+    }
+    std::thread::available_parallelism()
+        .map(|n| n.get().min(4))
+        .unwrap_or(1)
+}
+
+struct LoadedWhisper {
+    context: WhisperContext,
+    state: WhisperState,
+}
+
+pub struct WhisperBackend {
+    loaded: Option<LoadedWhisper>,
+}
+
+impl WhisperBackend {
+    pub fn new() -> Self {
+        Self { loaded: None }
+    }
+}
+
+impl SpeechBackend for WhisperBackend {
+    fn id(&self) -> &'static str {
+        "whisper"
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            streaming: false,
+            segment_timestamps: true,
+            word_timestamps: true,
+            translation: true,
+            language_selection: true,
+            vocabulary_biasing: true,
+            languages: Vec::new(),
+        }
+    }
+
+    fn load(&mut self, model_path: &Path) -> Result<()> {
+        let path = model_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid model path: {:?}", model_path))?;
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        let state = context
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        self.loaded = Some(LoadedWhisper { context, state });
+        Ok(())
+    }
+
+    fn unload(&mut self) {
+        // whisper.cpp frees its context when it is dropped
+        self.loaded = None;
+    }
+
+    fn transcribe(&mut self, audio: &[f32], options: &TranscribeOptions) -> Result<Transcript> {
+        let loaded = self
+            .loaded
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Whisper model is not loaded"))?;
+        let decoding = &options.whisper;
+        let threads = decoding_threads(decoding.threads);
+        let prompt = initial_prompt(decoding.initial_prompt.as_deref(), &options.custom_words);
+
+        // whisper.cpp detects the language inside `full` but discards the
+        // probability, so it is only measured here when asked for. That costs
+        // an extra mel computation and encoder pass over the first 30s, since
+        // `full` encodes the audio again
+        let (language, language_probability) = match &options.language {
+            Some(language) => (Some(language.clone()), None),
+            None if options.language_confidence => match loaded.detect_language(audio, threads) {
+                Ok((language, probability)) => (Some(language), Some(probability)),
+                Err(e) => {
+                    warn!("Whisper language detection failed: {}", e);
+                    (None, None)
+                }
+            },
+            None => (None, None),
+        };
+
+        let strategy = if decoding.beam_size > 1 {
+            SamplingStrategy::BeamSearch {
+                beam_size: decoding.beam_size as i32,
+                patience: -1.0,
+            }
+        } else {
+            SamplingStrategy::Greedy {
+                best_of: decoding.best_of as i32,
+            }
+        };
+        debug!("Whisper decoding with {:?}", decoding);
+
+        let mut params = FullParams::new(strategy);
+        params.set_n_threads(threads as i32);
+        params.set_temperature(decoding.temperature);
+        params.set_temperature_inc(decoding.temperature_increment);
+        params.set_no_speech_thold(decoding.no_speech_threshold);
+        params.set_language(language.as_deref());
+        params.set_translate(options.translate);
+        if let Some(prompt) = &prompt {
+            params.set_initial_prompt(prompt);
+        }
+        params.set_token_timestamps(true);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_suppress_blank(true);
+        params.set_suppress_non_speech_tokens(true);
+        let interrupt = options.interrupt.clone();
+        params.set_abort_callback_safe(move || interrupt.load(Ordering::SeqCst));
+
+        loaded.state.full(params, audio).map_err(|e| {
+            if options.interrupt.load(Ordering::SeqCst) {
+                anyhow::anyhow!("Whisper transcription interrupted")
+            } else {
+                anyhow::anyhow!("{:?}", e)
+            }
+        })?;
+
+        let mut transcript = loaded.collect_transcript()?;
+        transcript.language = language.or_else(|| loaded.detected_language());
+        transcript.language_probability = language_probability;
+        Ok(transcript)
+    }
+}
+
+impl LoadedWhisper {
+    /// Most likely spoken language and its probability.
+    fn detect_language(&mut self, audio: &[f32], threads: usize) -> Result<(String, f32)> {
+        self.state
+            .pcm_to_mel(audio, threads)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        let probabilities = self
+            .state
+            .lang_detect(0, threads)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+
+        let (id, probability) = probabilities
+            .into_iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .ok_or_else(|| anyhow::anyhow!("No language probabilities"))?;
+        let language = whisper_rs::get_lang_str(id as i32)
+            .ok_or_else(|| anyhow::anyhow!("Unknown language id {}", id))?;
+        debug!(
+            "Detected language '{}' with probability {:.2}",
+            language, probability
+        );
+        Ok((language.to_string(), probability))
+    }
+
+    /// Language `full` detected on its own, when none was set.
+    fn detected_language(&self) -> Option<String> {
+        let id = self.state.full_lang_id_from_state().ok()?;
+        whisper_rs::get_lang_str(id).map(str::to_string)
+    }
+
+    fn collect_transcript(&self) -> Result<Transcript> {
+        let token_eot = self.context.token_eot();
+        let n_segments = self
+            .state
+            .full_n_segments()
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+
+        let mut segments = Vec::with_capacity(n_segments.max(0) as usize);
+        for i in 0..n_segments {
+            let text = self
+                .state
+                .full_get_segment_text_lossy(i)
+                .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+            // whisper.cpp reports times in centiseconds
+            let start_ms = self.state.full_get_segment_t0(i).unwrap_or(0) * 10;
+            let end_ms = self.state.full_get_segment_t1(i).unwrap_or(0) * 10;
+
+            let mut words: Vec<TranscriptWord> = Vec::new();
+            let mut word_probs: Vec<Vec<f32>> = Vec::new();
+            let n_tokens = self.state.full_n_tokens(i).unwrap_or(0);
+            for j in 0..n_tokens {
+                let Ok(data) = self.state.full_get_token_data(i, j) else {
+                    continue;
+                };
+                // Skip special tokens (timestamps, [_BEG_], end of text...)
+                if data.id >= token_eot {
+                    continue;
+                }
+                let Ok(token_text) = self.state.full_get_token_text_lossy(i, j) else {
+                    continue;
+                };
+                if token_text.is_empty() {
+                    continue;
+                }
+
+                let starts_word = token_text.starts_with(' ') || words.is_empty();
+                if starts_word {
+                    words.push(TranscriptWord {
+                        text: token_text.trim_start().to_string(),
+                        start_ms: data.t0 * 10,
+                        end_ms: data.t1 * 10,
+                        probability: None,
+                    });
+                    word_probs.push(vec![data.p]);
+                } else if let (Some(word), Some(probs)) = (words.last_mut(), word_probs.last_mut())
+                {
+                    word.text.push_str(&token_text);
+                    word.end_ms = data.t1 * 10;
+                    probs.push(data.p);
+                }
+            }
+
+            for (word, probs) in words.iter_mut().zip(word_probs.iter()) {
+                word.probability = mean_probability(probs.iter().copied());
+            }
+            words.retain(|w| !w.text.trim().is_empty());
+
+            segments.push(TranscriptSegment {
+                text: text.trim().to_string(),
+                start_ms,
+                end_ms,
+                probability: mean_probability(word_probs.into_iter().flatten()),
+                words,
+            });
+        }
+
+        let text = segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(Transcript {
+            text,
+            segments,
+            ..Transcript::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vocabulary_prompt_lists_words() {
+        let words = vec!["Tauri".to_string(), " ".to_string(), "Parakeet".to_string()];
+        assert_eq!(
+            vocabulary_prompt(&words),
+            Some("Tauri, Parakeet.".to_string())
+        );
+    }
+
+    #[test]
+    fn language_code_accepts_names_and_codes() {
+        assert_eq!(language_code("English"), Some("en".to_string()));
+        assert_eq!(language_code("fr"), Some("fr".to_string()));
+        assert_eq!(language_code("klingon"), None);
+    }
+
+    #[test]
+    fn vocabulary_prompt_empty_without_words() {
+        assert_eq!(vocabulary_prompt(&[]), None);
+    }
+
+    #[test]
+    fn initial_prompt_puts_user_prompt_before_words() {
+        let words = vec!["Tauri".to_string()];
+        assert_eq!(
+            initial_prompt(Some(" Meeting notes. "), &words),
+            Some("Meeting notes. Tauri.".to_string())
+        );
+        assert_eq!(initial_prompt(Some(""), &words), Some("Tauri.".to_string()));
+        assert_eq!(
+            initial_prompt(Some("Meeting notes."), &[]),
+            Some("Meeting notes.".to_string())
+        );
+
+        let many: Vec<String> = (0..500).map(|i| format!("word{}", i)).collect();
+        let prompt = initial_prompt(Some("Meeting notes."), &many).unwrap();
+        assert!(prompt.starts_with("Meeting notes. word0"));
+        assert!(prompt.chars().count() <= MAX_PROMPT_CHARS);
+    }
+
+    #[test]
+    fn initial_prompt_budget_counts_characters() {
+        let words: Vec<String> = (0..500).map(|i| format!("mot{}é", i)).collect();
+        let long = "é".repeat(MAX_WHISPER_INITIAL_PROMPT_CHARS + 50);
+        let prompt = initial_prompt(Some(&long), &words).unwrap();
+        assert!(prompt.starts_with(&"é".repeat(MAX_WHISPER_INITIAL_PROMPT_CHARS)));
+        assert!(!prompt.starts_with(&"é".repeat(MAX_WHISPER_INITIAL_PROMPT_CHARS + 1)));
+        assert!(prompt.contains("mot0é"));
+        assert!(prompt.chars().count() <= MAX_PROMPT_CHARS);
+    }
+
+    #[test]
+    fn vocabulary_prompt_is_bounded() {
+        let words: Vec<String> = (0..500).map(|i| format!("word{}", i)).collect();
+        let prompt = vocabulary_prompt(&words).unwrap();
+        assert!(prompt.chars().count() <= MAX_PROMPT_CHARS);
+        assert!(prompt.ends_with('.'));
+    }
+}
@@ -6,7 +6,8 @@ use anyhow::{Context, Result};
 use log::{debug, info, warn};
 use std::sync::Mutex;
 use tts::{Features, Tts, Voice};
-use whichlang::detect_language;
+
+use crate::audio_toolkit::detect_text_language;
 
 /// Manager for system TTS with automatic language detection
 pub struct TtsManager {
@@ -50,25 +51,7 @@ impl TtsManager {
 
     /// Detect the language of the given text
     fn detect_language(&self, text: &str) -> Option<String> {
-        let detected = detect_language(text);
-        let lang_code = match detected {
-            whichlang::Lang::Ara => "ar",
-            whichlang::Lang::Cmn => "zh",
-            whichlang::Lang::Deu => "de",
-            whichlang::Lang::Eng => "en",
-            whichlang::Lang::Fra => "fr",
-            whichlang::Lang::Hin => "hi",
-            whichlang::Lang::Ita => "it",
-            whichlang::Lang::Jpn => "ja",
-            whichlang::Lang::Kor => "ko",
-            whichlang::Lang::Nld => "nl",
-            whichlang::Lang::Por => "pt",
-            whichlang::Lang::Rus => "ru",
-            whichlang::Lang::Spa => "es",
-            whichlang::Lang::Swe => "sv",
-            whichlang::Lang::Tur => "tr",
-            whichlang::Lang::Vie => "vi",
-        };
+        let lang_code = detect_text_language(text)?;
         info!(
             "Detected language: {} for text: {}...",
             lang_code,
@@ -99,14 +82,20 @@ impl TtsManager {
         })
     }
 
-    /// Speak text using system TTS with automatic language detection and voice selection
-    pub fn speak(&self, text: &str) -> Result<()> {
+    /// Speak text using system TTS, choosing a voice for `language`.
+    ///
+    /// The language is detected from the text when it isn't known.
+    pub fn speak(&self, text: &str, language: Option<&str>) -> Result<()> {
         if !self.is_ready() {
             self.initialize()?;
         }
 
         // Detect language and find appropriate voice
-        if let Some(detected_lang) = self.detect_language(text) {
+        let language = match language {
+            Some(language) => Some(language.to_string()),
+            None => self.detect_language(text),
+        };
+        if let Some(detected_lang) = language {
             if let Some(voice) = self.find_voice_for_language(&detected_lang) {
                 let mut guard = self.system_tts.lock().unwrap();
                 let tts = guard.as_mut().context("TTS not initialized")?;
@@ -0,0 +1,196 @@
+//! Wake word activation.
+//!
+//! Runs a keyword spotter on the always-on microphone stream and starts the
+//! binding mapped to each wake word when it is heard. Recordings started this
+//! way stop by themselves once the speaker falls silent.
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use std::sync::{mpsc, Arc};
+use tauri::{AppHandle, Manager};
+
+use super::audio::AudioRecordingManager;
+use super::model::ModelManager;
+use crate::actions::action_for_binding;
+use crate::audio_toolkit::{Keyword, SherpaKeywordSpotter};
+use crate::settings::{self, WakeWord};
+use crate::ManagedToggleState;
+
+pub const KWS_MODEL_ID: &str = "kws-zipformer-gigaspeech";
+const KEYWORDS_FILENAME: &str = "wake_words.txt";
+const LABEL_PREFIX: &str = "wake_";
+
+pub struct WakeWordManager {
+    app_handle: AppHandle,
+    model_manager: Arc<ModelManager>,
+}
+
+impl WakeWordManager {
+    pub fn new(app_handle: &AppHandle, model_manager: Arc<ModelManager>) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            model_manager,
+        }
+    }
+
+    /// Load the spotter for the current settings, or remove it if wake words
+    /// are off. Call after any wake word or microphone mode setting changes.
+    pub fn reload(&self) -> Result<()> {
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        let settings = settings::get_settings(&self.app_handle);
+        if !settings.wake_word_enabled || !settings.always_on_microphone {
+            rm.set_keyword_spotter(None);
+            return Ok(());
+        }
+
+        // Drop the old spotter first, so a failed reload leaves none running
+        rm.set_keyword_spotter(None);
+        let model_dir = self
+            .model_manager
+            .get_model_path(KWS_MODEL_ID)
+            .context("Wake word model not available")?;
+        let keywords_file = self
+            .app_handle
+            .path()
+            .app_data_dir()?
+            .join(KEYWORDS_FILENAME);
+
+        let spotter = SherpaKeywordSpotter::new(
+            &model_dir,
+            &keywords(&settings.wake_words),
+            settings.wake_word_sensitivity,
+            &keywords_file,
+        )?;
+        rm.set_keyword_spotter(Some(Box::new(spotter)));
+        info!("Listening for {} wake word(s)", settings.wake_words.len());
+        Ok(())
+    }
+}
+
+/// The spotter's keywords, labelled by their position in the settings.
+fn keywords(wake_words: &[WakeWord]) -> Vec<Keyword> {
+    wake_words
+        .iter()
+        .enumerate()
+        .map(|(index, wake_word)| Keyword {
+            label: format!("{}{}", LABEL_PREFIX, index),
+            phrase: wake_word.phrase.clone(),
+            tokens: wake_word.tokens.clone(),
+        })
+        .collect()
+}
+
+/// The wake word a spotter label stands for.
+fn wake_word_for_label<'a>(wake_words: &'a [WakeWord], label: &str) -> Option<&'a WakeWord> {
+    let index: usize = label.strip_prefix(LABEL_PREFIX)?.parse().ok()?;
+    wake_words.get(index)
+}
+
+/// Called by the recorder when the spotter hears a wake word.
+pub fn handle_keyword(app: &AppHandle, label: String) {
+    // The recorder calls this from its audio thread, which starting a
+    // recording would block on
+    let app = app.clone();
+    std::thread::spawn(move || run_wake_word(&app, &label));
+}
+
+fn run_wake_word(app: &AppHandle, label: &str) {
+    let settings = settings::get_settings(app);
+    let Some(wake_word) = wake_word_for_label(&settings.wake_words, label) else {
+        warn!("Unknown wake word label '{}'", label);
+        return;
+    };
+    let binding_id = wake_word.binding_id.as_str();
+    let Some(action) = action_for_binding(binding_id) else {
+        warn!("No action defined in ACTION_MAP for binding ID '{binding_id}'");
+        return;
+    };
+    info!(
+        "Wake word '{}' heard, starting '{}'",
+        wake_word.phrase, binding_id
+    );
+    let shortcut_string = "wake word";
+
+    if action.is_toggle() {
+        action.start(app, binding_id, shortcut_string);
+        return;
+    }
+
+    {
+        let toggle_state_manager = app.state::<ManagedToggleState>();
+        let Ok(mut states) = toggle_state_manager.lock() else {
+            warn!("Failed to lock toggle state manager");
+            return;
+        };
+        let is_currently_active = states
+            .active_toggles
+            .entry(binding_id.to_string())
+            .or_insert(false);
+        if *is_currently_active {
+            debug!("Wake word: '{}' is already active", binding_id);
+            return;
+        }
+        *is_currently_active = true;
+    }
+    action.start(app, binding_id, shortcut_string);
+
+    // Nobody is holding a key, so end the recording when the speaker stops
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    let (done_tx, done_rx) = mpsc::channel();
+    if let Err(e) = rm.stop_on_silence(binding_id, settings.hands_free_silence_ms, done_tx) {
+        debug!("Wake word: not stopping on silence: {}", e);
+        return;
+    }
+    // The channel closes without a message if the recording is stopped some
+    // other way first
+    if done_rx.recv().is_err() {
+        return;
+    }
+
+    let toggle_state_manager = app.state::<ManagedToggleState>();
+    let mut states = match toggle_state_manager.lock() {
+        Ok(s) => s,
+        Err(e) => {
+            warn!("Failed to lock toggle state manager: {e}");
+            return;
+        }
+    };
+    if let Some(active) = states.active_toggles.get_mut(binding_id) {
+        if *active {
+            *active = false;
+            drop(states);
+            debug!("Wake word: stopping '{}' after silence", binding_id);
+            action.stop(app, binding_id, shortcut_string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wake_word(phrase: &str, binding_id: &str) -> WakeWord {
+        WakeWord {
+            phrase: phrase.to_string(),
+            tokens: None,
+            binding_id: binding_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn labels_map_back_to_wake_words() {
+        let wake_words = vec![
+            wake_word("hey echo", "transcribe"),
+            wake_word("echo hands free", "hands_free"),
+        ];
+        let keywords = keywords(&wake_words);
+        assert_eq!(keywords[1].label, "wake_1");
+
+        for keyword in &keywords {
+            let found = wake_word_for_label(&wake_words, &keyword.label).unwrap();
+            assert_eq!(found.phrase, keyword.phrase);
+        }
+        assert!(wake_word_for_label(&wake_words, "wake_2").is_none());
+        assert!(wake_word_for_label(&wake_words, "HEY_ECHO").is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::managers::transcription::StreamingProgress;
 use crate::settings::{self, OverlayPosition};
 #[cfg(not(target_os = "linux"))]
 use enigo::{Enigo, Mouse};
@@ -336,46 +337,6 @@ pub fn show_transcribing_overlay(app_handle: &AppHandle) {
     });
 }
 
-/// Shows a warning overlay with a custom message
-pub fn show_warning_overlay(app_handle: &AppHandle, message: &str) {
-    // Check if overlay should be shown based on position setting
-    let settings = settings::get_settings(app_handle);
-    if settings.overlay_position == OverlayPosition::None {
-        return;
-    }
-
-    update_overlay_position(app_handle);
-
-    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
-        // On Wayland, bring window to front via GTK APIs
-        #[cfg(target_os = "linux")]
-        if crate::wayland::is_wayland() {
-            crate::wayland::present_gnome_overlay(&overlay_window);
-        }
-        // Emit position preference to frontend for CSS positioning
-        let position = match settings.overlay_position {
-            OverlayPosition::Top => "top",
-            OverlayPosition::Bottom | OverlayPosition::None => "bottom",
-        };
-        let _ = overlay_window.emit("overlay-position", position);
-        // Emit event to show warning state with message
-        let _ = overlay_window.emit(
-            "show-overlay",
-            serde_json::json!({
-                "state": "warning",
-                "message": message
-            }),
-        );
-
-        // Auto-hide after 2 seconds
-        let window_clone = overlay_window.clone();
-        std::thread::spawn(move || {
-            std::thread::sleep(std::time::Duration::from_secs(2));
-            let _ = window_clone.emit("hide-overlay", ());
-        });
-    }
-}
-
 /// Shows a tool result overlay with a custom message (auto-hides after 3 seconds)
 pub fn show_tool_overlay(app_handle: &AppHandle, message: &str) {
     let settings = settings::get_settings(app_handle);
@@ -447,13 +408,13 @@ pub fn emit_levels(app_handle: &AppHandle, levels: &Vec<f32>) {
     }
 }
 
-pub fn emit_transcription_progress(app_handle: &AppHandle, text: &str) {
+pub fn emit_transcription_progress(app_handle: &AppHandle, progress: &StreamingProgress) {
     // emit to main app
-    let _ = app_handle.emit("transcription-progress", text);
+    let _ = app_handle.emit("transcription-progress", progress);
 
     // also emit to the recording overlay if it's open
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
-        let _ = overlay_window.emit("transcription-progress", text);
+        let _ = overlay_window.emit("transcription-progress", progress);
     }
 }
 
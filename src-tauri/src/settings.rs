@@ -20,6 +20,140 @@ pub struct ShortcutBinding {
     pub description: String,
     pub default_binding: String,
     pub current_binding: String,
+    /// Transcription profile used by this binding instead of the global settings.
+    #[serde(default)]
+    pub profile_id: Option<String>,
+}
+
+/// A phrase that triggers a binding when heard on the always-on microphone.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WakeWord {
+    pub phrase: String,
+    /// Space-separated model tokens, for phrases the built-in spelling gets
+    /// wrong.
+    #[serde(default)]
+    pub tokens: Option<String>,
+    /// Binding in `ACTION_MAP` started when the phrase is heard.
+    pub binding_id: String,
+}
+
+/// Named overrides of the transcription settings, referenced by bindings.
+///
+/// Fields left as `None` fall back to the global setting.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TranscriptionProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub translate_to_english: Option<bool>,
+    #[serde(default)]
+    pub post_process_enabled: Option<bool>,
+    #[serde(default)]
+    pub post_process_prompt_id: Option<String>,
+}
+
+impl TranscriptionProfile {
+    /// Overwrite the global settings this profile overrides.
+    pub fn apply(&self, settings: &mut AppSettings) {
+        if let Some(model) = &self.model {
+            settings.selected_model = model.clone();
+        }
+        if let Some(language) = &self.language {
+            settings.selected_language = language.clone();
+        }
+        if let Some(translate) = self.translate_to_english {
+            settings.translate_to_english = translate;
+        }
+        if let Some(enabled) = self.post_process_enabled {
+            settings.post_process_enabled = enabled;
+        }
+        if let Some(prompt_id) = &self.post_process_prompt_id {
+            settings.post_process_selected_prompt_id = Some(prompt_id.clone());
+        }
+    }
+}
+
+/// Longest initial prompt accepted, leaving whisper room for the custom words.
+pub const MAX_WHISPER_INITIAL_PROMPT_CHARS: usize = 400;
+
+/// Whisper decoding parameters, stored per model id.
+///
+/// By default whisper decodes greedily with a single candidate, its fastest
+/// setting.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WhisperDecodingParams {
+    /// Beams kept while decoding; 1 decodes greedily.
+    pub beam_size: u32,
+    /// Candidates sampled per attempt when decoding greedily above temperature 0.
+    pub best_of: u32,
+    /// Sampling temperature of the first attempt.
+    pub temperature: f32,
+    /// Added to the temperature each time a segment fails whisper's quality
+    /// checks and is decoded again; 0 disables the fallback.
+    pub temperature_increment: f32,
+    /// Segments whose no-speech probability exceeds this are treated as silence.
+    pub no_speech_threshold: f32,
+    /// Decoder threads; 0 lets whisper.cpp choose.
+    pub threads: u32,
+    /// Text whisper conditions on as if it preceded the audio.
+    pub initial_prompt: Option<String>,
+}
+
+impl Default for WhisperDecodingParams {
+    fn default() -> Self {
+        Self {
+            beam_size: 1,
+            best_of: 1,
+            temperature: 0.0,
+            temperature_increment: 0.2,
+            no_speech_threshold: 0.6,
+            threads: 0,
+            initial_prompt: None,
+        }
+    }
+}
+
+impl WhisperDecodingParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=16).contains(&self.beam_size) {
+            return Err("Beam size must be between 1 and 16".to_string());
+        }
+        if !(1..=16).contains(&self.best_of) {
+            return Err("Best-of must be between 1 and 16".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.temperature) {
+            return Err("Temperature must be between 0 and 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.temperature_increment) {
+            return Err("Temperature increment must be between 0 and 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.no_speech_threshold) {
+            return Err("No-speech threshold must be between 0 and 1".to_string());
+        }
+        let max_threads = std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(1);
+        if self.threads > max_threads {
+            return Err(format!(
+                "Thread count must be at most {} on this machine",
+                max_threads
+            ));
+        }
+        if let Some(prompt) = &self.initial_prompt {
+            if prompt.chars().count() > MAX_WHISPER_INITIAL_PROMPT_CHARS {
+                return Err(format!(
+                    "Initial prompt must be at most {} characters",
+                    MAX_WHISPER_INITIAL_PROMPT_CHARS
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -92,6 +226,21 @@ pub enum RecordingRetentionPeriod {
     Months3,
 }
 
+/// Which voice activity detector the recorder runs.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VadEngine {
+    /// The Silero ONNX model: the most accurate, and the most CPU.
+    #[default]
+    Silero,
+    /// Loudness over the noise floor plus zero crossings; nearly free.
+    Energy,
+    /// WebRTC-style Gaussian mixtures over frequency bands.
+    Gmm,
+    /// No detection: everything the microphone hears is recorded.
+    None,
+}
+
 impl Default for ModelUnloadTimeout {
     fn default() -> Self {
         ModelUnloadTimeout::Never
@@ -188,10 +337,62 @@ pub struct AppSettings {
     pub selected_model: String,
     #[serde(default = "default_always_on_microphone")]
     pub always_on_microphone: bool,
+    /// Hands-free dictation: silence that ends an utterance, in milliseconds.
+    #[serde(default = "default_hands_free_silence_ms")]
+    pub hands_free_silence_ms: u32,
+    /// Hands-free utterances are cut at this length, in milliseconds.
+    #[serde(default = "default_hands_free_max_utterance_ms")]
+    pub hands_free_max_utterance_ms: u32,
+    /// Hands-free utterances with less speech than this are ignored, so coughs
+    /// aren't transcribed.
+    #[serde(default = "default_hands_free_min_speech_ms")]
+    pub hands_free_min_speech_ms: u32,
+    /// Audio kept from before the hotkey is pressed, in milliseconds. Only the
+    /// always-on microphone has anything to keep.
+    #[serde(default = "default_pre_roll_ms")]
+    pub pre_roll_ms: u32,
+    /// Voice activity detector to run. Hands-free dictation and stopping on
+    /// silence need one.
+    #[serde(default)]
+    pub vad_engine: VadEngine,
+    /// Speech probability above which a frame counts as voice. Lower it for
+    /// soft speakers, raise it for noisy rooms.
+    #[serde(default = "default_vad_threshold")]
+    pub vad_threshold: f32,
+    /// 30ms frames kept from before speech starts.
+    #[serde(default = "default_vad_prefill_frames")]
+    pub vad_prefill_frames: u32,
+    /// 30ms frames still kept after speech stops.
+    #[serde(default = "default_vad_hangover_frames")]
+    pub vad_hangover_frames: u32,
+    /// Consecutive voiced 30ms frames needed before speech starts.
+    #[serde(default = "default_vad_onset_frames")]
+    pub vad_onset_frames: u32,
+    /// Filter out rumble below 80 Hz before the VAD.
+    #[serde(default)]
+    pub high_pass_filter_enabled: bool,
+    /// Suppress steady background noise such as fans and keyboards.
+    #[serde(default)]
+    pub noise_suppression_enabled: bool,
+    /// Bring quiet and loud speakers to the same level.
+    #[serde(default)]
+    pub auto_gain_enabled: bool,
+    /// Listen for wake words on the always-on microphone.
+    #[serde(default)]
+    pub wake_word_enabled: bool,
+    #[serde(default = "default_wake_words")]
+    pub wake_words: Vec<WakeWord>,
+    /// From 0.0 (fewest false alarms) to 1.0 (fewest missed wake words).
+    #[serde(default = "default_wake_word_sensitivity")]
+    pub wake_word_sensitivity: f32,
     #[serde(default)]
     pub selected_microphone: Option<String>,
     #[serde(default)]
     pub clamshell_microphone: Option<String>,
+    /// Name patterns of preferred microphones, best first. The first one
+    /// plugged in is used; `selected_microphone` only when none is.
+    #[serde(default)]
+    pub microphone_priority: Vec<String>,
     #[serde(default)]
     pub selected_output_device: Option<String>,
     #[serde(default = "default_translate_to_english")]
@@ -212,6 +413,48 @@ pub struct AppSettings {
     pub model_unload_timeout: ModelUnloadTimeout,
     #[serde(default = "default_word_correction_threshold")]
     pub word_correction_threshold: f64,
+    /// Fuzzy-correct custom words in the finished transcript.
+    #[serde(default = "default_custom_words_fuzzy_correction")]
+    pub custom_words_fuzzy_correction: bool,
+    /// Transcribe through an OpenAI-compatible API, falling back to the local model.
+    #[serde(default)]
+    pub remote_transcription_enabled: bool,
+    /// Post-process provider whose base URL and API key the remote backend uses.
+    #[serde(default = "default_remote_transcription_provider_id")]
+    pub remote_transcription_provider_id: String,
+    #[serde(default = "default_remote_transcription_model")]
+    pub remote_transcription_model: String,
+    #[serde(default)]
+    pub transcription_profiles: Vec<TranscriptionProfile>,
+    /// Whisper decoding parameters by model id; models without an entry use the defaults.
+    #[serde(default)]
+    pub whisper_decoding_params: HashMap<String, WhisperDecodingParams>,
+    /// Drop text whisper produces from silence; see `transcription::filter`.
+    #[serde(default = "default_hallucination_filter_enabled")]
+    pub hallucination_filter_enabled: bool,
+    /// Sentences dropped when they appear on their own, ignoring case and punctuation.
+    #[serde(default = "default_hallucination_phrases")]
+    pub hallucination_phrases: Vec<String>,
+    /// Recordings with less speech than this, as measured by the VAD, produce no text.
+    #[serde(default = "default_hallucination_min_speech_ms")]
+    pub hallucination_min_speech_ms: u32,
+    /// Model catalog manifest to use instead of the built-in one: a path, a
+    /// `file://` URL or an HTTP(S) URL.
+    #[serde(default)]
+    pub model_catalog_source: Option<String>,
+    /// Servers or `file://` directories holding model files, tried before the
+    /// catalog's own download URLs.
+    #[serde(default)]
+    pub model_mirrors: Vec<String>,
+    /// Where models are stored, when moved out of the app data directory.
+    #[serde(default)]
+    pub models_directory: Option<String>,
+    /// Model downloads that run at once; the rest wait in a queue.
+    #[serde(default = "default_max_concurrent_downloads")]
+    pub max_concurrent_downloads: usize,
+    /// Cap on the combined download speed in bytes per second.
+    #[serde(default)]
+    pub download_bandwidth_limit: Option<u64>,
     #[serde(default = "default_history_limit")]
     pub history_limit: usize,
     #[serde(default = "default_recording_retention_period")]
@@ -277,6 +520,50 @@ fn default_always_on_microphone() -> bool {
     false
 }
 
+fn default_pre_roll_ms() -> u32 {
+    300
+}
+
+fn default_vad_threshold() -> f32 {
+    0.3
+}
+
+fn default_vad_prefill_frames() -> u32 {
+    15
+}
+
+fn default_vad_hangover_frames() -> u32 {
+    15
+}
+
+fn default_vad_onset_frames() -> u32 {
+    2
+}
+
+fn default_wake_words() -> Vec<WakeWord> {
+    vec![WakeWord {
+        phrase: "hey echo".to_string(),
+        tokens: None,
+        binding_id: "transcribe".to_string(),
+    }]
+}
+
+fn default_wake_word_sensitivity() -> f32 {
+    0.5
+}
+
+fn default_hands_free_silence_ms() -> u32 {
+    800
+}
+
+fn default_hands_free_max_utterance_ms() -> u32 {
+    30_000
+}
+
+fn default_hands_free_min_speech_ms() -> u32 {
+    300
+}
+
 fn default_translate_to_english() -> bool {
     false
 }
@@ -308,6 +595,10 @@ fn default_word_correction_threshold() -> f64 {
     0.18
 }
 
+fn default_custom_words_fuzzy_correction() -> bool {
+    true
+}
+
 fn default_history_limit() -> usize {
     5
 }
@@ -336,6 +627,49 @@ fn default_post_process_provider_id() -> String {
     "openai".to_string()
 }
 
+fn default_remote_transcription_provider_id() -> String {
+    "openai".to_string()
+}
+
+fn default_remote_transcription_model() -> String {
+    "whisper-1".to_string()
+}
+
+fn default_hallucination_filter_enabled() -> bool {
+    true
+}
+
+fn default_hallucination_phrases() -> Vec<String> {
+    [
+        "Thank you for watching",
+        "Thanks for watching",
+        "Thank you for watching and see you next time",
+        "Please subscribe",
+        "Please like and subscribe",
+        "Don't forget to like and subscribe",
+        "Subtitles by the Amara.org community",
+        "Untertitel der Amara.org-Community",
+        "Untertitel im Auftrag des ZDF, 2017",
+        "Sous-titrage Société Radio-Canada",
+        "Sous-titres réalisés par la communauté d'Amara.org",
+        "Subtítulos realizados por la comunidad de Amara.org",
+        "Продолжение следует...",
+        "字幕由Amara.org社区提供",
+        "ご視聴ありがとうございました",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_max_concurrent_downloads() -> usize {
+    2
+}
+
+fn default_hallucination_min_speech_ms() -> u32 {
+    250
+}
+
 fn default_debug_logging_enabled() -> bool {
     false
 }
@@ -442,8 +776,36 @@ fn get_default_shortcut() -> &'static str {
     }
 }
 
+/// Ids of the bindings that have an action of their own.
+pub const BUILT_IN_BINDINGS: [&str; 3] = ["transcribe", "hands_free", "test"];
+
+/// Bindings created for transcription profiles have ids starting with this
+/// and all run the transcribe action.
+pub const PROFILE_BINDING_PREFIX: &str = "transcribe_";
+
+/// Whether a binding id belongs to a built-in action or a profile.
+pub fn is_known_binding(id: &str) -> bool {
+    BUILT_IN_BINDINGS.contains(&id)
+        || id
+            .strip_prefix(PROFILE_BINDING_PREFIX)
+            .is_some_and(|suffix| !suffix.is_empty())
+}
+
+/// Default shortcut that switches hands-free dictation on and off.
+fn get_default_hands_free_shortcut() -> &'static str {
+    #[cfg(target_os = "macos")]
+    {
+        "option+shift+space"
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        "ctrl+alt+space"
+    }
+}
+
 pub fn get_default_settings() -> AppSettings {
     let default_shortcut = get_default_shortcut();
+    let hands_free_shortcut = get_default_hands_free_shortcut();
 
     let mut bindings = HashMap::new();
     bindings.insert(
@@ -454,6 +816,19 @@ pub fn get_default_settings() -> AppSettings {
             description: "Converts your speech into text.".to_string(),
             default_binding: default_shortcut.to_string(),
             current_binding: default_shortcut.to_string(),
+            profile_id: None,
+        },
+    );
+    bindings.insert(
+        "hands_free".to_string(),
+        ShortcutBinding {
+            id: "hands_free".to_string(),
+            name: "Hands-free Dictation".to_string(),
+            description: "Listens continuously and transcribes each utterance when you pause."
+                .to_string(),
+            default_binding: hands_free_shortcut.to_string(),
+            current_binding: hands_free_shortcut.to_string(),
+            profile_id: None,
         },
     );
 
@@ -467,8 +842,24 @@ pub fn get_default_settings() -> AppSettings {
         autostart_enabled: default_autostart_enabled(),
         selected_model: "".to_string(),
         always_on_microphone: false,
+        hands_free_silence_ms: default_hands_free_silence_ms(),
+        hands_free_max_utterance_ms: default_hands_free_max_utterance_ms(),
+        hands_free_min_speech_ms: default_hands_free_min_speech_ms(),
+        pre_roll_ms: default_pre_roll_ms(),
+        vad_engine: VadEngine::default(),
+        vad_threshold: default_vad_threshold(),
+        vad_prefill_frames: default_vad_prefill_frames(),
+        vad_hangover_frames: default_vad_hangover_frames(),
+        vad_onset_frames: default_vad_onset_frames(),
+        high_pass_filter_enabled: false,
+        noise_suppression_enabled: false,
+        auto_gain_enabled: false,
+        wake_word_enabled: false,
+        wake_words: default_wake_words(),
+        wake_word_sensitivity: default_wake_word_sensitivity(),
         selected_microphone: None,
         clamshell_microphone: None,
+        microphone_priority: Vec::new(),
         selected_output_device: None,
         translate_to_english: false,
         selected_language: "auto".to_string(),
@@ -479,6 +870,20 @@ pub fn get_default_settings() -> AppSettings {
         custom_words: Vec::new(),
         model_unload_timeout: ModelUnloadTimeout::Never,
         word_correction_threshold: default_word_correction_threshold(),
+        custom_words_fuzzy_correction: default_custom_words_fuzzy_correction(),
+        remote_transcription_enabled: false,
+        remote_transcription_provider_id: default_remote_transcription_provider_id(),
+        remote_transcription_model: default_remote_transcription_model(),
+        transcription_profiles: Vec::new(),
+        whisper_decoding_params: HashMap::new(),
+        hallucination_filter_enabled: default_hallucination_filter_enabled(),
+        hallucination_phrases: default_hallucination_phrases(),
+        hallucination_min_speech_ms: default_hallucination_min_speech_ms(),
+        model_catalog_source: None,
+        model_mirrors: Vec::new(),
+        models_directory: None,
+        max_concurrent_downloads: default_max_concurrent_downloads(),
+        download_bandwidth_limit: None,
         history_limit: default_history_limit(),
         recording_retention_period: default_recording_retention_period(),
         paste_method: PasteMethod::default(),
@@ -518,6 +923,35 @@ impl AppSettings {
             .find(|provider| provider.id == provider_id)
     }
 
+    pub fn transcription_profile(&self, profile_id: &str) -> Option<&TranscriptionProfile> {
+        self.transcription_profiles
+            .iter()
+            .find(|profile| profile.id == profile_id)
+    }
+
+    /// Profile referenced by a binding, if it has one and it still exists.
+    pub fn profile_for_binding(&self, binding_id: &str) -> Option<&TranscriptionProfile> {
+        let profile_id = self.bindings.get(binding_id)?.profile_id.as_deref()?;
+        self.transcription_profile(profile_id)
+    }
+
+    /// Settings as seen by a binding, with its profile applied.
+    pub fn for_binding(&self, binding_id: &str) -> AppSettings {
+        let mut settings = self.clone();
+        if let Some(profile) = self.profile_for_binding(binding_id) {
+            profile.apply(&mut settings);
+        }
+        settings
+    }
+
+    /// Decoding parameters for a whisper model, or the defaults if it has none.
+    pub fn decoding_params_for(&self, model_id: &str) -> WhisperDecodingParams {
+        self.whisper_decoding_params
+            .get(model_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     pub fn post_process_provider_mut(
         &mut self,
         provider_id: &str,
@@ -562,10 +996,9 @@ fn apply_settings_migrations_from_raw(
 
     // Migration: Remove invalid bindings that don't have corresponding actions
     // This cleans up stale bindings like 'cancel' from older versions
-    let valid_binding_ids = ["transcribe", "test"];
     let original_count = settings.bindings.len();
     settings.bindings.retain(|id, _| {
-        let is_valid = valid_binding_ids.contains(&id.as_str());
+        let is_valid = is_known_binding(id);
         if !is_valid {
             warn!(
                 "Removing stale binding '{}' from settings (no corresponding action)",
@@ -578,6 +1011,17 @@ fn apply_settings_migrations_from_raw(
         updated = true;
     }
 
+    // Migration: Add default bindings introduced since, such as hands-free
+    // dictation, without a key: a new global hotkey could clash with what the
+    // user already has, so they assign one themselves
+    for (id, mut binding) in get_default_settings().bindings {
+        if !settings.bindings.contains_key(&id) {
+            binding.current_binding.clear();
+            settings.bindings.insert(id, binding);
+            updated = true;
+        }
+    }
+
     // Migration: Auto-select default prompt if none is selected
     if (settings.post_process_selected_prompt_id.is_none()
         || settings
@@ -756,6 +1200,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn binding_profile_overrides_only_set_fields() {
+        let mut settings = get_default_settings();
+        settings.selected_model = "small".to_string();
+        settings.post_process_enabled = true;
+        settings.transcription_profiles.push(TranscriptionProfile {
+            id: "french".to_string(),
+            name: "French".to_string(),
+            model: None,
+            language: Some("fr".to_string()),
+            translate_to_english: None,
+            post_process_enabled: Some(false),
+            post_process_prompt_id: None,
+        });
+        settings.bindings.get_mut("transcribe").unwrap().profile_id = Some("french".to_string());
+
+        let resolved = settings.for_binding("transcribe");
+        assert_eq!(resolved.selected_language, "fr");
+        assert_eq!(resolved.selected_model, "small");
+        assert!(!resolved.post_process_enabled);
+
+        // A binding whose profile was deleted uses the global settings
+        settings.transcription_profiles.clear();
+        assert_eq!(settings.for_binding("transcribe").selected_language, "auto");
+    }
+
+    #[test]
+    fn migration_keeps_profile_bindings() {
+        let mut settings = get_default_settings();
+        let profile_binding = format!("{}french", PROFILE_BINDING_PREFIX);
+        for id in [profile_binding.as_str(), "cancel"] {
+            let mut binding = settings.bindings["transcribe"].clone();
+            binding.id = id.to_string();
+            settings.bindings.insert(id.to_string(), binding);
+        }
+
+        apply_settings_migrations_from_raw(&mut settings, None);
+        assert!(settings.bindings.contains_key(&profile_binding));
+        assert!(!settings.bindings.contains_key("cancel"));
+    }
+
+    #[test]
+    fn migration_adds_missing_default_bindings() {
+        let mut settings = get_default_settings();
+        settings.bindings.remove("hands_free");
+
+        assert!(apply_settings_migrations_from_raw(&mut settings, None));
+        let hands_free = &settings.bindings["hands_free"];
+        assert!(hands_free.current_binding.is_empty());
+        assert_eq!(
+            hands_free.default_binding,
+            get_default_hands_free_shortcut()
+        );
+    }
+
+    #[test]
+    fn whisper_decoding_params_are_validated() {
+        assert!(WhisperDecodingParams::default().validate().is_ok());
+
+        let invalid = [
+            WhisperDecodingParams {
+                beam_size: 0,
+                ..Default::default()
+            },
+            WhisperDecodingParams {
+                best_of: 17,
+                ..Default::default()
+            },
+            WhisperDecodingParams {
+                temperature: 1.5,
+                ..Default::default()
+            },
+            WhisperDecodingParams {
+                no_speech_threshold: f32::NAN,
+                ..Default::default()
+            },
+            WhisperDecodingParams {
+                threads: u32::MAX,
+                ..Default::default()
+            },
+            WhisperDecodingParams {
+                initial_prompt: Some("x".repeat(MAX_WHISPER_INITIAL_PROMPT_CHARS + 1)),
+                ..Default::default()
+            },
+        ];
+        for params in invalid {
+            assert!(
+                params.validate().is_err(),
+                "{:?} should be rejected",
+                params
+            );
+        }
+    }
+
+    #[test]
+    fn decoding_params_fall_back_to_defaults() {
+        let mut settings = get_default_settings();
+        let tuned = WhisperDecodingParams {
+            beam_size: 8,
+            ..Default::default()
+        };
+        settings
+            .whisper_decoding_params
+            .insert("large".to_string(), tuned.clone());
+
+        assert_eq!(settings.decoding_params_for("large"), tuned);
+        assert_eq!(
+            settings.decoding_params_for("small"),
+            WhisperDecodingParams::default()
+        );
+    }
+
     /// Simulates the race scenario: two sequential updates to different fields
     /// should both be preserved because each goes through the lock.
     /// (Without Tauri runtime we can't call update_settings, so we test the
@@ -1,5 +1,6 @@
 use crate::actions::{OPERATION_GENERATION, TRANSCRIPTION_TASK};
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::transcription::{JobKind, TranscriptionManager};
 use crate::ManagedToggleState;
 use log::{info, warn};
 use std::sync::atomic::Ordering;
@@ -27,6 +28,10 @@ pub fn cancel_current_operation(app: &AppHandle) {
         }
     }
 
+    // Aborting the task doesn't stop the engine; drop the dictation's jobs too
+    app.state::<Arc<TranscriptionManager>>()
+        .cancel_jobs(&[JobKind::Dictation, JobKind::Preview]);
+
     // Cancel any ongoing recording FIRST (before resetting toggle states)
     // This ensures the audio stream is stopped before any cleanup
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
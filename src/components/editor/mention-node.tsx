@@ -20,13 +20,18 @@ import {
 } from "@/components/ui/inline-combobox";
 import { cn } from "@/lib/utils";
 
-// Available mentions for the @output placeholder
+// Available mentions for the @output and @language placeholders
 const MENTION_ITEMS = [
   {
     key: "output",
     text: "output",
     description: "The transcribed text placeholder",
   },
+  {
+    key: "language",
+    text: "language",
+    description: "The spoken language code, e.g. en or fr",
+  },
 ] as const;
 
 export function MentionElement(
@@ -2,7 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { Upload, X } from "lucide-react";
 import type React from "react";
-import { useCallback, useEffect, useState } from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
 import { Button } from "@/components/ui/button";
 import { cn } from "@/lib/utils";
 
@@ -28,6 +28,9 @@ export const FileUploadZone: React.FC<FileUploadZoneProps> = ({
     null
   );
   const [error, setError] = useState<string | null>(null);
+  // Path of the copy being transcribed, needed to cancel it
+  const [activePath, setActivePath] = useState<string | null>(null);
+  const cancelledRef = useRef(false);
 
   useEffect(() => {
     const setupListener = async () => {
@@ -163,6 +166,9 @@ export const FileUploadZone: React.FC<FileUploadZoneProps> = ({
         });
         await fileHandle.write(uint8Array);
 
+        cancelledRef.current = false;
+        setActivePath(tempPath);
+
         // Call the transcription command
         const transcriptionText = await invoke<string>(
           "transcribe_audio_file",
@@ -184,13 +190,17 @@ export const FileUploadZone: React.FC<FileUploadZoneProps> = ({
           message: "Transcription complete! Copied to clipboard.",
         });
       } catch (err) {
-        console.error("Transcription failed:", err);
-        setError(
-          err instanceof Error ? err.message : "Failed to transcribe file"
-        );
+        if (!cancelledRef.current) {
+          console.error("Transcription failed:", err);
+          setError(
+            err instanceof Error ? err.message : "Failed to transcribe file"
+          );
+        }
         setIsProcessing(false);
         setCurrentFile(null);
         setProgress(null);
+      } finally {
+        setActivePath(null);
       }
     },
     [onTranscriptionComplete]
@@ -232,6 +242,20 @@ export const FileUploadZone: React.FC<FileUploadZoneProps> = ({
     [processFile]
   );
 
+  const cancelTranscription = async () => {
+    if (!activePath) {
+      return;
+    }
+    cancelledRef.current = true;
+    try {
+      await invoke<boolean>("cancel_file_transcription", {
+        filePath: activePath,
+      });
+    } catch (err) {
+      console.error("Failed to cancel transcription:", err);
+    }
+  };
+
   const cancelUpload = () => {
     setCurrentFile(null);
     setIsProcessing(false);
@@ -294,9 +318,21 @@ export const FileUploadZone: React.FC<FileUploadZoneProps> = ({
             <p className="font-medium text-sm">
               {currentFile?.name || "Processing..."}
             </p>
-            <p className="text-muted-foreground text-xs">
-              {Math.round(progress.progress * 100)}%
-            </p>
+            <div className="flex items-center gap-2">
+              <p className="text-muted-foreground text-xs">
+                {Math.round(progress.progress * 100)}%
+              </p>
+              {activePath && (
+                <Button
+                  onClick={cancelTranscription}
+                  size="icon-xs"
+                  title="Cancel transcription"
+                  variant="ghost"
+                >
+                  <X className="h-4 w-4" />
+                </Button>
+              )}
+            </div>
           </div>
 
           <div className="mb-2 h-2 overflow-hidden rounded-full bg-border">
@@ -24,6 +24,13 @@ export const AppSettings = () => {
 
       <CollapsibleSettingsGroup defaultOpen={true} title="Recording">
         <EchoShortcut descriptionMode="tooltip" grouped={true} />
+        <EchoShortcut
+          description="Set the keyboard shortcut that turns hands-free dictation on and off"
+          descriptionMode="tooltip"
+          grouped={true}
+          shortcutId="hands_free"
+          title="Hands-free Shortcut"
+        />
         <PushToTalk descriptionMode="tooltip" grouped={true} />
         <MicrophoneSelector descriptionMode="tooltip" grouped={true} />
       </CollapsibleSettingsGroup>
@@ -32,13 +32,19 @@ interface WaylandShortcutInfo {
 }
 
 interface EchoShortcutProps {
+  description?: string;
   descriptionMode?: "inline" | "tooltip";
   grouped?: boolean;
+  shortcutId?: string;
+  title?: string;
 }
 
 export const EchoShortcut: React.FC<EchoShortcutProps> = ({
+  description = "Set the keyboard shortcut to start and stop speech-to-text recording",
   descriptionMode = "tooltip",
   grouped = false,
+  shortcutId = "transcribe",
+  title = "Echo Shortcut",
 }) => {
   const bindings = useSetting("bindings") ?? {};
   const isLoading = useSettingsStore((s) => s.isLoading);
@@ -367,16 +373,16 @@ export const EchoShortcut: React.FC<EchoShortcutProps> = ({
 
   return (
     <SettingContainer
-      description="Set the keyboard shortcut to start and stop speech-to-text recording"
+      description={description}
       descriptionMode={descriptionMode}
       grouped={grouped}
       icon={<Keyboard className="h-4 w-4" />}
-      title="Echo Shortcut"
+      title={title}
       tooltipPosition="bottom"
     >
       {(() => {
-        const primaryBinding = Object.values(bindings)[0];
-        const primaryId = Object.keys(bindings)[0];
+        const primaryId = shortcutId in bindings ? shortcutId : undefined;
+        const primaryBinding = primaryId ? bindings[primaryId] : undefined;
 
         if (!(primaryBinding && primaryId)) {
           return (
@@ -401,7 +407,9 @@ export const EchoShortcut: React.FC<EchoShortcutProps> = ({
                 size="sm"
                 variant="secondary"
               >
-                {formatKeyCombination(primaryBinding.current_binding, osType)}
+                {primaryBinding.current_binding
+                  ? formatKeyCombination(primaryBinding.current_binding, osType)
+                  : "Not set"}
               </Button>
             )}
             <Button
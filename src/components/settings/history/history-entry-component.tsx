@@ -14,6 +14,8 @@ import {
 export interface HistoryEntry {
   file_name: string;
   id: number;
+  language?: string | null;
+  language_probability?: number | null;
   saved: boolean;
   timestamp: number;
   title: string;
@@ -108,7 +110,21 @@ export const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
   return (
     <div className="flex flex-col gap-3 px-4 py-4">
       <div className="flex items-center justify-between">
-        <p className="font-medium text-sm">{entry.title}</p>
+        <p className="font-medium text-sm">
+          {entry.title}
+          {entry.language && (
+            <span
+              className="ml-2 text-text/50 text-xs uppercase"
+              title={
+                entry.language_probability == null
+                  ? undefined
+                  : `${Math.round(entry.language_probability * 100)}% confidence`
+              }
+            >
+              {entry.language}
+            </span>
+          )}
+        </p>
         <TooltipProvider>
           <ButtonGroup>
             <Tooltip>
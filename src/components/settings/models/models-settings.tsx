@@ -1,10 +1,10 @@
-import { Check, Download, Loader2, Trash2 } from "lucide-react";
+import { Check, Download, Gauge, Loader2, Trash2 } from "lucide-react";
 import type React from "react";
-import { useEffect } from "react";
+import { useEffect, useState } from "react";
 import ProgressBar from "@/components/shared/progress-bar";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
-import type { ModelInfo } from "@/lib/types";
+import type { ModelBenchmark, ModelInfo } from "@/lib/types";
 import { cn } from "@/lib/utils";
 import { formatModelSize } from "@/lib/utils/format";
 import { type ModelStatus, useModelStore } from "@/stores/model-store";
@@ -68,7 +68,15 @@ const noop = () => {
   // intentionally empty - used for downloadable models that can't be deleted
 };
 
+const formatBenchmark = (benchmark: ModelBenchmark): string => {
+  const wer = Math.round(benchmark.word_error_rate * 100);
+  const rtf = benchmark.real_time_factor.toFixed(2);
+  const load = (benchmark.load_ms / 1000).toFixed(1);
+  return `Measured: ${wer}% WER · ${rtf}× real time · loads in ${load}s`;
+};
+
 interface ModelCardProps {
+  benchmark?: ModelBenchmark;
   downloadProgress?: { percentage: number };
   downloadSpeed?: number;
   isActive: boolean;
@@ -80,6 +88,7 @@ interface ModelCardProps {
 }
 
 const ModelCard: React.FC<ModelCardProps> = ({
+  benchmark,
   model,
   isActive,
   isDownloading,
@@ -125,31 +134,44 @@ const ModelCard: React.FC<ModelCardProps> = ({
             {model.id === "small" && !model.is_downloaded && (
               <Badge variant="secondary">Recommended</Badge>
             )}
+            {model.is_custom ? <Badge variant="secondary">Custom</Badge> : null}
           </div>
           <p className="mt-1 text-muted-foreground text-sm">
             {model.description}
           </p>
           <div className="mt-2 flex items-center gap-4 text-muted-foreground text-xs">
             <span>{formatModelSize(model.size_mb)}</span>
-            <div className="flex items-center gap-1">
-              <span>Accuracy:</span>
-              <div className="h-1.5 w-12 overflow-hidden rounded-full bg-muted/30">
-                <div
-                  className="h-full rounded-full bg-foreground/60"
-                  style={{ width: `${model.accuracy_score * 100}%` }}
-                />
-              </div>
-            </div>
-            <div className="flex items-center gap-1">
-              <span>Speed:</span>
-              <div className="h-1.5 w-12 overflow-hidden rounded-full bg-muted/30">
-                <div
-                  className="h-full rounded-full bg-foreground/60"
-                  style={{ width: `${model.speed_score * 100}%` }}
-                />
-              </div>
-            </div>
+            {model.is_custom ? null : (
+              <>
+                <div className="flex items-center gap-1">
+                  <span>Accuracy:</span>
+                  <div className="h-1.5 w-12 overflow-hidden rounded-full bg-muted/30">
+                    <div
+                      className="h-full rounded-full bg-foreground/60"
+                      style={{ width: `${model.accuracy_score * 100}%` }}
+                    />
+                  </div>
+                </div>
+                <div className="flex items-center gap-1">
+                  <span>Speed:</span>
+                  <div className="h-1.5 w-12 overflow-hidden rounded-full bg-muted/30">
+                    <div
+                      className="h-full rounded-full bg-foreground/60"
+                      style={{ width: `${model.speed_score * 100}%` }}
+                    />
+                  </div>
+                </div>
+              </>
+            )}
           </div>
+          {benchmark ? (
+            <p
+              className="mt-1 text-muted-foreground text-xs"
+              title={`Benchmarked on ${benchmark.reference_name}`}
+            >
+              {formatBenchmark(benchmark)}
+            </p>
+          ) : null}
         </div>
 
         <div className="flex items-center gap-2">
@@ -201,12 +223,15 @@ const WhisperModelsContent = () => {
   const downloadProgress = useModelStore((s) => s.downloadProgress);
   const downloadStats = useModelStore((s) => s.downloadStats);
   const extractingModels = useModelStore((s) => s.extractingModels);
+  const benchmarks = useModelStore((s) => s.benchmarks);
 
   const initialize = useModelStore((s) => s.initialize);
   const setupListeners = useModelStore((s) => s.setupListeners);
   const selectModel = useModelStore((s) => s.selectModel);
   const downloadModel = useModelStore((s) => s.downloadModel);
   const deleteModel = useModelStore((s) => s.deleteModel);
+  const runBenchmark = useModelStore((s) => s.runBenchmark);
+  const [isBenchmarking, setIsBenchmarking] = useState(false);
 
   const availableModels = models.filter((m) => m.is_downloaded);
   const downloadableModels = models.filter((m) => !m.is_downloaded);
@@ -254,6 +279,17 @@ const WhisperModelsContent = () => {
     }
   };
 
+  const handleBenchmark = async () => {
+    setIsBenchmarking(true);
+    try {
+      await runBenchmark();
+    } catch (err) {
+      console.error("Failed to benchmark models:", err);
+    } finally {
+      setIsBenchmarking(false);
+    }
+  };
+
   return (
     <div className="space-y-16">
       {/* Status Header */}
@@ -288,12 +324,29 @@ const WhisperModelsContent = () => {
       {/* Downloaded Models */}
       {availableModels.length > 0 && (
         <div className="space-y-3">
-          <h2 className="px-1 font-medium text-muted-foreground text-xs uppercase tracking-wide">
-            Downloaded Models
-          </h2>
+          <div className="flex items-center justify-between px-1">
+            <h2 className="font-medium text-muted-foreground text-xs uppercase tracking-wide">
+              Downloaded Models
+            </h2>
+            <Button
+              disabled={isBenchmarking}
+              onClick={handleBenchmark}
+              size="sm"
+              title="Measure the speed and accuracy of every downloaded model on this machine"
+              variant="outline"
+            >
+              {isBenchmarking ? (
+                <Loader2 className="mr-1 h-4 w-4 animate-spin" />
+              ) : (
+                <Gauge className="mr-1 h-4 w-4" />
+              )}
+              {isBenchmarking ? "Benchmarking..." : "Benchmark"}
+            </Button>
+          </div>
           <div className="space-y-2">
             {availableModels.map((model) => (
               <ModelCard
+                benchmark={benchmarks[model.id]}
                 downloadProgress={undefined}
                 downloadSpeed={undefined}
                 isActive={model.id === currentModelId}
@@ -6,6 +6,7 @@ export const ShortcutBindingSchema = z.object({
   description: z.string(),
   default_binding: z.string(),
   current_binding: z.string(),
+  profile_id: z.string().nullable().optional(),
 });
 
 export const ShortcutBindingsMapSchema = z.record(
@@ -59,6 +60,9 @@ export type RecordingRetentionPeriod = z.infer<
   typeof RecordingRetentionPeriodSchema
 >;
 
+export const VadEngineSchema = z.enum(["silero", "energy", "gmm", "none"]);
+export type VadEngine = z.infer<typeof VadEngineSchema>;
+
 export const LLMPromptSchema = z.object({
   id: z.string(),
   name: z.string(),
@@ -67,6 +71,38 @@ export const LLMPromptSchema = z.object({
 
 export type LLMPrompt = z.infer<typeof LLMPromptSchema>;
 
+export const WakeWordSchema = z.object({
+  phrase: z.string(),
+  tokens: z.string().nullable().optional(),
+  binding_id: z.string(),
+});
+
+export type WakeWord = z.infer<typeof WakeWordSchema>;
+
+export const TranscriptionProfileSchema = z.object({
+  id: z.string(),
+  name: z.string(),
+  model: z.string().nullable().optional(),
+  language: z.string().nullable().optional(),
+  translate_to_english: z.boolean().nullable().optional(),
+  post_process_enabled: z.boolean().nullable().optional(),
+  post_process_prompt_id: z.string().nullable().optional(),
+});
+
+export type TranscriptionProfile = z.infer<typeof TranscriptionProfileSchema>;
+
+export const WhisperDecodingParamsSchema = z.object({
+  beam_size: z.number().int().min(1).max(16).optional().default(1),
+  best_of: z.number().int().min(1).max(16).optional().default(1),
+  temperature: z.number().min(0).max(1).optional().default(0),
+  temperature_increment: z.number().min(0).max(1).optional().default(0.2),
+  no_speech_threshold: z.number().min(0).max(1).optional().default(0.6),
+  threads: z.number().int().min(0).optional().default(0),
+  initial_prompt: z.string().nullable().optional(),
+});
+
+export type WhisperDecodingParams = z.infer<typeof WhisperDecodingParamsSchema>;
+
 export const PostProcessProviderSchema = z.object({
   id: z.string(),
   label: z.string(),
@@ -94,8 +130,29 @@ export const SettingsSchema = z.object({
   autostart_enabled: z.boolean().optional().default(false),
   selected_model: z.string(),
   always_on_microphone: z.boolean(),
+  pre_roll_ms: z.number().int().min(0).max(2000).optional().default(300),
+  hands_free_silence_ms: z.number().int().min(100).optional().default(800),
+  hands_free_max_utterance_ms: z
+    .number()
+    .int()
+    .min(1000)
+    .optional()
+    .default(30000),
+  hands_free_min_speech_ms: z.number().int().min(0).optional().default(300),
+  vad_engine: VadEngineSchema.optional().default("silero"),
+  vad_threshold: z.number().min(0).max(1).optional().default(0.3),
+  vad_prefill_frames: z.number().int().min(0).max(100).optional().default(15),
+  vad_hangover_frames: z.number().int().min(0).max(100).optional().default(15),
+  vad_onset_frames: z.number().int().min(1).max(10).optional().default(2),
+  high_pass_filter_enabled: z.boolean().optional().default(false),
+  noise_suppression_enabled: z.boolean().optional().default(false),
+  auto_gain_enabled: z.boolean().optional().default(false),
+  wake_word_enabled: z.boolean().optional().default(false),
+  wake_words: z.array(WakeWordSchema).optional().default([]),
+  wake_word_sensitivity: z.number().min(0).max(1).optional().default(0.5),
   selected_microphone: z.string().nullable().optional(),
   clamshell_microphone: z.string().nullable().optional(),
+  microphone_priority: z.array(z.string()).optional().default([]),
   selected_output_device: z.string().nullable().optional(),
   translate_to_english: z.boolean(),
   selected_language: z.string(),
@@ -106,6 +163,26 @@ export const SettingsSchema = z.object({
   custom_words: z.array(z.string()).optional().default([]),
   model_unload_timeout: ModelUnloadTimeoutSchema.optional().default("never"),
   word_correction_threshold: z.number().optional().default(0.18),
+  custom_words_fuzzy_correction: z.boolean().optional().default(true),
+  remote_transcription_enabled: z.boolean().optional().default(false),
+  remote_transcription_provider_id: z.string().optional().default("openai"),
+  remote_transcription_model: z.string().optional().default("whisper-1"),
+  transcription_profiles: z
+    .array(TranscriptionProfileSchema)
+    .optional()
+    .default([]),
+  whisper_decoding_params: z
+    .record(z.string(), WhisperDecodingParamsSchema)
+    .optional()
+    .default({}),
+  hallucination_filter_enabled: z.boolean().optional().default(true),
+  hallucination_phrases: z.array(z.string()).optional().default([]),
+  hallucination_min_speech_ms: z.number().int().min(0).optional().default(250),
+  model_catalog_source: z.string().nullable().optional(),
+  model_mirrors: z.array(z.string()).optional().default([]),
+  models_directory: z.string().nullable().optional(),
+  max_concurrent_downloads: z.number().int().min(1).optional().default(2),
+  download_bandwidth_limit: z.number().int().min(1).nullable().optional(),
   history_limit: z.number().optional().default(5),
   recording_retention_period:
     RecordingRetentionPeriodSchema.optional().default("preserve_limit"),
@@ -155,18 +232,65 @@ export const ModelInfoSchema = z.object({
   name: z.string(),
   description: z.string(),
   filename: z.string(),
-  url: z.string().optional(),
+  urls: z.array(z.string()).optional().default([]),
+  sha256: z.string().nullable().optional(),
   size_mb: z.number(),
   is_downloaded: z.boolean(),
   is_downloading: z.boolean(),
   partial_size: z.number(),
   is_directory: z.boolean(),
+  is_custom: z.boolean().optional(),
+  backend: z.string().nullable().optional(),
   accuracy_score: z.number(),
   speed_score: z.number(),
 });
 
 export type ModelInfo = z.infer<typeof ModelInfoSchema>;
 
+export const ModelVerificationSchema = z.object({
+  model_id: z.string(),
+  status: z.enum(["ok", "corrupt", "unverified"]),
+  error: z.string().nullable().optional(),
+});
+
+export type ModelVerification = z.infer<typeof ModelVerificationSchema>;
+
+export const StorageReportSchema = z.object({
+  models_dir: z.string(),
+  total_bytes: z.number(),
+  models: z.array(
+    z.object({
+      model_id: z.string(),
+      name: z.string(),
+      bytes: z.number(),
+      partial_bytes: z.number(),
+    })
+  ),
+  orphans: z.array(
+    z.object({
+      path: z.string(),
+      bytes: z.number(),
+      kind: z.enum(["partial", "extracting", "importing"]),
+    })
+  ),
+});
+
+export type StorageReport = z.infer<typeof StorageReportSchema>;
+
+export const ModelBenchmarkSchema = z.object({
+  model_id: z.string(),
+  timestamp: z.number(),
+  reference_name: z.string(),
+  audio_ms: z.number(),
+  load_ms: z.number(),
+  transcribe_ms: z.number(),
+  real_time_factor: z.number(),
+  word_error_rate: z.number(),
+  transcript: z.string(),
+});
+
+export type ModelBenchmark = z.infer<typeof ModelBenchmarkSchema>;
+
 export const FileTranscriptionProgressSchema = z.object({
   status: z.string(),
   progress: z.number(),
@@ -177,6 +301,21 @@ export type FileTranscriptionProgress = z.infer<
   typeof FileTranscriptionProgressSchema
 >;
 
+export const TranscriptionJobSchema = z.object({
+  id: z.number(),
+  kind: z.enum([
+    "dictation",
+    "preview",
+    "retranscription",
+    "meeting",
+    "file",
+    "benchmark",
+  ]),
+  state: z.enum(["queued", "running"]),
+  audio_ms: z.number(),
+});
+export type TranscriptionJob = z.infer<typeof TranscriptionJobSchema>;
+
 // ── Meeting types ──────────────────────────────────────────────────────
 
 export const MeetingStatusSchema = z.enum([
@@ -190,6 +329,23 @@ export type MeetingStatus = z.infer<typeof MeetingStatusSchema>;
 export const ExportFormatSchema = z.enum(["srt", "vtt", "txt", "markdown"]);
 export type ExportFormat = z.infer<typeof ExportFormatSchema>;
 
+export const TranscriptWordSchema = z.object({
+  text: z.string(),
+  start_ms: z.number(),
+  end_ms: z.number(),
+  probability: z.number().nullable().optional(),
+});
+export type TranscriptWord = z.infer<typeof TranscriptWordSchema>;
+
+export const TranscriptSegmentSchema = z.object({
+  text: z.string(),
+  start_ms: z.number(),
+  end_ms: z.number(),
+  probability: z.number().nullable().optional(),
+  words: z.array(TranscriptWordSchema).optional().default([]),
+});
+export type TranscriptSegment = z.infer<typeof TranscriptSegmentSchema>;
+
 export const MeetingSegmentSchema = z.object({
   id: z.number(),
   meeting_id: z.number(),
@@ -199,6 +355,7 @@ export const MeetingSegmentSchema = z.object({
   text: z.string(),
   confidence: z.number().nullable().optional(),
   audio_source: z.string(),
+  words: z.array(TranscriptWordSchema).optional().default([]),
 });
 export type MeetingSegment = z.infer<typeof MeetingSegmentSchema>;
 
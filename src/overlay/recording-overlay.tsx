@@ -12,6 +12,14 @@ interface WarningPayload {
   state: "warning" | "tool";
 }
 
+/** Streaming preview: committed text is final, tentative may still change */
+interface TranscriptionProgress {
+  committed: string;
+  tentative: string;
+}
+
+const EMPTY_PROGRESS: TranscriptionProgress = { committed: "", tentative: "" };
+
 const NOTCH_HEIGHT = 42;
 const NOTCH_WIDTH = 310;
 const EXPANDED_HEIGHT = 76;
@@ -60,13 +68,16 @@ const RecordingOverlay = () => {
   const [state, setState] = useState<OverlayState>("recording");
   const [position, setPosition] = useState<"top" | "bottom">("top");
   const [warningMessage, setWarningMessage] = useState("");
-  const [streamingText, setStreamingText] = useState("");
+  const [streamingText, setStreamingText] =
+    useState<TranscriptionProgress>(EMPTY_PROGRESS);
   const textScrollRef = useRef<HTMLDivElement>(null);
   const barsRef = useRef<HTMLDivElement>(null);
   const hasBeenShown = useRef(false);
 
   const hasText =
-    Boolean(streamingText) || state === "warning" || state === "tool";
+    Boolean(streamingText.committed || streamingText.tentative) ||
+    state === "warning" ||
+    state === "tool";
 
   // Store unlisten fns in a ref so the synchronous cleanup can call them
   const unlistenRef = useRef<UnlistenFn[]>([]);
@@ -83,7 +94,7 @@ const RecordingOverlay = () => {
             setState(newState);
             setWarningMessage("");
             if (newState === "recording") {
-              setStreamingText("");
+              setStreamingText(EMPTY_PROGRESS);
             }
           } else if (
             typeof event.payload === "object" &&
@@ -111,7 +122,7 @@ const RecordingOverlay = () => {
         }
       });
 
-      const unlistenProgress = await listen<string>(
+      const unlistenProgress = await listen<TranscriptionProgress>(
         "transcription-progress",
         (event) => {
           setStreamingText(event.payload);
@@ -213,7 +224,10 @@ const RecordingOverlay = () => {
   }, [isVisible]);
 
   useEffect(() => {
-    if (textScrollRef.current && streamingText) {
+    if (
+      textScrollRef.current &&
+      (streamingText.committed || streamingText.tentative)
+    ) {
       textScrollRef.current.scrollTo({
         left: textScrollRef.current.scrollWidth,
         behavior: "smooth",
@@ -298,9 +312,19 @@ const RecordingOverlay = () => {
               "-webkit-linear-gradient(left, transparent, black 12px, black calc(100% - 12px), transparent)",
           }}
         >
-          {state === "warning" || state === "tool"
-            ? warningMessage
-            : streamingText}
+          {state === "warning" || state === "tool" ? (
+            warningMessage
+          ) : (
+            <>
+              {streamingText.committed}
+              {streamingText.tentative && (
+                <span className="text-white/45">
+                  {streamingText.committed ? " " : ""}
+                  {streamingText.tentative}
+                </span>
+              )}
+            </>
+          )}
         </div>
 
         {/* Progress sweep line during transcription */}
@@ -1,7 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { create } from "zustand";
-import type { ModelInfo } from "@/lib/types";
+import type {
+  ModelBenchmark,
+  ModelInfo,
+  ModelVerification,
+  StorageReport,
+} from "@/lib/types";
 
 export type ModelStatus =
   | "ready"
@@ -21,8 +26,19 @@ interface ModelStateEvent {
 
 interface DownloadProgress {
   downloaded: number;
+  eta_seconds?: number | null;
   model_id: string;
   percentage: number;
+  /** Bytes per second, smoothed by the backend. */
+  speed?: number;
+  total: number;
+}
+
+interface BenchmarkProgress {
+  error?: string;
+  index: number;
+  model_id: string;
+  status: "running" | "completed" | "failed";
   total: number;
 }
 
@@ -59,6 +75,8 @@ const getStatusText = (
 };
 
 interface ModelStore {
+  benchmarks: Record<string, ModelBenchmark>;
+  cleanupOrphans: () => Promise<number>;
   currentModelId: string;
   deleteModel: (modelId: string) => Promise<void>;
   downloadModel: (modelId: string) => Promise<void>;
@@ -66,19 +84,37 @@ interface ModelStore {
   downloadStats: Map<string, DownloadStats>;
   extractingModels: Set<string>;
   getModelDisplayText: () => string;
+  importModel: (
+    sourcePath: string,
+    name: string,
+    engineType?: "Whisper" | "Parakeet",
+    link?: boolean
+  ) => Promise<ModelInfo>;
   initialize: () => Promise<void>;
+  loadBenchmarks: () => Promise<void>;
   loadCurrentModel: () => Promise<void>;
 
   loadModels: () => Promise<void>;
+  loadStorageReport: () => Promise<void>;
   modelError: string | null;
   modelStatus: ModelStatus;
   models: ModelInfo[];
+  relocateModels: (path: string) => Promise<void>;
+  runBenchmark: (
+    audioPath?: string,
+    referenceText?: string,
+    language?: string
+  ) => Promise<void>;
   selectModel: (modelId: string) => Promise<void>;
   setupListeners: () => Promise<() => void>;
+  storageReport: StorageReport | null;
+  verifyModels: () => Promise<ModelVerification[]>;
 }
 
 export const useModelStore = create<ModelStore>((set, get) => ({
   models: [],
+  benchmarks: {},
+  storageReport: null,
   currentModelId: "",
   modelStatus: "unloaded",
   modelError: null,
@@ -95,6 +131,59 @@ export const useModelStore = create<ModelStore>((set, get) => ({
     }
   },
 
+  loadStorageReport: async () => {
+    try {
+      const report = await invoke<StorageReport>("get_model_storage_report");
+      set({ storageReport: report });
+    } catch (err) {
+      console.error("Failed to load model storage report:", err);
+    }
+  },
+
+  cleanupOrphans: async () => {
+    const freed = await invoke<number>("cleanup_orphaned_model_files");
+    await get().loadStorageReport();
+    return freed;
+  },
+
+  relocateModels: async (path) => {
+    try {
+      set({ modelError: null });
+      await invoke<string>("relocate_models_dir", { path });
+      await Promise.all([get().loadModels(), get().loadStorageReport()]);
+    } catch (err) {
+      set({ modelError: `${err}` });
+      throw err;
+    }
+  },
+
+  loadBenchmarks: async () => {
+    try {
+      const results = await invoke<ModelBenchmark[]>("get_model_benchmarks");
+      set({
+        benchmarks: Object.fromEntries(results.map((b) => [b.model_id, b])),
+      });
+    } catch (err) {
+      console.error("Failed to load model benchmarks:", err);
+    }
+  },
+
+  runBenchmark: async (audioPath, referenceText, language) => {
+    try {
+      set({ modelError: null });
+      await invoke("run_model_benchmark", {
+        audioPath: audioPath ?? null,
+        referenceText: referenceText ?? null,
+        language: language ?? null,
+        modelIds: null,
+      });
+      await get().loadBenchmarks();
+    } catch (err) {
+      set({ modelError: `${err}` });
+      throw err;
+    }
+  },
+
   loadCurrentModel: async () => {
     try {
       const current = await invoke<string>("get_current_model");
@@ -144,6 +233,23 @@ export const useModelStore = create<ModelStore>((set, get) => ({
     }
   },
 
+  importModel: async (sourcePath, name, engineType, link = false) => {
+    try {
+      set({ modelError: null });
+      const model = await invoke<ModelInfo>("import_model", {
+        sourcePath,
+        name,
+        engineType: engineType ?? null,
+        link,
+      });
+      await get().loadModels();
+      return model;
+    } catch (err) {
+      set({ modelError: `${err}` });
+      throw err;
+    }
+  },
+
   deleteModel: async (modelId) => {
     try {
       await invoke("delete_model", { modelId });
@@ -157,9 +263,27 @@ export const useModelStore = create<ModelStore>((set, get) => ({
     }
   },
 
+  verifyModels: async () => {
+    try {
+      const results = await invoke<ModelVerification[]>(
+        "verify_installed_models"
+      );
+      const corrupt = results.filter((r) => r.status === "corrupt");
+      if (corrupt.length > 0) {
+        set({
+          modelError: `Corrupt model files: ${corrupt.map((r) => r.model_id).join(", ")}`,
+        });
+      }
+      return results;
+    } catch (err) {
+      set({ modelError: `${err}` });
+      throw err;
+    }
+  },
+
   initialize: async () => {
-    const { loadModels, loadCurrentModel } = get();
-    await Promise.all([loadModels(), loadCurrentModel()]);
+    const { loadModels, loadCurrentModel, loadBenchmarks } = get();
+    await Promise.all([loadModels(), loadCurrentModel(), loadBenchmarks()]);
   },
 
   setupListeners: async () => {
@@ -229,10 +353,13 @@ export const useModelStore = create<ModelStore>((set, get) => ({
           const timeDiff = (now - current.lastUpdate) / 1000;
           if (timeDiff > 0.5) {
             const bytesDiff = progress.downloaded - current.totalDownloaded;
-            const currentSpeed = bytesDiff / (1024 * 1024) / timeDiff;
+            const currentSpeed =
+              progress.speed === undefined
+                ? bytesDiff / (1024 * 1024) / timeDiff
+                : progress.speed / (1024 * 1024);
             const validCurrentSpeed = Math.max(0, currentSpeed);
             const smoothedSpeed =
-              current.speed > 0
+              current.speed > 0 && progress.speed === undefined
                 ? current.speed * 0.8 + validCurrentSpeed * 0.2
                 : validCurrentSpeed;
 
@@ -260,6 +387,24 @@ export const useModelStore = create<ModelStore>((set, get) => ({
     );
     listeners.push(downloadProgressUnlisten);
 
+    const downloadQueuedUnlisten = await listen<string>(
+      "model-download-queued",
+      (event) => {
+        const modelId = event.payload;
+        set((state) => {
+          const newProgress = new Map(state.downloadProgress);
+          newProgress.set(modelId, {
+            model_id: modelId,
+            downloaded: 0,
+            total: 0,
+            percentage: 0,
+          });
+          return { downloadProgress: newProgress };
+        });
+      }
+    );
+    listeners.push(downloadQueuedUnlisten);
+
     const downloadCompleteUnlisten = await listen<string>(
       "model-download-complete",
       async (event) => {
@@ -329,6 +474,42 @@ export const useModelStore = create<ModelStore>((set, get) => ({
     });
     listeners.push(extractionFailedUnlisten);
 
+    const verificationFailedUnlisten = await listen<{
+      model_id: string;
+      error: string;
+    }>("model-verification-failed", async (event) => {
+      const modelId = event.payload.model_id;
+      set((state) => {
+        const newProgress = new Map(state.downloadProgress);
+        newProgress.delete(modelId);
+        const newStats = new Map(state.downloadStats);
+        newStats.delete(modelId);
+        return {
+          downloadProgress: newProgress,
+          downloadStats: newStats,
+          modelError: event.payload.error,
+          modelStatus: "error" as const,
+        };
+      });
+      await get().loadModels();
+    });
+    listeners.push(verificationFailedUnlisten);
+
+    const catalogUpdatedUnlisten = await listen("model-catalog-updated", () => {
+      get().loadModels();
+    });
+    listeners.push(catalogUpdatedUnlisten);
+
+    const benchmarkProgressUnlisten = await listen<BenchmarkProgress>(
+      "model-benchmark-progress",
+      (event) => {
+        if (event.payload.status === "completed") {
+          get().loadBenchmarks();
+        }
+      }
+    );
+    listeners.push(benchmarkProgressUnlisten);
+
     return () => {
       for (const unlisten of listeners) {
         unlisten();
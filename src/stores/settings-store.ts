@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type { load as LoadFn } from "@tauri-apps/plugin-store";
 import { create } from "zustand";
 import { useShallow } from "zustand/react/shallow";
@@ -41,6 +42,7 @@ const DEFAULT_SETTINGS: Partial<Settings> = {
   push_to_talk: false,
   selected_microphone: "Default",
   clamshell_microphone: "Default",
+  microphone_priority: [],
   selected_output_device: "Default",
   translate_to_english: false,
   selected_language: "auto",
@@ -75,6 +77,36 @@ const settingUpdaters: {
 } = {
   always_on_microphone: (value) =>
     invoke("update_microphone_mode", { alwaysOn: value }),
+  pre_roll_ms: (value) =>
+    invoke("change_pre_roll_ms_setting", { preRollMs: value }),
+  hands_free_silence_ms: (value) =>
+    invoke("change_hands_free_silence_ms_setting", { silenceMs: value }),
+  hands_free_max_utterance_ms: (value) =>
+    invoke("change_hands_free_max_utterance_ms_setting", {
+      maxUtteranceMs: value,
+    }),
+  hands_free_min_speech_ms: (value) =>
+    invoke("change_hands_free_min_speech_ms_setting", { minSpeechMs: value }),
+  vad_engine: (value) => invoke("change_vad_engine_setting", { engine: value }),
+  vad_threshold: (value) =>
+    invoke("change_vad_threshold_setting", { threshold: value }),
+  vad_prefill_frames: (value) =>
+    invoke("change_vad_prefill_frames_setting", { frames: value }),
+  vad_hangover_frames: (value) =>
+    invoke("change_vad_hangover_frames_setting", { frames: value }),
+  vad_onset_frames: (value) =>
+    invoke("change_vad_onset_frames_setting", { frames: value }),
+  high_pass_filter_enabled: (value) =>
+    invoke("change_high_pass_filter_setting", { enabled: value }),
+  noise_suppression_enabled: (value) =>
+    invoke("change_noise_suppression_setting", { enabled: value }),
+  auto_gain_enabled: (value) =>
+    invoke("change_auto_gain_setting", { enabled: value }),
+  wake_word_enabled: (value) =>
+    invoke("change_wake_word_enabled_setting", { enabled: value }),
+  wake_words: (value) => invoke("update_wake_words", { wakeWords: value }),
+  wake_word_sensitivity: (value) =>
+    invoke("change_wake_word_sensitivity_setting", { sensitivity: value }),
   audio_feedback: (value) =>
     invoke("change_audio_feedback_setting", { enabled: value }),
   audio_feedback_volume: (value) =>
@@ -94,6 +126,8 @@ const settingUpdaters: {
     invoke("set_clamshell_microphone", {
       deviceName: value === "Default" ? "default" : value,
     }),
+  microphone_priority: (value) =>
+    invoke("set_microphone_priority", { patterns: value }),
   selected_output_device: (value) =>
     invoke("set_selected_output_device", {
       deviceName: value === "Default" ? "default" : value,
@@ -112,6 +146,35 @@ const settingUpdaters: {
   custom_words: (value) => invoke("update_custom_words", { words: value }),
   word_correction_threshold: (value) =>
     invoke("change_word_correction_threshold_setting", { threshold: value }),
+  custom_words_fuzzy_correction: (value) =>
+    invoke("change_custom_words_fuzzy_correction_setting", { enabled: value }),
+  remote_transcription_enabled: (value) =>
+    invoke("change_remote_transcription_enabled_setting", { enabled: value }),
+  remote_transcription_provider_id: (value) =>
+    invoke("change_remote_transcription_provider_setting", {
+      providerId: value,
+    }),
+  remote_transcription_model: (value) =>
+    invoke("change_remote_transcription_model_setting", { model: value }),
+  hallucination_filter_enabled: (value) =>
+    invoke("change_hallucination_filter_enabled_setting", { enabled: value }),
+  hallucination_phrases: (value) =>
+    invoke("update_hallucination_phrases", { phrases: value }),
+  hallucination_min_speech_ms: (value) =>
+    invoke("change_hallucination_min_speech_ms_setting", {
+      minSpeechMs: value,
+    }),
+  model_catalog_source: (value) =>
+    invoke("set_model_catalog_source", { source: value ?? null }),
+  model_mirrors: (value) => invoke("update_model_mirrors", { mirrors: value }),
+  max_concurrent_downloads: (value) =>
+    invoke("change_max_concurrent_downloads_setting", {
+      maxConcurrent: value,
+    }),
+  download_bandwidth_limit: (value) =>
+    invoke("change_download_bandwidth_limit_setting", {
+      bytesPerSecond: value ?? null,
+    }),
   paste_method: (value) =>
     invoke("change_paste_method_setting", { method: value }),
   clipboard_handling: (value) =>
@@ -311,6 +374,11 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
       refreshOutputDevices(),
       checkCustomSounds(),
     ]);
+
+    // The recorder moved to another microphone after a disconnect or hot-plug
+    listen<string>("microphone-changed", () => {
+      get().refreshAudioDevices();
+    });
   },
 
   updateSetting: async (key, value) => {